@@ -0,0 +1,81 @@
+//! Compares ray queries of `SceneList` accelerated with `Bvh` against the naive scan of all the objects.
+//! Run with `cargo bench --bench bvh`
+
+use {
+    rustyengine::{engn::*, math::*},
+    std::{rc::Rc, time::Instant},
+    uuid::Uuid,
+};
+
+/// Rows and columns of rays that is the usual terminal canvas
+const RAYS: (usize, usize) = (60, 200);
+
+/// Grids of balls with the given number of balls along each axis
+const SIDES: [usize; 4] = [2, 5, 10, 20];
+
+fn scene(side: usize, accel: bool) -> SceneList {
+    let mut scene = SceneList::new().with_bvh(accel);
+    for x in 0..side {
+        for y in 0..side {
+            let center = vec![
+                5.0 + 3.0 * x as f64,
+                -1.5 * side as f64 + 3.0 * y as f64,
+                (x * y % 5) as f64 - 2.0,
+            ];
+            scene.append(Box::new(
+                HypeEllipse::new(
+                    Entity::new(Rc::new(Uuid::new_v4())),
+                    Point::new(center),
+                    Basis::default(),
+                    vec![1.0; 3],
+                    None,
+                )
+                .unwrap(),
+            ));
+        }
+    }
+    scene
+}
+
+/// Seconds spent to cast all the rays of canvas, number of hits
+fn frame(scene: &SceneList, cs: &CoordSys) -> (f64, usize) {
+    let inc = Point::default();
+    let start = Instant::now();
+    let mut hits = 0;
+    for r in 0..RAYS.0 {
+        for c in 0..RAYS.1 {
+            let dir = Vector::new(vec![
+                1.0,
+                (c as f64 / RAYS.1 as f64 - 0.5) * 2.0,
+                (r as f64 / RAYS.0 as f64 - 0.5) * 0.6,
+            ]);
+            if scene.collide(cs, &inc, &dir).is_some() {
+                hits += 1;
+            }
+        }
+    }
+    (start.elapsed().as_secs_f64(), hits)
+}
+
+fn main() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    println!(
+        "{:>8} {:>12} {:>12} {:>8}",
+        "objects", "naive, ms", "bvh, ms", "speedup"
+    );
+    for side in SIDES {
+        let (naive, naive_hits) = frame(&scene(side, false), &cs);
+        let accel_scene = scene(side, true);
+        frame(&accel_scene, &cs);
+        let (accel, accel_hits) = frame(&accel_scene, &cs);
+        assert_eq!(naive_hits, accel_hits);
+        println!(
+            "{:>8} {:>12.1} {:>12.1} {:>8.1}",
+            side * side,
+            naive * 1e3,
+            accel * 1e3,
+            naive / accel
+        );
+    }
+}
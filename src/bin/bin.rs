@@ -11,7 +11,7 @@ use {
 fn main() -> Result<()> {
     let mut conf = Conf::read(vec!["src/bin/conf.toml"])?;
     conf.initpt = gen_init_pos();
-    let mut scene = Scene::new(conf.draw_dist)?;
+    let mut scene = Scene::new(&conf)?;
     scene.expand();
     let es = MovementEventSys::new(STEP);
     let mut game = Game::<MovementEvent<Scene>, MovementEventSys, Scene>::new(conf, scene, es)?;
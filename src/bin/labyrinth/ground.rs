@@ -41,4 +41,8 @@ impl AsCollided for Ground {
     fn charmap(&self, _dist: f64) -> Option<char> {
         None
     }
+
+    fn normal(&self, _cs: &CoordSys, _pt: &Point) -> Option<Vector> {
+        Some(Vector::new(vec![0.0, 0.0, 1.0]))
+    }
 }
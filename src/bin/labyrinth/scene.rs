@@ -1,11 +1,11 @@
 use {
     crate::labyrinth::{ground::*, walls::*},
-    either::Either,
     rand::Rng,
     rustyengine::{conf::*, engn::*, errs::*, math::*},
     std::{
         cmp::Ordering,
         ops::{Div, Mul},
+        rc::Rc,
     },
 };
 
@@ -23,10 +23,12 @@ pub struct Scene {
     ground: Ground,
     ground_charcoal: Charcoal,
     sun: HypeEllipse,
+    lighting: Lighting,
 }
 
 impl Scene {
-    pub fn new(draw_dist: f64) -> ReRes<Self> {
+    pub fn new(conf: &Conf) -> ReRes<Self> {
+        let draw_dist = conf.draw_dist;
         let xz_walls = [
             XzWalls::new(Entity::new(IdPool::get().generate()), 0.0, vec![0.0, 10.0]),
             XzWalls::new(
@@ -132,21 +134,23 @@ impl Scene {
 
         Ok(Self {
             xz_walls,
-            xz_charcoal: Charcoal::new("><".to_string(), 6.0),
+            xz_charcoal: Charcoal::new("#%=+-:.".to_string(), draw_dist).with_fg(Rgb(200, 120, 60)),
 
             yz_walls,
-            yz_charcoal: Charcoal::new("^\"".to_string(), 6.0),
+            yz_charcoal: Charcoal::new("@&*~^',".to_string(), draw_dist).with_fg(Rgb(170, 90, 40)),
 
             ground: Ground::new(Entity::new(IdPool::get().generate())),
-            ground_charcoal: Charcoal::new("#$?".to_string(), draw_dist),
+            ground_charcoal: Charcoal::new("#$?".to_string(), draw_dist).with_fg(Rgb(60, 160, 60)),
 
             sun: HypeEllipse::new(
                 Entity::new(IdPool::get().generate()),
                 Point::new(vec![-10.0, -10.0, 80.0]),
                 Basis::new(Matrix::identity(3).to_multicol())?,
                 vec![20.0, 20.0, 20.0],
-                Some(Charcoal::new("0Oo".to_string(), draw_dist)),
+                Some(Charcoal::new("0Oo".to_string(), draw_dist).with_fg(Rgb(255, 220, 0))),
             )?,
+
+            lighting: Lighting::from_conf(conf),
         })
     }
 
@@ -166,130 +170,96 @@ impl Scene {
         }
     }
 
-    pub fn collision_ag_xz_walls(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
-        let mut collision = None;
-        match dir[1].partial_cmp(&0.0) {
+    /// The nearest hit of walls collinear to Oxz plane
+    pub fn collision_ag_xz_walls(
+        &self,
+        cs: &CoordSys,
+        inc: &Point,
+        dir: &Vector,
+    ) -> Option<Hit<'_>> {
+        let hit = match dir[1].partial_cmp(&0.0) {
             Some(Ordering::Greater) => {
                 let rng = match inc[1] < 0.0 {
                     true => 0..XZWALLS,
                     false => (inc[1] / PASSAGE + 1.0).floor() as usize..XZWALLS,
                 };
-                for i in rng {
-                    if let Some(dist) = self.xz_walls[i].collide(cs, inc, dir) {
-                        if collision.is_none() || dist < collision.unwrap() {
-                            collision = Some(dist);
-                        }
-                        break;
-                    }
-                }
+                rng.into_iter()
+                    .find_map(|i| self.xz_walls[i].hit(cs, inc, dir))
             }
             Some(Ordering::Less) => {
                 let rng = match BACKWALL < inc[1] {
                     true => (0..XZWALLS).rev(),
                     false => (0..(inc[1] / PASSAGE).ceil() as usize).rev(),
                 };
-                for i in rng {
-                    if let Some(dist) = self.xz_walls[i].collide(cs, inc, dir) {
-                        if collision.is_none() || dist < collision.unwrap() {
-                            collision = Some(dist);
-                        }
-                        break;
-                    }
-                }
+                rng.into_iter()
+                    .find_map(|i| self.xz_walls[i].hit(cs, inc, dir))
             }
-            _ => return None,
+            _ => None,
         };
-        collision
+        hit.map(|hit| hit.with_charcoal(&self.xz_charcoal))
     }
 
-    pub fn collision_ag_yz_walls(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
-        let mut collision = None;
-
-        match dir[0].partial_cmp(&0.0) {
+    /// The nearest hit of walls collinear to Oyz plane
+    pub fn collision_ag_yz_walls(
+        &self,
+        cs: &CoordSys,
+        inc: &Point,
+        dir: &Vector,
+    ) -> Option<Hit<'_>> {
+        let hit = match dir[0].partial_cmp(&0.0) {
             Some(Ordering::Greater) => {
                 let rng = match inc[0] < 0.0 {
                     true => 0..YZWALLS,
                     false => (inc[0] / PASSAGE + 1.0).floor() as usize..YZWALLS,
                 };
-                for i in rng {
-                    if let Some(dist) = self.yz_walls[i].collide(cs, inc, dir) {
-                        if collision.is_none() || dist < collision.unwrap() {
-                            collision = Some(dist);
-                        }
-                        break;
-                    }
-                }
+                rng.into_iter()
+                    .find_map(|i| self.yz_walls[i].hit(cs, inc, dir))
             }
             Some(Ordering::Less) => {
                 let rng = match BACKWALL < inc[0] {
                     true => (0..YZWALLS).rev(),
                     false => (0..(inc[0] / PASSAGE).ceil() as usize).rev(),
                 };
-                for i in rng {
-                    if let Some(dist) = self.yz_walls[i].collide(cs, inc, dir) {
-                        if collision.is_none() || dist < collision.unwrap() {
-                            collision = Some(dist);
-                        }
-                        break;
-                    }
-                }
+                rng.into_iter()
+                    .find_map(|i| self.yz_walls[i].hit(cs, inc, dir))
             }
-            _ => return None,
+            _ => None,
         };
-        collision
+        hit.map(|hit| hit.with_charcoal(&self.yz_charcoal))
     }
 }
 
 impl AsScene for Scene {
-    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Either<f64, char> {
-        let mut collision: Option<(f64, char)> = None;
-
-        if let Some(dist) = self.collision_ag_xz_walls(cs, inc, dir) {
-            if collision.is_none() || dist < collision.unwrap().0 {
-                collision = Some((dist, self.xz_charcoal.ignite(dist)));
-            }
-        }
-
-        if let Some(dist) = self.collision_ag_yz_walls(cs, inc, dir) {
-            if collision.is_none() || dist < collision.unwrap().0 {
-                collision = Some((dist, self.yz_charcoal.ignite(dist)));
-            }
-        }
-
-        if let Some(dist) = self.ground.collide(cs, inc, dir) {
-            if collision.is_none() || dist < collision.unwrap().0 {
-                collision = Some((dist, self.ground_charcoal.ignite(dist)));
-            }
-        }
-
-        if let Some(dist) = self.sun.collide(cs, inc, dir) {
-            if collision.is_none() || dist < collision.unwrap().0 {
-                collision = Some((dist, self.sun.charcoal.as_ref().unwrap().ignite(dist)));
-            }
-        }
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        Hit::nearest([
+            self.collision_ag_xz_walls(cs, inc, dir),
+            self.collision_ag_yz_walls(cs, inc, dir),
+            self.ground
+                .hit(cs, inc, dir)
+                .map(|hit| hit.with_charcoal(&self.ground_charcoal)),
+            self.sun.hit(cs, inc, dir),
+        ])
+    }
 
-        if let Some((_, c)) = collision {
-            Either::Right(c)
-        } else {
-            Either::Left(-1.0)
+    /// The sun shines itself, other surfaces are lit according to their normals
+    fn shade(&self, cs: &CoordSys, dir: &Vector, hit: &Hit) -> Option<Cell> {
+        let charcoal = hit.charcoal?;
+        match &hit.normal {
+            Some(normal) if !Rc::ptr_eq(&hit.id, self.sun.id()) => Some(
+                self.lighting
+                    .shade(charcoal, cs, &hit.pt, normal, dir, hit.dist),
+            ),
+            _ => Some(charcoal.paint(hit.dist)),
         }
     }
 
     fn validate_mv(&self, cs: &CoordSys, pos: &Point, mv: &mut Vector) {
-        let mut collision = None;
-        if let Some(dist) = self.collision_ag_xz_walls(cs, pos, mv) {
-            if collision.is_none() || dist < collision.unwrap() {
-                collision = Some(dist);
-            }
-        }
-        if let Some(dist) = self.collision_ag_yz_walls(cs, pos, mv) {
-            if collision.is_none() || dist < collision.unwrap() {
-                collision = Some(dist);
-            }
-        }
-
-        if let Some(dist) = collision {
-            if dist < 1.0 {
+        let collision = Hit::nearest([
+            self.collision_ag_xz_walls(cs, pos, mv),
+            self.collision_ag_yz_walls(cs, pos, mv),
+        ]);
+        if let Some(hit) = collision {
+            if hit.dist < 1.0 {
                 *mv = Vector::new(vec![0.0; 3])
             }
         }
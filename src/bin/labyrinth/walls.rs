@@ -79,6 +79,17 @@ impl AsCollided for XzWalls {
     fn charmap(&self, _dist: f64) -> Option<char> {
         None
     }
+
+    fn normal(&self, _cs: &CoordSys, _pt: &Point) -> Option<Vector> {
+        Some(Vector::new(vec![0.0, 1.0, 0.0]))
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        Some(Aabb::new(
+            vec![self.x_seg.first()?.0, self.y0, 0.0],
+            vec![self.x_seg.last()?.0, self.y0, H],
+        ))
+    }
 }
 
 /// Part of the entire plane that is right rectangle and collinear to Oyz plane.
@@ -153,4 +164,15 @@ impl AsCollided for YzWalls {
     fn charmap(&self, _dist: f64) -> Option<char> {
         None
     }
+
+    fn normal(&self, _cs: &CoordSys, _pt: &Point) -> Option<Vector> {
+        Some(Vector::new(vec![1.0, 0.0, 0.0]))
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        Some(Aabb::new(
+            vec![self.x0, self.y_seg.first()?.0, 0.0],
+            vec![self.x0, self.y_seg.last()?.0, H],
+        ))
+    }
 }
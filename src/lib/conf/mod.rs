@@ -15,7 +15,7 @@ use {
         },
         math::*,
     },
-    std::{f64::consts::PI, fs::read_to_string},
+    std::{f64::consts::PI, fs::read_to_string, str::FromStr},
     toml::{Table, Value},
 };
 
@@ -26,6 +26,15 @@ const HFOV_KEY: &str = "VERTICAL_FIELD_OF_VIEW_OUT_OF_PI";
 const DRAW_DIST_KEY: &str = "DRAW_DISTANCE";
 const CHARMAP_KEY: &str = "CHARMAP";
 const PRECISION_KEY: &str = "PRECISION";
+const COLOR_MODE_KEY: &str = "COLOR_MODE";
+const RENDER_MODE_KEY: &str = "RENDER_MODE";
+const PROJECTION_KEY: &str = "PROJECTION";
+const VIEW_WIDTH_KEY: &str = "VIEW_WIDTH";
+const AMBIENT_KEY: &str = "AMBIENT_LIGHT";
+const LIGHTS_KEY: &str = "LIGHTS";
+const LIGHT_DIR_KEY: &str = "DIRECTION";
+const LIGHT_POS_KEY: &str = "POSITION";
+const LIGHT_INTENSITY_KEY: &str = "INTENSITY";
 
 /// Struct that packages configuration parameters,
 /// it further is used for `Game` object instanciating
@@ -38,6 +47,12 @@ pub struct Conf {
     pub draw_dist: f64,
     pub charmap: String,
     pub precision: u8,
+    pub color_mode: ColorMode,
+    pub render_mode: RenderMode,
+    pub projection: Projection,
+    pub view_width: f64,
+    pub ambient: f64,
+    pub lights: Vec<Light>,
 }
 
 impl Conf {
@@ -61,7 +76,13 @@ impl Conf {
                 .parse_hfov(&mut table)?
                 .parse_draw_dist(&mut table)?
                 .parse_charmap(&mut table)?
-                .parse_precision(&mut table)?;
+                .parse_precision(&mut table)?
+                .parse_color_mode(&mut table)?
+                .parse_render_mode(&mut table)?
+                .parse_projection(&mut table)?
+                .parse_view_width(&mut table)?
+                .parse_ambient(&mut table)?
+                .parse_lights(&mut table)?;
         }
         Ok(conf)
     }
@@ -151,6 +172,123 @@ impl Conf {
         }
         Ok(self)
     }
+
+    /// Parses `COLOR_MODE` parameter that is one of `"mono"`, `"16"`, `"256"`, `"truecolor"`
+    pub fn parse_color_mode(mut self, table: &mut Table) -> ReRes<Self> {
+        let value = match table.remove(COLOR_MODE_KEY) {
+            Some(value) => value,
+            None => return Ok(self),
+        };
+        let mode = match value {
+            Value::String(mode) => mode,
+            Value::Integer(mode) => mode.to_string(),
+            _ => return Err(GameErr(InvalidConfValue(COLOR_MODE_KEY))),
+        };
+        match ColorMode::from_str(&mode) {
+            Ok(mode) => self.color_mode = mode,
+            Err(_) => return Err(GameErr(InvalidConfValue(COLOR_MODE_KEY))),
+        }
+        Ok(self)
+    }
+
+    /// Parses `RENDER_MODE` parameter that is one of `"chars"`, `"halfblock"`, `"braille"`
+    pub fn parse_render_mode(mut self, table: &mut Table) -> ReRes<Self> {
+        let value = match table.remove(RENDER_MODE_KEY) {
+            Some(value) => value,
+            None => return Ok(self),
+        };
+        match value {
+            Value::String(mode) => match RenderMode::from_str(&mode) {
+                Ok(mode) => self.render_mode = mode,
+                Err(_) => return Err(GameErr(InvalidConfValue(RENDER_MODE_KEY))),
+            },
+            _ => return Err(GameErr(InvalidConfValue(RENDER_MODE_KEY))),
+        }
+        Ok(self)
+    }
+
+    /// Parses `PROJECTION` parameter that is one of `"perspective"`, `"orthographic"`, `"fisheye"`, `"panoramic"`
+    pub fn parse_projection(mut self, table: &mut Table) -> ReRes<Self> {
+        let value = match table.remove(PROJECTION_KEY) {
+            Some(value) => value,
+            None => return Ok(self),
+        };
+        match value {
+            Value::String(projection) => match Projection::from_str(&projection) {
+                Ok(projection) => self.projection = projection,
+                Err(_) => return Err(GameErr(InvalidConfValue(PROJECTION_KEY))),
+            },
+            _ => return Err(GameErr(InvalidConfValue(PROJECTION_KEY))),
+        }
+        Ok(self)
+    }
+
+    /// Parses `VIEW_WIDTH` parameter that is width of the view plane of orthographic projection
+    pub fn parse_view_width(mut self, table: &mut Table) -> ReRes<Self> {
+        let value = match table.remove(VIEW_WIDTH_KEY) {
+            Some(value) => value,
+            None => return Ok(self),
+        };
+        match value {
+            Value::Integer(width) => self.view_width = width as f64,
+            Value::Float(width) => self.view_width = width,
+            _ => return Err(GameErr(InvalidConfValue(VIEW_WIDTH_KEY))),
+        }
+        Ok(self)
+    }
+
+    /// Parses `AMBIENT_LIGHT` parameter that is brightness of surfaces without any lights
+    pub fn parse_ambient(mut self, table: &mut Table) -> ReRes<Self> {
+        let value = match table.remove(AMBIENT_KEY) {
+            Some(value) => value,
+            None => return Ok(self),
+        };
+        match value {
+            Value::Integer(ambient) => self.ambient = ambient as f64,
+            Value::Float(ambient) => self.ambient = ambient,
+            _ => return Err(GameErr(InvalidConfValue(AMBIENT_KEY))),
+        }
+        Ok(self)
+    }
+
+    /// Parses `LIGHTS` parameter that is array of tables, each one has either `DIRECTION`
+    /// of directional light or `POSITION` of point light and optional `INTENSITY` that is 1 by default
+    pub fn parse_lights(mut self, table: &mut Table) -> ReRes<Self> {
+        let value = match table.remove(LIGHTS_KEY) {
+            Some(value) => value,
+            None => return Ok(self),
+        };
+        let array = match value {
+            Value::Array(array) => array,
+            _ => return Err(GameErr(InvalidConfValue(LIGHTS_KEY))),
+        };
+        let mut lights = vec![];
+        for val in array {
+            let mut light = match val {
+                Value::Table(light) => light,
+                _ => return Err(GameErr(InvalidConfValue(LIGHTS_KEY))),
+            };
+            let intensity = match light.remove(LIGHT_INTENSITY_KEY) {
+                Some(Value::Integer(intensity)) => intensity as f64,
+                Some(Value::Float(intensity)) => intensity,
+                None => 1.0,
+                _ => return Err(GameErr(InvalidConfValue(LIGHT_INTENSITY_KEY))),
+            };
+            match (light.remove(LIGHT_DIR_KEY), light.remove(LIGHT_POS_KEY)) {
+                (Some(dir), None) => lights.push(Light::directional(
+                    Vector::new(parse_single(dir, LIGHT_DIR_KEY)?),
+                    intensity,
+                )),
+                (None, Some(pos)) => lights.push(Light::point(
+                    Point::new(parse_single(pos, LIGHT_POS_KEY)?),
+                    intensity,
+                )),
+                _ => return Err(GameErr(InvalidConfValue(LIGHTS_KEY))),
+            }
+        }
+        self.lights = lights;
+        Ok(self)
+    }
 }
 
 /// Parses `Vec<f64>` parameter from the `toml::Value::Array(toml::Array)`.
@@ -184,6 +322,12 @@ impl Default for Conf {
             draw_dist: 100.0,
             charmap: "$@&%#WMNB8RGAHP694XKYJOUVIL*+:-·".to_string(),
             precision: 100,
+            color_mode: ColorMode::Mono,
+            render_mode: RenderMode::Chars,
+            projection: Projection::Perspective,
+            view_width: 20.0,
+            ambient: 1.0,
+            lights: vec![],
         }
     }
 }
@@ -45,11 +45,65 @@ fn conf_fov_double_assignment() {
 }
 
 #[test]
-fn conf_default_double_assignment() {
+fn conf_double_assignment_keeps_unset_defaults() {
     let conf = Conf::read(vec![
         "src/lib/conf/test/conf1.toml",
         "src/lib/conf/test/conf2.toml",
     ])
     .unwrap();
-    assert_eq!(conf.wscr, 100);
+    let default = Conf::default();
+    assert_eq!(conf.angle_discr, default.angle_discr);
+    assert_eq!(conf.charmap, default.charmap);
+    assert_eq!(conf.precision, default.precision);
+}
+
+#[test]
+fn color_mode_from_conf() {
+    let conf = Conf::read(vec!["src/lib/conf/test/conf3.toml"]).unwrap();
+    assert_eq!(conf.color_mode, crate::engn::ColorMode::TrueColor);
+}
+
+#[test]
+fn color_mode_default() {
+    let conf = Conf::read(vec!["src/lib/conf/test/conf1.toml"]).unwrap();
+    assert_eq!(conf.color_mode, crate::engn::ColorMode::Mono);
+}
+
+#[test]
+fn render_mode_from_conf() {
+    let conf = Conf::read(vec!["src/lib/conf/test/conf3.toml"]).unwrap();
+    assert_eq!(conf.render_mode, crate::engn::RenderMode::Braille);
+}
+
+#[test]
+fn lights_from_conf() {
+    let conf = Conf::read(vec!["src/lib/conf/test/conf4.toml"]).unwrap();
+    assert_eq!(conf.ambient, 0.2);
+    assert_eq!(
+        conf.lights,
+        vec![
+            crate::engn::Light::directional(Vector::new(vec![0.0, 0.0, -1.0]), 0.5),
+            crate::engn::Light::point(Point::new(vec![1.0, 2.0, 3.0]), 1.0),
+        ]
+    );
+}
+
+#[test]
+fn lights_default() {
+    let conf = Conf::read(vec!["src/lib/conf/test/conf1.toml"]).unwrap();
+    assert_eq!(conf.ambient, 1.0);
+    assert!(conf.lights.is_empty());
+}
+
+#[test]
+fn projection_from_conf() {
+    let conf = Conf::read(vec!["src/lib/conf/test/conf5.toml"]).unwrap();
+    assert_eq!(conf.projection, crate::engn::Projection::Fisheye);
+    assert_eq!(conf.view_width, 12.0);
+}
+
+#[test]
+fn projection_default() {
+    let conf = Conf::read(vec!["src/lib/conf/test/conf1.toml"]).unwrap();
+    assert_eq!(conf.projection, crate::engn::Projection::Perspective);
 }
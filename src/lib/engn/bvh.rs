@@ -0,0 +1,273 @@
+//! Bounding volume hierarchy over axis-aligned bounds of objects, that speeds up ray queries of scenes.
+//! Hierarchy stores indices of objects, so it's usable by any scene keeping objects in slice.
+//! Objects without bounds, eg infinite planes, are kept aside and tested by each ray
+
+use crate::{engn::*, math::*};
+
+/// Maximal number of objects in the leaf of hierarchy
+const LEAF_SIZE: usize = 2;
+
+/// Tolerance of slab test, so rays touching the flat bounds aren't missed
+const SLAB_EPS: f64 = 1e-9;
+
+/// Axis-aligned bounding box given by its minimal and maximal corners
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aabb {
+    pub(crate) min: Vec<f64>,
+    pub(crate) max: Vec<f64>,
+}
+
+impl Aabb {
+    /// Box with the given corners, coordinates are swapped if `min` is greater than `max`
+    pub fn new(mut min: Vec<f64>, mut max: Vec<f64>) -> Self {
+        for i in 0..min.len().min(max.len()) {
+            if max[i] < min[i] {
+                std::mem::swap(&mut min[i], &mut max[i]);
+            }
+        }
+        Self { min, max }
+    }
+
+    /// Box of the given half sizes `extent` around `center`
+    pub fn around(center: &Point, extent: &[f64]) -> Self {
+        Self::new(
+            (0..center.dim()).map(|i| center[i] - extent[i]).collect(),
+            (0..center.dim()).map(|i| center[i] + extent[i]).collect(),
+        )
+    }
+
+    pub fn min(&self) -> &Vec<f64> {
+        &self.min
+    }
+
+    pub fn max(&self) -> &Vec<f64> {
+        &self.max
+    }
+
+    pub fn dim(&self) -> usize {
+        self.min.len()
+    }
+
+    /// The least box containing both boxes
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: self
+                .min
+                .iter()
+                .zip(&other.min)
+                .map(|(l, r)| l.min(*r))
+                .collect(),
+            max: self
+                .max
+                .iter()
+                .zip(&other.max)
+                .map(|(l, r)| l.max(*r))
+                .collect(),
+        }
+    }
+
+    /// Center of the box along axis `i`
+    pub fn centroid(&self, i: usize) -> f64 {
+        (self.min[i] + self.max[i]) / 2.0
+    }
+
+    /// Range of ray resizing coefficients inside the box by slab method, `None` if ray misses it.
+    /// Range starts with `0.0` if ray goes from inside the box
+    pub fn intersect(&self, inc: &Point, dir: &Vector) -> Option<(f64, f64)> {
+        let (mut tmin, mut tmax) = (f64::NEG_INFINITY, f64::INFINITY);
+        for i in 0..self.dim() {
+            let (o, d) = (inc[i], dir.at(i));
+            if d == 0.0 {
+                if o < self.min[i] - SLAB_EPS || self.max[i] + SLAB_EPS < o {
+                    return None;
+                }
+                continue;
+            }
+            let (t1, t2) = ((self.min[i] - o) / d, (self.max[i] - o) / d);
+            tmin = tmin.max(t1.min(t2));
+            tmax = tmax.min(t1.max(t2));
+        }
+        if tmin > tmax + SLAB_EPS || tmax < -SLAB_EPS {
+            None
+        } else {
+            Some((tmin.max(0.0), tmax))
+        }
+    }
+}
+
+/// Node of hierarchy that is either leaf with indices of objects or branch with two children
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf {
+        bounds: Aabb,
+        items: Vec<usize>,
+    },
+    Branch {
+        bounds: Aabb,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl Node {
+    fn bounds(&self) -> &Aabb {
+        match self {
+            Self::Leaf { bounds, .. } | Self::Branch { bounds, .. } => bounds,
+        }
+    }
+}
+
+/// Bounding volume hierarchy over indices of objects. Built by median split along the longest axis
+/// of objects centroids, children are always stored after their parent
+#[derive(Debug, Clone, Default)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    unbounded: Vec<usize>,
+    len: usize,
+}
+
+impl Bvh {
+    /// Builds hierarchy where `bounds[i]` are bounds of the object with index `i`
+    pub fn build(bounds: &[Option<Aabb>]) -> Self {
+        let mut bvh = Self {
+            nodes: vec![],
+            unbounded: vec![],
+            len: bounds.len(),
+        };
+        let mut items = vec![];
+        for (i, aabb) in bounds.iter().enumerate() {
+            match aabb {
+                Some(aabb) => items.push((i, aabb.clone())),
+                None => bvh.unbounded.push(i),
+            }
+        }
+        if !items.is_empty() {
+            bvh.build_node(items);
+        }
+        bvh
+    }
+
+    fn build_node(&mut self, mut items: Vec<(usize, Aabb)>) -> usize {
+        let bounds = items[1..]
+            .iter()
+            .fold(items[0].1.clone(), |acc, (_, aabb)| acc.union(aabb));
+        let idx = self.nodes.len();
+        if items.len() <= LEAF_SIZE {
+            self.nodes.push(Node::Leaf {
+                bounds,
+                items: items.iter().map(|(i, _)| *i).collect(),
+            });
+            return idx;
+        }
+
+        let axis = (0..bounds.dim())
+            .max_by_key(|i| {
+                let centroids = items.iter().map(|(_, aabb)| Float(aabb.centroid(*i)));
+                let (min, max) = (centroids.clone().min(), centroids.max());
+                Float(max.unwrap().0 - min.unwrap().0)
+            })
+            .unwrap();
+        items.sort_by_key(|(_, aabb)| Float(aabb.centroid(axis)));
+        let rest = items.split_off(items.len() / 2);
+
+        self.nodes.push(Node::Branch {
+            bounds,
+            left: 0,
+            right: 0,
+        });
+        let (l, r) = (self.build_node(items), self.build_node(rest));
+        if let Node::Branch { left, right, .. } = &mut self.nodes[idx] {
+            (*left, *right) = (l, r);
+        }
+        idx
+    }
+
+    /// Recomputes bounds of nodes keeping their topology, that is enough after objects are moved.
+    /// Hierarchy is rebuilt if objects are added, removed or got or lost their bounds
+    pub fn refit(&mut self, bounds: &[Option<Aabb>]) {
+        let unbounded = bounds
+            .iter()
+            .enumerate()
+            .filter(|(_, aabb)| aabb.is_none())
+            .map(|(i, _)| i);
+        if bounds.len() != self.len || !unbounded.eq(self.unbounded.iter().copied()) {
+            *self = Self::build(bounds);
+            return;
+        }
+        for idx in (0..self.nodes.len()).rev() {
+            let refitted = match &self.nodes[idx] {
+                Node::Leaf { items, .. } => items[1..]
+                    .iter()
+                    .fold(bounds[items[0]].clone().unwrap(), |acc, i| {
+                        acc.union(bounds[*i].as_ref().unwrap())
+                    }),
+                Node::Branch { left, right, .. } => self.nodes[*left]
+                    .bounds()
+                    .union(self.nodes[*right].bounds()),
+            };
+            match &mut self.nodes[idx] {
+                Node::Leaf { bounds, .. } | Node::Branch { bounds, .. } => *bounds = refitted,
+            }
+        }
+    }
+
+    /// Bounds of all the bounded objects, `None` if there are no such objects
+    pub fn bounds(&self) -> Option<&Aabb> {
+        self.nodes.first().map(|node| node.bounds())
+    }
+
+    /// Number of objects hierarchy is built for
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The nearest hit of ray, where `hit(i)` collides ray with the object of index `i`.
+    /// Only objects which bounds are crossed by ray closer than the nearest found hit are tested
+    pub fn nearest<'h>(
+        &self,
+        inc: &Point,
+        dir: &Vector,
+        mut hit: impl FnMut(usize) -> Option<Hit<'h>>,
+    ) -> Option<Hit<'h>> {
+        let mut nearest = Hit::nearest(self.unbounded.iter().map(|i| hit(*i)));
+        let closer = |nearest: &Option<Hit>, t: f64| nearest.as_ref().map_or(true, |h| t <= h.dist);
+
+        let mut stack = match self
+            .nodes
+            .first()
+            .and_then(|root| root.bounds().intersect(inc, dir))
+        {
+            Some((tmin, _)) => vec![(0, tmin)],
+            None => vec![],
+        };
+        while let Some((idx, tmin)) = stack.pop() {
+            if !closer(&nearest, tmin) {
+                continue;
+            }
+            match &self.nodes[idx] {
+                Node::Leaf { items, .. } => {
+                    for i in items {
+                        if let Some(h) = hit(*i) {
+                            if closer(&nearest, h.dist) {
+                                nearest = Some(h);
+                            }
+                        }
+                    }
+                }
+                Node::Branch { left, right, .. } => {
+                    let mut children = [*left, *right]
+                        .into_iter()
+                        .filter_map(|c| Some((c, self.nodes[c].bounds().intersect(inc, dir)?.0)))
+                        .collect::<Vec<(usize, f64)>>();
+                    children.sort_by_key(|(_, t)| Float(-t));
+                    stack.extend(children);
+                }
+            }
+        }
+        nearest
+    }
+}
@@ -15,73 +15,101 @@ use {
         f64::consts::{FRAC_PI_2, PI},
         rc::Rc,
     },
+    strum_macros::{Display, EnumString},
     uuid::Uuid,
 };
 
-#[derive(Debug)]
-pub struct Vision {
-    // first - zenith rotation
-    // second - azimut rotation
-    // third - rows of rays
-    // fourth - columns of rays
-    rays: Vec<Vec<Vec<Vec<Vector>>>>,
+/// Ways of casting rays of camera, selected in `Conf` with `PROJECTION` key
+#[derive(Debug, Display, EnumString, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Projection {
+    /// Rectilinear perspective, rays go from camera position through the view plane
+    #[default]
+    #[strum(serialize = "perspective")]
+    Perspective,
+    /// Parallel rays along view direction going from the view plane of `VIEW_WIDTH` centered at camera position,
+    /// its height keeps the ratio of fields of view
+    #[strum(serialize = "orthographic")]
+    Orthographic,
+    /// Equidistant fisheye, angle of ray from view direction grows linearly with distance from center of view,
+    /// so fields of view may exceed `PI`
+    #[strum(serialize = "fisheye")]
+    Fisheye,
+    /// Cylindrical panorama covering the whole circle around camera horizontally, vertical field of view is kept
+    #[strum(serialize = "panoramic")]
+    Panoramic,
 }
 
-impl Vision {
-    pub(crate) fn new(discr: usize, wfov: f64, hfov: f64, size: (usize, usize)) -> Self {
-        let mut rays: Vec<Vec<Vec<Vec<Vector>>>> =
-            vec![vec![vec![vec![]; size.0]; 4 * discr]; 2 * discr - 1];
-
-        let angle_step = FRAC_PI_2 / (discr as f64);
-
-        rays[discr - 1][0] = init_rays(wfov, hfov, size.1, size.0);
-
-        let rot = &Matrix::rotation(0, 2, angle_step, 3);
-        for i in (0..(discr - 1)).rev() {
-            for r in 0..size.0 {
-                for c in 0..size.1 {
-                    let coord = rot.mul(rays[i + 1][0][r][c].coord()).to_col();
-                    rays[i][0][r].push((Vector { coord }))
+impl Projection {
+    /// Direction and origin offset of ray in camera frame for the point of view `(sy, sz)` within [-1, 1],
+    /// it's the left and top edges of view if positive
+    pub(crate) fn sample(
+        &self,
+        sy: f64,
+        sz: f64,
+        fov: (f64, f64),
+        width: f64,
+    ) -> ([f64; 3], [f64; 3]) {
+        let (ay, az) = (sy * fov.0 / 2.0, sz * fov.1 / 2.0);
+        match self {
+            Self::Perspective => ([1.0, ay.tan(), az.tan()], [0.0; 3]),
+            Self::Orthographic => (
+                [1.0, 0.0, 0.0],
+                [0.0, sy * width / 2.0, sz * width * fov.1 / fov.0 / 2.0],
+            ),
+            Self::Fisheye => {
+                let angle = ay.hypot(az);
+                match angle > 0.0 {
+                    true => {
+                        let sin = angle.sin() / angle;
+                        ([angle.cos(), ay * sin, az * sin], [0.0; 3])
+                    }
+                    false => ([1.0, 0.0, 0.0], [0.0; 3]),
                 }
             }
-        }
-
-        let rot = &Matrix::rotation(0, 2, -angle_step, 3);
-        for i in discr..(2 * discr - 1) {
-            for r in 0..size.0 {
-                for c in 0..size.1 {
-                    let coord = rot.mul(rays[i - 1][0][r][c].coord()).to_col();
-                    rays[i][0][r].push(Vector { coord })
-                }
+            Self::Panoramic => {
+                let phi = sy * PI;
+                ([phi.cos(), phi.sin(), az.tan()], [0.0; 3])
             }
         }
+    }
 
-        let rot = &Matrix::rotation(0, 1, angle_step, 3);
-        for i in 0..(2 * discr - 1) {
-            for j in 1..(4 * discr) {
-                for r in 0..size.0 {
-                    for c in 0..size.1 {
-                        let coord = rot.mul(rays[i][j - 1][r][c].coord()).to_col();
-                        rays[i][j][r].push(Vector { coord })
-                    }
-                }
-            }
+    /// Horizontal point of view of sample `pos` out of `count`. Panorama closes the circle, so its samples
+    /// are centers of `count` equal parts of view and the first one doesn't repeat the last one
+    fn view_y(&self, pos: f64, count: usize) -> f64 {
+        match self {
+            Self::Panoramic => 1.0 - (2.0 * pos + 1.0) / count as f64,
+            _ => view_coord(pos, count),
         }
+    }
+}
 
-        Self { rays }
+/// Point of view within [-1, 1] of sample `pos` out of `count`, center of view if there's single sample
+fn view_coord(pos: f64, count: usize) -> f64 {
+    match count > 1 {
+        true => 1.0 - 2.0 * pos / (count - 1) as f64,
+        false => 0.0,
     }
 }
 
-/// Camera object that can be moved and rotated
+/// Camera object that can be moved and rotated. Orientation is continuous yaw, angle of the view direction
+/// from x axis towards y axis, pitch, angle of the view direction above xy plane, and roll around the view
+/// direction lifting the left side of view. Rays are rotated from
+/// the base frustum looking along [1, 0, 0] each time orientation changes. Discrete steps of `rotate_up`,
+/// `rotate_down`, `rotate_left` and `rotate_right` are `FRAC_PI_2 / discr`
 #[derive(Debug)]
 pub struct Camera {
     pub(crate) pos: Point,
-    pub(crate) vision: Vision,
+    pub(crate) base: Vec<Vec<Vector>>,
+    pub(crate) rays: Vec<Vec<Vector>>,
     pub(crate) discr: usize,
-    pub(crate) zen_idx: usize,
-    pub(crate) azi_idx: usize,
-    pub(crate) zen_max: usize,
-    pub(crate) azi_max: usize,
+    pub(crate) yaw: f64,
+    pub(crate) pitch: f64,
+    pub(crate) roll: f64,
+    pub(crate) orientation: Matrix,
+    pub(crate) projection: Projection,
+    pub(crate) view_width: f64,
+    pub(crate) shifts: Vec<Vec<Vector>>,
+    pub(crate) origins: Vec<Vec<Point>>,
     pub(crate) size: (usize, usize),
     pub(crate) wfov: f64,
     pub(crate) hfov: f64,
@@ -97,14 +125,20 @@ impl Camera {
         size: (usize, usize),
         draw_dist: f64,
     ) -> Self {
+        let base = init_rays(yfov, zfov, size.1, size.0);
         Self {
             pos,
-            vision: Vision::new(discr, yfov, zfov, size),
+            rays: base.clone(),
+            base,
             discr,
-            zen_idx: discr - 1,
-            azi_idx: 0,
-            zen_max: 2 * discr - 1,
-            azi_max: 4 * discr,
+            yaw: 0.0,
+            pitch: 0.0,
+            roll: 0.0,
+            orientation: Matrix::identity(3),
+            projection: Projection::Perspective,
+            view_width: 1.0,
+            shifts: vec![],
+            origins: vec![],
             size,
             wfov: yfov,
             hfov: zfov,
@@ -112,46 +146,273 @@ impl Camera {
         }
     }
 
+    /// Sets the way of casting rays, it's `Projection::Perspective` by default
+    pub fn with_projection(mut self, projection: Projection) -> Self {
+        self.projection = projection;
+        self.rebuild();
+        self
+    }
+
+    /// Width of the view plane of `Projection::Orthographic`
+    pub fn with_view_width(mut self, width: f64) -> Self {
+        self.view_width = width;
+        if self.projection == Projection::Orthographic {
+            self.rebuild();
+        }
+        self
+    }
+
+    pub fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    /// Number of rays as (rows, cols)
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    /// Horizontal and vertical fields of view
+    pub fn fov(&self) -> (f64, f64) {
+        (self.wfov, self.hfov)
+    }
+
+    /// Sets horizontal and vertical fields of view keeping position and orientation
+    pub fn set_fov(&mut self, wfov: f64, hfov: f64) {
+        (self.wfov, self.hfov) = (wfov, hfov);
+        self.rebuild();
+    }
+
+    /// Sets number of rays as (rows, cols) keeping fields of view
+    pub fn resize(&mut self, size: (usize, usize)) {
+        self.size = size;
+        self.rays = vec![vec![Vector::new(vec![1.0, 0.0, 0.0]); size.1]; size.0];
+        self.rebuild();
+    }
+
+    /// Narrows both fields of view `factor` times keeping their ratio, so it zooms in if `factor` is
+    /// greater than 1. The view plane is narrowed instead for `Projection::Orthographic`.
+    /// Zoom is ignored if the view would become wider than projection can show
+    pub fn zoom(&mut self, factor: f64) {
+        if !(factor.is_finite() && factor > 0.0) {
+            return;
+        }
+        let (wfov, hfov) = (self.wfov / factor, self.hfov / factor);
+        match self.projection {
+            Projection::Perspective if wfov < PI && hfov < PI => self.set_fov(wfov, hfov),
+            Projection::Fisheye if wfov <= 2.0 * PI && hfov <= 2.0 * PI => self.set_fov(wfov, hfov),
+            Projection::Panoramic if hfov < PI => self.set_fov(wfov, hfov),
+            Projection::Orthographic => {
+                self.view_width /= factor;
+                self.rebuild();
+            }
+            _ => (),
+        }
+    }
+
+    /// Recomputes the base frustum after changing projection, fields of view or size
+    fn rebuild(&mut self) {
+        let (rows, cols) = self.size;
+        (self.base, self.shifts) = match self.projection {
+            Projection::Perspective => (init_rays(self.wfov, self.hfov, cols, rows), vec![]),
+            projection => {
+                let samples = (0..rows)
+                    .map(|r| {
+                        (0..cols)
+                            .map(|c| {
+                                let (sy, sz) = (
+                                    projection.view_y(c as f64, cols),
+                                    view_coord(r as f64, rows),
+                                );
+                                projection.sample(sy, sz, (self.wfov, self.hfov), self.view_width)
+                            })
+                            .collect::<Vec<([f64; 3], [f64; 3])>>()
+                    })
+                    .collect::<Vec<Vec<([f64; 3], [f64; 3])>>>();
+                let part = |i: usize| {
+                    samples
+                        .iter()
+                        .map(|row| {
+                            row.iter()
+                                .map(|sample| Vector::new([sample.0, sample.1][i].to_vec()))
+                                .collect()
+                        })
+                        .collect()
+                };
+                match projection {
+                    Projection::Orthographic => (part(0), part(1)),
+                    _ => (part(0), vec![]),
+                }
+            }
+        };
+        self.orient(self.yaw, self.pitch, self.roll);
+    }
+
     pub fn pos(&self) -> &Point {
         &self.pos
     }
 
+    /// Horizontal direction of view within xy plane
     pub fn dir(&self) -> (f64, f64) {
-        let ang = ((2 * self.azi_idx) as f64 / self.azi_max as f64) * PI;
-        (ang.cos(), ang.sin())
+        (self.yaw.cos(), self.yaw.sin())
+    }
+
+    pub fn yaw(&self) -> f64 {
+        self.yaw
+    }
+
+    pub fn pitch(&self) -> f64 {
+        self.pitch
+    }
+
+    pub fn roll(&self) -> f64 {
+        self.roll
+    }
+
+    /// Unit view direction in the world
+    pub fn forward(&self) -> Vector {
+        self.world(vec![1.0, 0.0, 0.0])
+    }
+
+    /// Unit direction to the right side of view in the world
+    pub fn right(&self) -> Vector {
+        self.world(vec![0.0, -1.0, 0.0])
+    }
+
+    /// Unit direction to the top side of view in the world
+    pub fn up(&self) -> Vector {
+        self.world(vec![0.0, 0.0, 1.0])
+    }
+
+    /// Vector of the camera frame in the world
+    fn world(&self, v: Vec<f64>) -> Vector {
+        Vector {
+            coord: self.orientation.mul(&Matrix::col(v)).to_col(),
+        }
+    }
+
+    /// Angle of single discrete rotation step
+    pub fn angle_step(&self) -> f64 {
+        FRAC_PI_2 / self.discr as f64
     }
 
     pub fn mv(&mut self, vec: &Vector) -> ReRes<()> {
-        self.pos.mv_assign(vec)
+        self.pos.mv_assign(vec)?;
+        self.place();
+        Ok(())
     }
 
     pub fn ray(&self, r: usize, c: usize) -> &Vector {
-        &self.vision.rays[self.zen_idx][self.azi_idx][r][c]
+        &self.rays[r][c]
+    }
+
+    /// Point ray `(r, c)` goes from, it's camera position unless projection is orthographic
+    pub fn origin(&self, r: usize, c: usize) -> &Point {
+        match self.origins.is_empty() {
+            true => &self.pos,
+            false => &self.origins[r][c],
+        }
+    }
+
+    /// Moves origins of rays of orthographic projection into the world
+    fn place(&mut self) {
+        self.origins = self
+            .shifts
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|shift| {
+                        let shift = self.world((0..3).map(|i| shift.at(i)).collect());
+                        self.pos.clone().mv(&shift).unwrap()
+                    })
+                    .collect()
+            })
+            .collect();
+    }
+
+    /// Turns camera to the given yaw, pitch and roll. Pitch is clamped so camera can look straight up
+    /// or down but not over its head, yaw and roll are kept within [-PI, PI]
+    pub fn orient(&mut self, yaw: f64, pitch: f64, roll: f64) {
+        let wrap = |angle: f64| match angle.abs() > PI {
+            true => angle - 2.0 * PI * (angle / (2.0 * PI)).round(),
+            false => angle,
+        };
+        self.yaw = wrap(yaw);
+        self.pitch = pitch.clamp(-FRAC_PI_2, FRAC_PI_2);
+        self.roll = wrap(roll);
+        self.orientation = Matrix::rotation(0, 1, self.yaw, 3)
+            .mul(&Matrix::rotation(0, 2, self.pitch, 3))
+            .mul(&Matrix::rotation(1, 2, self.roll, 3));
+        for r in 0..self.base.len() {
+            for c in 0..self.base[r].len() {
+                let coord = self.orientation.mul(self.base[r][c].coord()).to_col();
+                self.rays[r][c] = Vector { coord };
+            }
+        }
+        self.place();
+    }
+
+    /// Turns camera to the given yaw and pitch keeping its roll
+    pub fn look(&mut self, yaw: f64, pitch: f64) {
+        self.orient(yaw, pitch, self.roll)
+    }
+
+    /// Turns camera to look at `target` with level horizon, orientation is kept if `target` is camera position
+    pub fn look_at(&mut self, target: &Point) {
+        let df = [0, 1, 2].map(|i| target[i] - self.pos[i]);
+        if df == [0.0; 3] {
+            return;
+        }
+        let yaw = df[1].atan2(df[0]);
+        let pitch = df[2].atan2(df[0].hypot(df[1]));
+        self.orient(yaw, pitch, 0.0)
+    }
+
+    /// Turns camera around its view direction by the given angle
+    pub fn tilt(&mut self, roll: f64) {
+        self.orient(self.yaw, self.pitch, self.roll + roll)
+    }
+
+    /// Point of view of the center of canvas cell `(r, c)` that covers `cell` samples as (rows, cols)
+    fn cell_sample(&self, r: usize, c: usize, cell: (usize, usize)) -> ([f64; 3], [f64; 3]) {
+        let center =
+            |idx: usize, samples: usize| (idx * samples) as f64 + (samples - 1) as f64 / 2.0;
+        let sy = self.projection.view_y(center(c, cell.1), self.size.1);
+        let sz = view_coord(center(r, cell.0), self.size.0);
+        self.projection
+            .sample(sy, sz, (self.wfov, self.hfov), self.view_width)
+    }
+
+    /// World direction of ray through the center of canvas cell `(r, c)` that covers `cell` samples as (rows, cols),
+    /// eg `RenderMode::cell_samples`. It's the ray of sample `(r, c)` if cell is single sample
+    pub fn cell_ray(&self, r: usize, c: usize, cell: (usize, usize)) -> Vector {
+        self.world(self.cell_sample(r, c, cell).0.to_vec())
+    }
+
+    /// Point ray through the center of canvas cell `(r, c)` goes from, see `Camera::cell_ray`
+    pub fn cell_origin(&self, r: usize, c: usize, cell: (usize, usize)) -> Point {
+        let shift = self.world(self.cell_sample(r, c, cell).1.to_vec());
+        self.pos.clone().mv(&shift).unwrap()
+    }
+
+    /// Turns camera by the given angles of yaw and pitch
+    pub fn rotate(&mut self, yaw: f64, pitch: f64) {
+        self.look(self.yaw + yaw, self.pitch + pitch)
     }
 
     pub fn rotate_up(&mut self, step: usize) {
-        self.zen_idx = self.zen_idx.saturating_sub(step)
+        self.rotate(0.0, step as f64 * self.angle_step())
     }
 
     pub fn rotate_down(&mut self, step: usize) {
-        let idx = self.zen_idx + step;
-        self.zen_idx = if idx >= self.zen_max {
-            self.zen_max - 1
-        } else {
-            idx
-        }
+        self.rotate(0.0, -(step as f64) * self.angle_step())
     }
 
     pub fn rotate_left(&mut self, step: usize) {
-        self.azi_idx = (self.azi_idx + step) % self.azi_max
+        self.rotate(step as f64 * self.angle_step(), 0.0)
     }
 
     pub fn rotate_right(&mut self, step: usize) {
-        if self.azi_idx >= step {
-            self.azi_idx = self.azi_idx - step
-        } else {
-            self.azi_idx = self.azi_max + self.azi_idx - (step % self.azi_max)
-        }
+        self.rotate(-(step as f64) * self.angle_step(), 0.0)
     }
 }
 
@@ -9,65 +9,331 @@ use {
         grid::*,
         math::*,
     },
-    either::Either,
+    crossterm::style::Color,
     std::{cmp::min, marker::PhantomData, str::Chars, thread, time::Duration},
+    strum_macros::{Display, EnumString},
 };
 
-/// Stores picture as `Vec<String>` respectively to `charmap` given in the `Conf`
+/// Number of unchanged cells between two changed runs in one row
+/// that are reprinted instead of moving cursor over them
+const MAX_GAP: usize = 6;
+
+/// Ratio of height of terminal cell to its width
+pub const CELL_ASPECT: f64 = 2.0;
+
+/// Bits of braille dots in the 4x2 cell counting from the top left dot
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Thresholds of ordered dithering in the 4x2 braille cell
+const BRAILLE_DITHER: [[f64; 2]; 4] = [
+    [0.0625, 0.5625],
+    [0.8125, 0.3125],
+    [0.1875, 0.6875],
+    [0.9375, 0.4375],
+];
+
+/// Ways of drawing samples of scene in terminal cells, selected in `Conf` with `RENDER_MODE` key
+#[derive(Debug, Display, EnumString, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Single sample per cell drawn with charcoal character
+    #[default]
+    #[strum(serialize = "chars")]
+    Chars,
+    /// Two vertically stacked samples per cell drawn with `▀` or `▄` in separate colors,
+    /// so it requires colored `ColorMode`
+    #[strum(serialize = "halfblock")]
+    HalfBlock,
+    /// 4x2 samples per cell drawn with braille dots, brightness is dithered, so it suits monochrome
+    #[strum(serialize = "braille")]
+    Braille,
+}
+
+impl RenderMode {
+    /// Number of samples in one cell as (rows, cols)
+    pub fn cell_samples(&self) -> (usize, usize) {
+        match self {
+            RenderMode::Chars => (1, 1),
+            RenderMode::HalfBlock => (2, 1),
+            RenderMode::Braille => (4, 2),
+        }
+    }
+
+    /// Number of samples on canvas of the given size as (rows, cols)
+    pub fn samples(&self, size: (usize, usize)) -> (usize, usize) {
+        let cell = self.cell_samples();
+        (size.0 * cell.0, size.1 * cell.1)
+    }
+
+    /// Vertical field of view that keeps proportions of picture on canvas of the given size
+    pub fn hfov(&self, wfov: f64, size: (usize, usize)) -> f64 {
+        let (rows, cols) = self.samples(size);
+        let (cell_rows, cell_cols) = self.cell_samples();
+        let sample_aspect = CELL_ASPECT * cell_cols as f64 / cell_rows as f64;
+        wfov * rows as f64 * sample_aspect / cols as f64
+    }
+}
+
+/// Stores picture as cells respectively to `charmap` given in the `Conf`, colors of cells are
+/// drawn in the given `ColorMode`. Each cell is composed of samples according to `RenderMode`,
+/// so camera must have `RenderMode::samples` rays. Keeps the last drawn picture to redraw only changed cells
 #[derive(Debug)]
 pub struct Canvas<Scn: AsScene> {
     phantom: PhantomData<Scn>,
     size: (usize, usize),
     charcoal: Charcoal,
-    picture: Vec<String>,
+    color_mode: ColorMode,
+    render_mode: RenderMode,
+    picture: Vec<Vec<Cell>>,
+    drawn: Option<Vec<Vec<Cell>>>,
+    bytes: usize,
 }
 
 impl<Scn: AsScene> Canvas<Scn> {
     /// Constructs new canvas
-    pub fn new(size: (usize, usize), chars: String, draw_dist: f64) -> Self {
+    pub fn new(size: (usize, usize), chars: String, draw_dist: f64, mode: ColorMode) -> Self {
         Self {
             phantom: PhantomData,
             size,
             charcoal: Charcoal::new(chars, draw_dist),
-            picture: vec![(0..size.1).map(|_| ' ').collect::<String>(); size.0],
+            color_mode: mode,
+            render_mode: RenderMode::Chars,
+            picture: vec![vec![Cell::default(); size.1]; size.0],
+            drawn: None,
+            bytes: 0,
         }
     }
 
+    /// Sets the way of composing cells from samples, it's `RenderMode::Chars` by default
+    pub fn with_render_mode(mut self, mode: RenderMode) -> Self {
+        self.render_mode = mode;
+        self
+    }
+
     /// Updates picture via colliding entities against all camera rays
     pub fn update(&mut self, camera: &Camera, cs: &CoordSys, scene: &Scn) -> ReRes<()> {
+        let (cell_rows, cell_cols) = self.render_mode.cell_samples();
+        let mut samples = vec![vec![Cell::default(); cell_cols]; cell_rows];
         for r in 0..self.size.0 {
-            self.picture[r] = (0..self.size.1)
-                .map(|c| {
-                    let ray = camera.ray(r, c);
-                    match scene.collide(cs, &camera.pos, ray) {
-                        Either::Left(d) => self.charcoal.ignite(d),
-                        Either::Right(c) => c,
+            for c in 0..self.size.1 {
+                for i in 0..cell_rows {
+                    for j in 0..cell_cols {
+                        let (sr, sc) = (r * cell_rows + i, c * cell_cols + j);
+                        let ray = camera.ray(sr, sc);
+                        samples[i][j] = match scene.collide(cs, camera.origin(sr, sc), ray) {
+                            Some(hit) => scene
+                                .shade(cs, ray, &hit)
+                                .unwrap_or_else(|| self.paint_dist(hit.dist)),
+                            None => scene
+                                .background(cs, ray)
+                                .unwrap_or_else(|| self.paint_dist(-1.0)),
+                        };
                     }
-                })
-                .collect::<String>();
+                }
+                self.picture[r][c] = match self.render_mode {
+                    RenderMode::Chars => samples[0][0],
+                    RenderMode::HalfBlock => half_block(&samples),
+                    RenderMode::Braille => braille(&samples),
+                };
+            }
+        }
+        Ok(())
+    }
+
+    /// Cell of the sample that has only distance to the closest entity.
+    /// Character of canvas charcoal is colored in gray fading with distance if the entity is hit
+    fn paint_dist(&self, dist: f64) -> Cell {
+        match (dist < 0.0, self.render_mode) {
+            (true, _) | (false, RenderMode::Chars) => Cell::from(self.charcoal.ignite(dist)),
+            (false, _) => Cell::new(
+                self.charcoal.ignite(dist),
+                Some(Rgb(255, 255, 255).dim(1.0 - dist / self.charcoal.fade)),
+                None,
+            ),
+        }
+    }
+
+    /// Prints into the given terminal runs of cells changed since the previous drawing
+    /// or the whole picture if there is nothing drawn yet
+    pub fn draw<Term: AsTerminal>(&mut self, term: &mut Term) -> ReRes<()> {
+        let mut colors = (None, None);
+        for (r, line) in self.picture.iter().enumerate() {
+            let runs = match &self.drawn {
+                Some(drawn) => changed_runs(line, &drawn[r]),
+                None => vec![(0, line.len())],
+            };
+            for (start, end) in runs {
+                term.move_cursor((r + 3) as u16, start as u16)?;
+                self.print_run(term, &line[start..end], &mut colors)?;
+            }
+        }
+        if colors != (None, None) {
+            term.set_colors(None, None)?;
         }
+        self.bytes = term.flush()?;
+        self.drawn = Some(self.picture.clone());
         Ok(())
     }
 
-    /// Prints all string to console
-    pub fn draw(&self) -> ReRes<()> {
-        console::move_cursor(3, 0)?;
-        for line in &self.picture {
-            println!("{}", line);
+    /// Prints cells from the current cursor position changing colors only when they differ
+    /// from `colors` that are the current colors of terminal
+    fn print_run<Term: AsTerminal>(
+        &self,
+        term: &mut Term,
+        run: &[Cell],
+        colors: &mut (Option<Color>, Option<Color>),
+    ) -> ReRes<()> {
+        let mut text = String::new();
+        for cell in run {
+            let cell_colors = (
+                cell.fg.and_then(|fg| self.color_mode.quantize(fg)),
+                cell.bg.and_then(|bg| self.color_mode.quantize(bg)),
+            );
+            if cell_colors != *colors {
+                if !text.is_empty() {
+                    term.print(&text)?;
+                    text.clear();
+                }
+                term.set_colors(cell_colors.0, cell_colors.1)?;
+                *colors = cell_colors;
+            }
+            text.push(cell.ch);
+        }
+        if !text.is_empty() {
+            term.print(&text)?;
         }
         Ok(())
     }
 
-    /// Clears all console and shows one message
-    pub fn banner(&self, msg: &str, timeout: Duration) -> ReRes<()> {
+    /// Forgets the last drawn picture, so the next drawing is the full redraw
+    pub fn invalidate(&mut self) {
+        self.drawn = None;
+    }
+
+    /// Sets size of canvas as (rows, cols) with blank picture, so the next drawing is the full redraw
+    pub fn resize(&mut self, size: (usize, usize)) {
+        self.size = size;
+        self.picture = vec![vec![Cell::default(); size.1]; size.0];
+        self.invalidate();
+    }
+
+    /// Clears all the terminal and shows one message
+    pub fn banner<Term: AsTerminal>(
+        &mut self,
+        term: &mut Term,
+        msg: &str,
+        timeout: Duration,
+    ) -> ReRes<()> {
         let col = self.size.1.saturating_sub(msg.len()) / 2;
         let row = self.size.0 / 2;
-        console::move_cursor(row as u16, col as u16)?;
 
-        console::clear();
-        println!("{}", &msg[..min(msg.len(), self.size.1)]);
+        term.clear()?;
+        term.move_cursor(row as u16, col as u16)?;
+        term.print(&msg[..min(msg.len(), self.size.1)])?;
+        term.flush()?;
         thread::sleep(timeout);
-        console::clear();
+        term.clear()?;
+        term.flush()?;
+        self.invalidate();
         Ok(())
     }
+
+    /// Number of bytes written into terminal on the last drawing
+    pub fn bytes_drawn(&self) -> usize {
+        self.bytes
+    }
+
+    /// Size of canvas as (rows, cols)
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    /// Color mode of canvas
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Render mode of canvas
+    pub fn render_mode(&self) -> RenderMode {
+        self.render_mode
+    }
+
+    /// Picture drawn on the last update
+    pub fn picture(&self) -> &Vec<Vec<Cell>> {
+        &self.picture
+    }
+
+    /// Characters of picture drawn on the last update
+    pub fn lines(&self) -> Vec<String> {
+        self.picture
+            .iter()
+            .map(|line| line.iter().map(|cell| cell.ch).collect())
+            .collect()
+    }
+}
+
+/// Half-open ranges of cells where `line` differs from `prev`.
+/// Ranges separated by at most `MAX_GAP` unchanged cells are merged
+pub(crate) fn changed_runs<T: PartialEq>(line: &[T], prev: &[T]) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = vec![];
+    for c in 0..line.len() {
+        if prev.get(c) == Some(&line[c]) {
+            continue;
+        }
+        match runs.last_mut() {
+            Some(run) if c - run.1 <= MAX_GAP => run.1 = c + 1,
+            _ => runs.push((c, c + 1)),
+        }
+    }
+    runs
+}
+
+/// Color the sample is seen, samples without colors are white if they aren't blank
+fn sample_color(sample: &Cell) -> Option<Rgb> {
+    match (sample.fg.or(sample.bg), sample.ch) {
+        (Some(color), _) => Some(color),
+        (None, ' ') => None,
+        (None, _) => Some(Rgb(255, 255, 255)),
+    }
+}
+
+/// Cell showing two vertically stacked samples with upper and lower half blocks
+pub(crate) fn half_block(samples: &[Vec<Cell>]) -> Cell {
+    match (sample_color(&samples[0][0]), sample_color(&samples[1][0])) {
+        (None, None) => Cell::default(),
+        (None, Some(lower)) => Cell::new('▄', Some(lower), None),
+        (upper, lower) => Cell::new('▀', upper, lower),
+    }
+}
+
+/// Cell showing 4x2 samples with braille dots, each dot is shown if brightness of the sample
+/// exceeds the threshold of ordered dithering. Color is average of shown dots
+pub(crate) fn braille(samples: &[Vec<Cell>]) -> Cell {
+    let (mut bits, mut sum, mut colored) = (0, (0, 0, 0), 0);
+    for i in 0..4 {
+        for j in 0..2 {
+            let color = match sample_color(&samples[i][j]) {
+                Some(color) => color,
+                None => continue,
+            };
+            if color.luma() > BRAILLE_DITHER[i][j] {
+                bits |= BRAILLE_DOTS[i][j];
+                if samples[i][j].fg.or(samples[i][j].bg).is_some() {
+                    sum = (
+                        sum.0 + color.0 as u32,
+                        sum.1 + color.1 as u32,
+                        sum.2 + color.2 as u32,
+                    );
+                    colored += 1;
+                }
+            }
+        }
+    }
+    let fg = match colored {
+        0 => None,
+        n => Some(Rgb((sum.0 / n) as u8, (sum.1 / n) as u8, (sum.2 / n) as u8)),
+    };
+    match bits {
+        0 => Cell::default(),
+        _ => Cell::new(char::from_u32(0x2800 + bits).unwrap(), fg, None),
+    }
 }
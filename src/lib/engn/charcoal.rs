@@ -1,7 +1,13 @@
+use crate::engn::*;
+
+/// Maps distance to character of `charmap` and optionally to colors of the cell
 #[derive(Debug, Clone)]
 pub struct Charcoal {
     pub(crate) charmap: Vec<char>,
     pub(crate) coef: f64,
+    pub(crate) fade: f64,
+    pub(crate) fg: Option<Rgb>,
+    pub(crate) bg: Option<Rgb>,
 }
 
 impl Charcoal {
@@ -9,10 +15,31 @@ impl Charcoal {
         let charmap: Vec<char> = chars.chars().collect();
         Self {
             coef: charmap.len() as f64 / draw_dist,
+            fade: draw_dist,
             charmap,
+            fg: None,
+            bg: None,
         }
     }
 
+    /// Sets base foreground color that fades with distance
+    pub fn with_fg(mut self, fg: Rgb) -> Self {
+        self.fg = Some(fg);
+        self
+    }
+
+    /// Sets base background color that fades with distance
+    pub fn with_bg(mut self, bg: Rgb) -> Self {
+        self.bg = Some(bg);
+        self
+    }
+
+    /// Sets distance where colors fade out completely, it's `draw_dist` by default
+    pub fn with_fade(mut self, fade: f64) -> Self {
+        self.fade = fade;
+        self
+    }
+
     pub fn ignite(&self, dist: f64) -> char {
         let idx = (dist * self.coef).floor();
         if idx < 0.0 {
@@ -23,4 +50,26 @@ impl Charcoal {
             None => *self.charmap.last().unwrap(),
         }
     }
+
+    /// Cell with character given by `ignite` and base colors faded according to distance
+    pub fn paint(&self, dist: f64) -> Cell {
+        let coef = 1.0 - dist / self.fade;
+        Cell::new(
+            self.ignite(dist),
+            self.fg.map(|fg| fg.dim(coef)),
+            self.bg.map(|bg| bg.dim(coef)),
+        )
+    }
+
+    /// Cell for `brightness` within [0, 1]: the brightest is the first character of `charmap`
+    /// and the darkest is the last one, base colors are dimmed with `brightness`
+    pub fn shade(&self, brightness: f64) -> Cell {
+        let brightness = brightness.clamp(0.0, 1.0);
+        let idx = ((1.0 - brightness) * self.charmap.len() as f64).floor() as usize;
+        Cell::new(
+            self.charmap[idx.min(self.charmap.len() - 1)],
+            self.fg.map(|fg| fg.dim(brightness)),
+            self.bg.map(|bg| bg.dim(brightness)),
+        )
+    }
 }
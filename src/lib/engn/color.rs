@@ -0,0 +1,137 @@
+use {
+    crossterm::style::Color,
+    strum_macros::{Display, EnumString},
+};
+
+/// Palette of 16 standard terminal colors with their approximate RGB values
+const ANSI16: [(Color, Rgb); 16] = [
+    (Color::Black, Rgb(0, 0, 0)),
+    (Color::DarkRed, Rgb(128, 0, 0)),
+    (Color::DarkGreen, Rgb(0, 128, 0)),
+    (Color::DarkYellow, Rgb(128, 128, 0)),
+    (Color::DarkBlue, Rgb(0, 0, 128)),
+    (Color::DarkMagenta, Rgb(128, 0, 128)),
+    (Color::DarkCyan, Rgb(0, 128, 128)),
+    (Color::Grey, Rgb(192, 192, 192)),
+    (Color::DarkGrey, Rgb(128, 128, 128)),
+    (Color::Red, Rgb(255, 0, 0)),
+    (Color::Green, Rgb(0, 255, 0)),
+    (Color::Yellow, Rgb(255, 255, 0)),
+    (Color::Blue, Rgb(0, 0, 255)),
+    (Color::Magenta, Rgb(255, 0, 255)),
+    (Color::Cyan, Rgb(0, 255, 255)),
+    (Color::White, Rgb(255, 255, 255)),
+];
+
+/// Levels of each component in the 6x6x6 color cube of 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Color defined with its red, green and blue components
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Multiplies each component to the given `coef` clamped within [0, 1]
+    pub fn dim(&self, coef: f64) -> Self {
+        let coef = coef.clamp(0.0, 1.0);
+        let comp = |c: u8| (c as f64 * coef).round() as u8;
+        Self(comp(self.0), comp(self.1), comp(self.2))
+    }
+
+    /// Relative luminance within [0, 1]
+    pub fn luma(&self) -> f64 {
+        (0.2126 * self.0 as f64 + 0.7152 * self.1 as f64 + 0.0722 * self.2 as f64) / 255.0
+    }
+
+    /// Squared euclidean distance between colors
+    pub(crate) fn dist2(&self, other: &Self) -> u32 {
+        let df = |l: u8, r: u8| (l as i32 - r as i32).pow(2) as u32;
+        df(self.0, other.0) + df(self.1, other.1) + df(self.2, other.2)
+    }
+}
+
+/// Color modes of terminal output, selected in `Conf` with `COLOR_MODE` key
+#[derive(Debug, Display, EnumString, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// No colors at all, only characters are drawn
+    #[default]
+    #[strum(serialize = "mono")]
+    Mono,
+    /// 16 standard colors
+    #[strum(serialize = "16")]
+    Ansi16,
+    /// 256-color palette
+    #[strum(serialize = "256")]
+    Ansi256,
+    /// 24-bit RGB colors
+    #[strum(serialize = "truecolor")]
+    TrueColor,
+}
+
+impl ColorMode {
+    /// The closest to `rgb` terminal color available in the mode, `None` in `Mono` mode
+    pub fn quantize(&self, rgb: Rgb) -> Option<Color> {
+        match self {
+            ColorMode::Mono => None,
+            ColorMode::Ansi16 => ANSI16
+                .iter()
+                .min_by_key(|(_, pal)| pal.dist2(&rgb))
+                .map(|(color, _)| *color),
+            ColorMode::Ansi256 => Some(Color::AnsiValue(ansi256(rgb))),
+            ColorMode::TrueColor => Some(Color::Rgb {
+                r: rgb.0,
+                g: rgb.1,
+                b: rgb.2,
+            }),
+        }
+    }
+}
+
+/// Index of the closest to `rgb` color in 256-color palette: either in color cube or in grayscale ramp
+pub(crate) fn ansi256(rgb: Rgb) -> u8 {
+    let level = |c: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|i| (CUBE_LEVELS[*i] as i32 - c as i32).abs())
+            .unwrap()
+    };
+    let (r, g, b) = (level(rgb.0), level(rgb.1), level(rgb.2));
+    let cube = Rgb(CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+
+    let avg = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray_idx = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray_lvl = 8 + 10 * gray_idx;
+
+    if Rgb(gray_lvl, gray_lvl, gray_lvl).dist2(&rgb) < cube.dist2(&rgb) {
+        232 + gray_idx
+    } else {
+        (16 + 36 * r + 6 * g + b) as u8
+    }
+}
+
+/// Single cell of `Canvas`: character with optional foreground and background colors.
+/// `None` color stands for the terminal default one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub ch: char,
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+}
+
+impl Cell {
+    /// Cell with the given character and colors
+    pub fn new(ch: char, fg: Option<Rgb>, bg: Option<Rgb>) -> Self {
+        Self { ch, fg, bg }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(ch: char) -> Self {
+        Self::new(ch, None, None)
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::from(' ')
+    }
+}
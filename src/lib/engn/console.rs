@@ -1,12 +1,19 @@
 use {
-    crate::errs::{ReErr, ReRes},
+    crate::{
+        engn::*,
+        errs::{ReErr, ReRes},
+    },
     crossterm::{
         cursor,
         event::{self as crossterm_event, Event},
-        terminal::{enable_raw_mode, size},
-        ExecutableCommand, Result,
+        style::{Color, Colors, Print, SetColors},
+        terminal::{enable_raw_mode, size, Clear, ClearType},
+        ExecutableCommand, QueueableCommand, Result,
+    },
+    std::{
+        io::{stdout, Error as IoError, Write},
+        time::Duration,
     },
-    std::io::{stdout, Error as IoError},
 };
 
 /// Clears, enables raw mode anr returns console size as (rows, cols)
@@ -32,3 +39,71 @@ pub(crate) fn move_cursor(row: u16, col: u16) -> Result<()> {
     stdout().execute(cursor::MoveTo(col, row))?;
     Ok(())
 }
+
+/// Terminal that is the real console handled with `crossterm`.
+/// All the commands are queued into buffer that is written into stdout at once on `flush`
+#[derive(Debug, Default, Clone)]
+pub struct Console {
+    pub(crate) buf: Vec<u8>,
+}
+
+impl Console {
+    /// Constructs new console
+    pub fn new() -> Self {
+        Self { buf: vec![] }
+    }
+}
+
+impl AsTerminal for Console {
+    fn init(&mut self) -> ReRes<(u16, u16)> {
+        init()
+    }
+
+    fn size(&self) -> ReRes<(u16, u16)> {
+        let size = size()?;
+        Ok((size.1, size.0))
+    }
+
+    fn listen(&mut self) -> ReRes<Option<Event>> {
+        Ok(Some(listen()?))
+    }
+
+    fn poll(&mut self, timeout: Duration) -> ReRes<Option<Event>> {
+        match crossterm_event::poll(timeout)? {
+            true => Ok(Some(listen()?)),
+            false => Ok(None),
+        }
+    }
+
+    fn move_cursor(&mut self, row: u16, col: u16) -> ReRes<()> {
+        self.buf.queue(cursor::MoveTo(col, row))?;
+        Ok(())
+    }
+
+    fn print(&mut self, s: &str) -> ReRes<()> {
+        self.buf.queue(Print(s))?;
+        Ok(())
+    }
+
+    fn set_colors(&mut self, fg: Option<Color>, bg: Option<Color>) -> ReRes<()> {
+        self.buf.queue(SetColors(Colors::new(
+            fg.unwrap_or(Color::Reset),
+            bg.unwrap_or(Color::Reset),
+        )))?;
+        Ok(())
+    }
+
+    fn clear(&mut self) -> ReRes<()> {
+        self.buf.queue(Clear(ClearType::All))?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> ReRes<usize> {
+        let mut out = stdout().lock();
+        out.write_all(&self.buf)?;
+        out.flush()?;
+        let bytes = self.buf.len();
+        self.buf.clear();
+        Ok(bytes)
+    }
+}
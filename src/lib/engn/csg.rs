@@ -0,0 +1,265 @@
+//! Constructive solid geometry: solids combined with union, intersection and difference.
+//! Operands report intervals of ray inside them with `AsCollided::intervals`, intervals are combined
+//! by the operation and the first boundary ahead of ray is the hit. Nodes are solids themselves,
+//! so they can be nested into trees
+
+use {
+    crate::{
+        engn::*,
+        errs::{GameErr::*, ReErr::*, ReRes},
+        math::*,
+    },
+    std::{collections::HashMap, rc::Rc},
+    uuid::Uuid,
+};
+
+/// Tolerance of matching interval bounds and dropping empty intervals
+const BOUND_EPS: f64 = 1e-9;
+
+/// Part of distance to the hit that ray probing operand surface starts before it
+const PROBE_EPS: f64 = 1e-6;
+
+/// Boolean operation on solids
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsgOp {
+    /// Points inside either solid
+    Union,
+    /// Points inside both solids
+    Intersection,
+    /// Points inside the left solid and outside the right one
+    Difference,
+}
+
+impl CsgOp {
+    fn apply(&self, left: bool, right: bool) -> bool {
+        match self {
+            Self::Union => left || right,
+            Self::Intersection => left && right,
+            Self::Difference => left && !right,
+        }
+    }
+
+    /// Intervals where the operation holds for the given sorted disjoint intervals of operands
+    pub fn combine(&self, left: &[Interval], right: &[Interval]) -> Vec<Interval> {
+        let mut bounds = left
+            .iter()
+            .chain(right)
+            .flat_map(|(enter, exit)| [*enter, *exit])
+            .collect::<Vec<f64>>();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+
+        let inside = |intervals: &[Interval], t: f64| {
+            intervals
+                .iter()
+                .any(|(enter, exit)| *enter < t && t < *exit)
+        };
+        let mut combined: Vec<Interval> = vec![];
+        for pair in bounds.windows(2) {
+            let (enter, exit) = (pair[0], pair[1]);
+            let mid = match (enter.is_finite(), exit.is_finite()) {
+                (true, true) => (enter + exit) / 2.0,
+                (false, true) => exit - 1.0,
+                (true, false) => enter + 1.0,
+                (false, false) => 0.0,
+            };
+            if !self.apply(inside(left, mid), inside(right, mid)) {
+                continue;
+            }
+            match combined.last_mut() {
+                Some(last) if last.1 == enter => last.1 = exit,
+                _ => combined.push((enter, exit)),
+            }
+        }
+        combined.retain(|(enter, exit)| exit - enter > BOUND_EPS);
+        combined
+    }
+}
+
+/// Node of solids tree combining two operands with `CsgOp`. Surface of the node is drawn with
+/// charcoal of operand it belongs to unless the node has its own charcoal
+#[derive(Debug)]
+pub struct Csg {
+    pub(crate) entity: Entity,
+    pub(crate) op: CsgOp,
+    pub(crate) left: Box<dyn AsCollided>,
+    pub(crate) right: Box<dyn AsCollided>,
+    pub charcoal: Option<Charcoal>,
+}
+
+impl Csg {
+    /// Node combining operands with `op`, both of them must be solids reporting `AsCollided::intervals`
+    pub fn new(
+        entity: Entity,
+        op: CsgOp,
+        left: Box<dyn AsCollided>,
+        right: Box<dyn AsCollided>,
+    ) -> ReRes<Self> {
+        for operand in [&left, &right] {
+            if !operand.is_solid() {
+                return Err(GameErr(NonSolidOperand(**operand.id())));
+            }
+        }
+        Ok(Self {
+            entity,
+            op,
+            left,
+            right,
+            charcoal: None,
+        })
+    }
+
+    pub fn union(
+        entity: Entity,
+        left: Box<dyn AsCollided>,
+        right: Box<dyn AsCollided>,
+    ) -> ReRes<Self> {
+        Self::new(entity, CsgOp::Union, left, right)
+    }
+
+    pub fn intersection(
+        entity: Entity,
+        left: Box<dyn AsCollided>,
+        right: Box<dyn AsCollided>,
+    ) -> ReRes<Self> {
+        Self::new(entity, CsgOp::Intersection, left, right)
+    }
+
+    /// Left solid with the right one carved out of it
+    pub fn difference(
+        entity: Entity,
+        left: Box<dyn AsCollided>,
+        right: Box<dyn AsCollided>,
+    ) -> ReRes<Self> {
+        Self::new(entity, CsgOp::Difference, left, right)
+    }
+
+    /// Draws the whole surface with the given charcoal instead of charcoals of operands
+    pub fn with_charcoal(mut self, charcoal: Charcoal) -> Self {
+        self.charcoal = Some(charcoal);
+        self
+    }
+
+    pub fn op(&self) -> CsgOp {
+        self.op
+    }
+
+    pub fn left(&self) -> &dyn AsCollided {
+        self.left.as_ref()
+    }
+
+    pub fn right(&self) -> &dyn AsCollided {
+        self.right.as_ref()
+    }
+
+    /// Intervals of both operands, `None` if any of them can't be computed
+    fn operands(
+        &self,
+        cs: &CoordSys,
+        inc: &Point,
+        dir: &Vector,
+    ) -> Option<(Vec<Interval>, Vec<Interval>)> {
+        Some((
+            self.left.intervals(cs, inc, dir)?,
+            self.right.intervals(cs, inc, dir)?,
+        ))
+    }
+}
+
+/// The first bound of intervals ahead of ray
+fn first_ahead(intervals: &[Interval]) -> Option<f64> {
+    intervals
+        .iter()
+        .flat_map(|(enter, exit)| [*enter, *exit])
+        .find(|t| *t >= 0.0)
+}
+
+/// Whether any of intervals has bound at `t`
+fn has_bound(intervals: &[Interval], t: f64) -> bool {
+    let eps = BOUND_EPS * t.abs().max(1.0);
+    intervals
+        .iter()
+        .any(|(enter, exit)| (enter - t).abs() < eps || (exit - t).abs() < eps)
+}
+
+impl AsEntity for Csg {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for Csg {
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        first_ahead(&self.intervals(cs, inc, dir)?).and_then(validate_collision)
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.charcoal.as_ref().map(|charcoal| charcoal.ignite(dist))
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    fn intervals(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Vec<Interval>> {
+        let (left, right) = self.operands(cs, inc, dir)?;
+        Some(self.op.combine(&left, &right))
+    }
+
+    fn is_solid(&self) -> bool {
+        true
+    }
+
+    /// Union is bounded by bounds of both operands, intersection and difference by the left one
+    fn bounds(&self) -> Option<Aabb> {
+        match self.op {
+            CsgOp::Union => Some(self.left.bounds()?.union(&self.right.bounds()?)),
+            CsgOp::Intersection => self.left.bounds().or_else(|| self.right.bounds()),
+            CsgOp::Difference => self.left.bounds(),
+        }
+    }
+
+    /// Hit of the operand surface the first bound belongs to, the operand is hit by ray going from
+    /// just before the bound so nested nodes report their surfaces as well. Normal of the carved out surface
+    /// of difference is reversed, so it's directed out of the node
+    fn hit(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        let (left, right) = self.operands(cs, inc, dir)?;
+        let dist = validate_collision(first_ahead(&self.op.combine(&left, &right))?)?;
+        let mut hit = Hit::new(inc, dir, dist, Rc::clone(self.entity.id()));
+        let (operand, reversed) = match has_bound(&left, dist) {
+            true => (self.left.as_ref(), false),
+            false => (self.right.as_ref(), self.op == CsgOp::Difference),
+        };
+        let probe = inc
+            .clone()
+            .mv(&dir.clone().resize(dist - PROBE_EPS * dist.max(1.0)))
+            .ok()
+            .and_then(|probe| operand.hit(cs, &probe, dir));
+        let (normal, uv) = match &probe {
+            Some(local) => (local.normal.clone(), local.uv),
+            None => (operand.normal(cs, &hit.pt), operand.uv(cs, &hit.pt)),
+        };
+        hit.normal = normal.map(|normal| match reversed {
+            true => normal.resize(-1.0),
+            false => normal,
+        });
+        hit.uv = uv;
+        if let Some(local) = &probe {
+            (hit.bary, hit.face) = (local.bary, local.face);
+        }
+        hit.charcoal = self
+            .charcoal
+            .as_ref()
+            .or(probe.and_then(|local| local.charcoal))
+            .or(operand.charcoal());
+        Some(hit)
+    }
+}
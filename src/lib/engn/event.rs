@@ -23,6 +23,8 @@ pub enum MovementEvent<Scn: AsScene> {
     MoveBack,
     MoveLeft,
     MoveRight,
+    ZoomIn,
+    ZoomOut,
     None(PhantomData<Scn>),
 }
 
@@ -47,6 +49,9 @@ impl<Scn: AsScene> From<Event> for MovementEvent<Scn> {
             (KeyCode::Char('s'), KeyModifiers::NONE) => Self::MoveBack,
             (KeyCode::Char('a'), KeyModifiers::NONE) => Self::MoveLeft,
             (KeyCode::Char('d'), KeyModifiers::NONE) => Self::MoveRight,
+            (KeyCode::Char('='), KeyModifiers::NONE) => Self::ZoomIn,
+            (KeyCode::Char('+'), KeyModifiers::NONE | KeyModifiers::SHIFT) => Self::ZoomIn,
+            (KeyCode::Char('-'), KeyModifiers::NONE) => Self::ZoomOut,
             (KeyCode::Char('c'), KeyModifiers::CONTROL) => std::process::exit(0),
             _ => Self::None(PhantomData),
         }
@@ -55,18 +60,29 @@ impl<Scn: AsScene> From<Event> for MovementEvent<Scn> {
 
 impl<Scn: AsScene> AsEvent<Scn> for MovementEvent<Scn> {}
 
+/// Default factor of single zoom step
+const ZOOM_STEP: f64 = 1.25;
+
 pub struct MovementEventSys {
     step: f64,
-    movement: [usize; 8],
+    zoom: f64,
+    movement: [usize; 10],
 }
 
 impl MovementEventSys {
     pub fn new(step: f64) -> Self {
         Self {
             step,
-            movement: [0; 8],
+            zoom: ZOOM_STEP,
+            movement: [0; 10],
         }
     }
+
+    /// Factor fields of view are narrowed by on `ZoomIn` and widened by on `ZoomOut`
+    pub fn with_zoom(mut self, zoom: f64) -> Self {
+        self.zoom = zoom;
+        self
+    }
 }
 
 impl<Scn: AsScene> AsEventSys<MovementEvent<Scn>, Scn> for MovementEventSys {
@@ -80,6 +96,8 @@ impl<Scn: AsScene> AsEventSys<MovementEvent<Scn>, Scn> for MovementEventSys {
             MovementEvent::MoveBack => self.movement[5] += 1,
             MovementEvent::MoveLeft => self.movement[6] += 1,
             MovementEvent::MoveRight => self.movement[7] += 1,
+            MovementEvent::ZoomIn => self.movement[8] += 1,
+            MovementEvent::ZoomOut => self.movement[9] += 1,
             MovementEvent::None(_) => (),
         }
     }
@@ -112,7 +130,12 @@ impl<Scn: AsScene> AsEventSys<MovementEvent<Scn>, Scn> for MovementEventSys {
         scene.validate_mv(cs, camera.pos(), &mut mv);
         camera.mv(&mv)?;
 
-        self.movement = [0; 8];
+        let zoom = self.movement[8] as i32 - self.movement[9] as i32;
+        if zoom != 0 {
+            camera.zoom(self.zoom.powi(zoom));
+        }
+
+        self.movement = [0; 10];
         Ok(())
     }
 }
@@ -128,6 +151,20 @@ where
     pub(crate) events: VecDeque<Evt>,
 }
 
+impl<Evt, Scn> EventQueue<Evt, Scn>
+where
+    Evt: AsEvent<Scn>,
+    Scn: AsScene,
+{
+    /// Constructs empty queue
+    pub fn new() -> Self {
+        Self {
+            phantom: PhantomData,
+            events: VecDeque::new(),
+        }
+    }
+}
+
 impl<Evt, Scn> AsEventSys<Evt, Scn> for EventQueue<Evt, Scn>
 where
     Evt: AsEvent<Scn>,
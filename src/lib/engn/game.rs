@@ -10,17 +10,25 @@ use {
         grid::*,
         math::*,
     },
-    std::{f64::consts::PI, marker::PhantomData, rc::Rc, time::Duration},
+    crossterm::event::{Event, KeyEvent},
+    std::{
+        f64::consts::PI,
+        marker::PhantomData,
+        rc::Rc,
+        time::{Duration, Instant},
+    },
     uuid::Uuid,
 };
 
-/// Struct responsible for storing current CoordSys and EntityList and running related scripts
+/// Struct responsible for storing current CoordSys and EntityList and running related scripts.
+/// Everything is drawn into `Term` that is the real console by default
 #[derive(Debug)]
-pub struct Game<Evt, EvtSys, Scn>
+pub struct Game<Evt, EvtSys, Scn, Term = Console>
 where
     Evt: AsEvent<Scn>,
     Scn: AsScene,
     EvtSys: AsEventSys<Evt, Scn>,
+    Term: AsTerminal,
 {
     phantom: PhantomData<Evt>,
     pub(crate) cs: CoordSys,
@@ -28,38 +36,40 @@ where
     pub(crate) scene: Scn,
     pub(crate) canvas: Canvas<Scn>,
     pub(crate) camera: Camera,
+    pub(crate) term: Term,
+    pub(crate) keep_aspect: bool,
 }
 
-impl<Evt, EvtSys, Scn> Game<Evt, EvtSys, Scn>
+impl<Evt, EvtSys, Scn> Game<Evt, EvtSys, Scn, Console>
 where
     Evt: AsEvent<Scn>,
     Scn: AsScene,
     EvtSys: AsEventSys<Evt, Scn>,
 {
-    /// Constructor for `Game` taking `Conf` and returning `ReRes` if something fails
-    pub fn new(mut conf: Conf, scene: Scn, es: EvtSys) -> ReRes<Self> {
-        set_biform(Matrix::identity(3));
-
-        set_exact_mode();
-        set_precision(conf.precision);
-
-        let cs = CoordSys::new(
-            conf.initpt.clone(),
-            Basis::new(Matrix::identity(3).to_multicol())?,
-        )?;
-
-        let size = console::init()?;
-        let mut size = ((size.0 - 3) as usize, size.1 as usize);
-        if size.0 % 2 == 0 {
-            size.0 -= 1
-        }
-        if size.1 % 2 == 0 {
-            size.1 -= 1
-        }
+    /// Constructor for `Game` drawing into the real console,
+    /// takes `Conf` and returns `ReRes` if something fails
+    pub fn new(conf: Conf, scene: Scn, es: EvtSys) -> ReRes<Self> {
+        Self::with_term(conf, scene, es, Console::new())
+    }
+}
+
+impl<Evt, EvtSys, Scn, Term> Game<Evt, EvtSys, Scn, Term>
+where
+    Evt: AsEvent<Scn>,
+    Scn: AsScene,
+    EvtSys: AsEventSys<Evt, Scn>,
+    Term: AsTerminal,
+{
+    /// Constructor for `Game` drawing into the given terminal,
+    /// takes `Conf` and returns `ReRes` if something fails
+    pub fn with_term(mut conf: Conf, scene: Scn, es: EvtSys, mut term: Term) -> ReRes<Self> {
+        let cs = init_space(&conf)?;
+
+        let size = canvas_size(term.init()?);
 
         let hfov = match conf.hfov {
             Some(val) => val,
-            None => (size.0 as f64) * conf.wfov / (size.1 as f64),
+            None => conf.render_mode.hfov(conf.wfov, size),
         };
 
         let camera = Camera::new(
@@ -67,11 +77,14 @@ where
             conf.angle_discr,
             conf.wfov * PI,
             hfov * PI,
-            size.clone(),
+            conf.render_mode.samples(size),
             conf.draw_dist,
-        );
+        )
+        .with_projection(conf.projection)
+        .with_view_width(conf.view_width);
 
-        let canvas = Canvas::new(size, conf.charmap, conf.draw_dist);
+        let canvas = Canvas::new(size, conf.charmap, conf.draw_dist, conf.color_mode)
+            .with_render_mode(conf.render_mode);
 
         Ok(Self {
             phantom: PhantomData,
@@ -80,30 +93,90 @@ where
             scene,
             canvas,
             camera,
+            term,
+            keep_aspect: conf.hfov.is_none(),
         })
     }
 
     /// Running game: listening to events, handling them with respect to given implementation.
-    /// Never exits if such event isn't provided
+    /// Exits only when the terminal has no more events, so never exits on the real console
+    /// if such event isn't provided
     pub fn run(&mut self) -> ReRes<()> {
-        loop {
-            self.es.push(Evt::from(console::listen()?));
+        while let Some(event) = self.term.listen()? {
+            if let Event::Resize(cols, rows) = event {
+                self.resize((rows, cols))?;
+            }
+            self.es.push(Evt::from(event));
             self.es
                 .handle_all(&self.cs, &mut self.camera, &mut self.scene)?;
             self.update()?;
         }
+        Ok(())
     }
 
-    /// Updates image on canvas and drawing it in console
-    fn update(&mut self) -> ReRes<()> {
+    /// Updates image on canvas and drawing it in terminal
+    pub fn update(&mut self) -> ReRes<()> {
         self.canvas.update(&self.camera, &self.cs, &self.scene)?;
-        self.canvas.draw()?;
+        self.canvas.draw(&mut self.term)?;
+        Ok(())
+    }
+
+    /// Plays camera path drawing its frames `1 / fps` seconds apart counting time of drawing as well.
+    /// Skip key of path jumps to its last keyframe, resize is handled and the other events are dropped
+    /// while waiting for the next frame
+    pub fn play(&mut self, path: &CameraPath) -> ReRes<()> {
+        let step = Duration::try_from_secs_f64(1.0 / path.fps()).unwrap_or_default();
+        let frames = path.frames();
+        for frame in 0..frames {
+            let deadline = Instant::now() + step;
+            if let Some(key) = path.sample(path.frame_time(frame)) {
+                key.apply(&mut self.camera)?;
+            }
+            self.update()?;
+            if frame + 1 == frames {
+                break;
+            }
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                match self.term.poll(remaining)? {
+                    None => break,
+                    Some(Event::Key(KeyEvent { code, .. })) if code == path.skip() => {
+                        if let Some(key) = path.keys().last() {
+                            key.apply(&mut self.camera)?;
+                        }
+                        return self.update();
+                    }
+                    Some(Event::Resize(cols, rows)) => self.resize((rows, cols))?,
+                    Some(_) => (),
+                }
+                if Instant::now() >= deadline {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fits canvas and camera into terminal of the given size as (rows, cols) and clears it for the full redraw.
+    /// Vertical field of view is computed again to keep proportions of picture unless it's given in `Conf`
+    pub fn resize(&mut self, term_size: (u16, u16)) -> ReRes<()> {
+        let size = canvas_size(term_size);
+        let render_mode = self.canvas.render_mode();
+        self.canvas.resize(size);
+        self.camera.resize(render_mode.samples(size));
+        if self.keep_aspect {
+            let wfov = self.camera.fov().0;
+            self.camera.set_fov(wfov, render_mode.hfov(wfov, size));
+        }
+        self.term.clear()?;
         Ok(())
     }
 
     /// Exits game process with printing useful message
-    pub fn ban(self) {
-        self.canvas.banner("BAN", Duration::from_secs(1)).ok();
+    pub fn ban(mut self) {
+        self.canvas
+            .banner(&mut self.term, "BAN", Duration::from_secs(1))
+            .ok();
         std::process::exit(0)
     }
 
@@ -121,4 +194,50 @@ where
     pub fn camera(&self) -> &Camera {
         &self.camera
     }
+
+    /// Mutable ref to `Camera` in current game
+    pub fn camera_mut(&mut self) -> &mut Camera {
+        &mut self.camera
+    }
+
+    /// Scene in current game
+    pub fn scene(&self) -> &Scn {
+        &self.scene
+    }
+
+    /// Terminal that current game draws into
+    pub fn term(&self) -> &Term {
+        &self.term
+    }
+
+    /// Mutable ref to terminal that current game draws into
+    pub fn term_mut(&mut self) -> &mut Term {
+        &mut self.term
+    }
+}
+
+/// Size of canvas as (rows, cols) fitting into terminal of the given size below its 3 top lines,
+/// both are odd so there is the central cell
+pub(crate) fn canvas_size(term_size: (u16, u16)) -> (usize, usize) {
+    let mut size = (term_size.0.saturating_sub(3) as usize, term_size.1 as usize);
+    if size.0 % 2 == 0 {
+        size.0 = size.0.saturating_sub(1).max(1)
+    }
+    if size.1 % 2 == 0 {
+        size.1 = size.1.saturating_sub(1).max(1)
+    }
+    size
+}
+
+/// Sets global bilinear form and precision from `Conf`, returns coordinate system of the game
+pub(crate) fn init_space(conf: &Conf) -> ReRes<CoordSys> {
+    set_biform(Matrix::identity(3));
+
+    set_exact_mode();
+    set_precision(conf.precision);
+
+    CoordSys::new(
+        conf.initpt.clone(),
+        Basis::new(Matrix::identity(3).to_multicol())?,
+    )
 }
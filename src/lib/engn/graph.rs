@@ -0,0 +1,461 @@
+//! Hierarchical scene graph: nodes are placed relative to their parents, so moving or rotating a node
+//! carries all of its descendants. World transforms are composed lazily on the first ray query after
+//! change and are cached until the node or any of its ancestors is changed again
+
+use {
+    super::instance::{local_ray, world_bounds, world_hit},
+    crate::{
+        engn::*,
+        errs::{GameErr::*, ReErr::*, ReRes},
+        math::*,
+    },
+    std::{
+        cell::{Cell as Flag, RefCell},
+        collections::HashMap,
+        rc::Rc,
+    },
+    uuid::Uuid,
+};
+
+/// Node of `SceneGraph` with transform `pt -> pos + dir * pt` of its frame into the frame of parent.
+/// Node without object is a pivot that only carries its children, eg hinge of door
+#[derive(Debug)]
+pub struct SceneNode {
+    pub(crate) entity: Entity,
+    pub(crate) parent: Option<Rc<Uuid>>,
+    pub(crate) children: Vec<Rc<Uuid>>,
+    pub(crate) pos: Point,
+    pub(crate) dir: Matrix,
+    pub(crate) object: Option<Box<dyn AsCollided>>,
+}
+
+impl SceneNode {
+    /// Pivot placed at `pos` of parent frame without rotation
+    pub fn new(entity: Entity, pos: Point) -> Self {
+        Self {
+            entity,
+            parent: None,
+            children: vec![],
+            pos,
+            dir: Matrix::identity(3),
+            object: None,
+        }
+    }
+
+    /// Object given in the frame of node
+    pub fn with_object(mut self, object: Box<dyn AsCollided>) -> Self {
+        self.object = Some(object);
+        self
+    }
+
+    pub fn object(&self) -> Option<&dyn AsCollided> {
+        self.object.as_deref()
+    }
+
+    pub fn parent(&self) -> Option<&Rc<Uuid>> {
+        self.parent.as_ref()
+    }
+
+    pub fn children(&self) -> &Vec<Rc<Uuid>> {
+        &self.children
+    }
+}
+
+impl AsEntity for SceneNode {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+/// Object of node in the frame of parent, children aren't collided
+impl AsCollided for SceneNode {
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        self.hit(cs, inc, dir).map(|hit| hit.dist)
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.object.as_ref()?.charmap(dist)
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.object.as_ref()?.charcoal()
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        Some(world_bounds(
+            &self.pos,
+            &self.dir,
+            &self.object.as_ref()?.bounds()?,
+        ))
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+
+    fn hit(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        let inv = self.dir.inv().ok()?;
+        let (local_inc, local_dir) = local_ray(&self.pos, &inv, inc, dir);
+        let local = self.object.as_ref()?.hit(cs, &local_inc, &local_dir)?;
+        Some(world_hit(inc, dir, Rc::clone(self.id()), &inv, local))
+    }
+}
+
+impl AsGameObject for SceneNode {
+    fn pos(&self) -> &Point {
+        &self.pos
+    }
+
+    fn pos_mut(&mut self) -> &mut Point {
+        &mut self.pos
+    }
+
+    fn dir(&self) -> &Matrix {
+        &self.dir
+    }
+
+    fn dir_mut(&mut self) -> &mut Matrix {
+        &mut self.dir
+    }
+
+    /// Rotates node frame in the plane of parent axes `from` and `to`
+    fn planar_rotate(&mut self, from: usize, to: usize, angle: f64) -> ReRes<()> {
+        *self.dir_mut() = Matrix::rotation(from, to, angle, 3).mul(self.dir());
+        self.dir().ag_failed()?;
+        Ok(())
+    }
+}
+
+/// Transform of node frame into the world, `inv` is `None` if it's degenerate
+#[derive(Debug)]
+pub(crate) struct World {
+    pos: Point,
+    dir: Matrix,
+    inv: Option<Matrix>,
+}
+
+/// Scene of nodes attached to each other. Ray gets the nearest hit of objects of all the nodes placed
+/// with their world transforms, hits have `Uuid` of node. Ray queries go through `Bvh` like in `SceneList`
+#[derive(Debug)]
+pub struct SceneGraph {
+    pub(crate) nodes: Vec<SceneNode>,
+    pub(crate) world: RefCell<Vec<Option<Rc<World>>>>,
+    pub(crate) lighting: Option<Lighting>,
+    pub(crate) background: Option<Cell>,
+    pub(crate) radius: f64,
+    pub(crate) bvh: RefCell<Option<Bvh>>,
+    pub(crate) refit: Flag<bool>,
+}
+
+impl SceneGraph {
+    /// Empty graph without lighting, background cell is left to renderer and movement isn't kept off objects
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![],
+            world: RefCell::new(vec![]),
+            lighting: None,
+            background: None,
+            radius: 0.0,
+            bvh: RefCell::new(None),
+            refit: Flag::new(false),
+        }
+    }
+
+    /// Surfaces having normals are lit, others are faded with distance
+    pub fn with_lighting(mut self, lighting: Lighting) -> Self {
+        self.lighting = Some(lighting);
+        self
+    }
+
+    /// Cell drawn where rays hit nothing
+    pub fn with_background(mut self, cell: Cell) -> Self {
+        self.background = Some(cell);
+        self
+    }
+
+    /// Distance that `validate_mv` keeps between moving point and objects
+    pub fn with_radius(mut self, radius: f64) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    fn index(&self, id: &Uuid) -> ReRes<usize> {
+        self.nodes
+            .iter()
+            .position(|node| node.id().as_ref() == id)
+            .ok_or(GameErr(UnknownNode(*id)))
+    }
+
+    /// Indices of the node and all of its descendants
+    fn subtree(&self, idx: usize) -> Vec<usize> {
+        let mut subtree = vec![idx];
+        let mut i = 0;
+        while i < subtree.len() {
+            for child in &self.nodes[subtree[i]].children {
+                if let Ok(child) = self.index(child) {
+                    subtree.push(child);
+                }
+            }
+            i += 1;
+        }
+        subtree
+    }
+
+    /// Adds node as child of `parent` or as root if it's `None`, returns id of the node
+    pub fn add(&mut self, parent: Option<&Rc<Uuid>>, mut node: SceneNode) -> ReRes<Rc<Uuid>> {
+        let id = Rc::clone(node.id());
+        if let Some(parent) = parent {
+            let idx = self.index(parent)?;
+            self.nodes[idx].children.push(Rc::clone(&id));
+        }
+        node.parent = parent.cloned();
+        self.nodes.push(node);
+        self.world.get_mut().push(None);
+        *self.bvh.get_mut() = None;
+        Ok(id)
+    }
+
+    /// Removes node with all of its descendants
+    pub fn remove(&mut self, id: &Rc<Uuid>) -> ReRes<()> {
+        let idx = self.index(id)?;
+        let removed = self
+            .subtree(idx)
+            .into_iter()
+            .map(|i| Rc::clone(self.nodes[i].id()))
+            .collect::<Vec<Rc<Uuid>>>();
+        if let Some(parent) = self.nodes[idx].parent.clone() {
+            let parent = self.index(&parent)?;
+            self.nodes[parent].children.retain(|child| child != id);
+        }
+        self.nodes.retain(|node| !removed.contains(node.id()));
+        *self.world.get_mut() = vec![None; self.nodes.len()];
+        *self.bvh.get_mut() = None;
+        Ok(())
+    }
+
+    /// Moves node with its descendants under `parent` or makes it root if it's `None`.
+    /// Local transform is kept, so node is placed relative to the new parent
+    pub fn attach(&mut self, id: &Rc<Uuid>, parent: Option<&Rc<Uuid>>) -> ReRes<()> {
+        let idx = self.index(id)?;
+        if let Some(parent) = parent {
+            let new = self.index(parent)?;
+            if self.subtree(idx).contains(&new) {
+                return Err(GameErr(CyclicNode(**parent)));
+            }
+            self.nodes[new].children.push(Rc::clone(id));
+        }
+        if let Some(old) = self.nodes[idx].parent.take() {
+            let old = self.index(&old)?;
+            self.nodes[old].children.retain(|child| child != id);
+        }
+        self.nodes[idx].parent = parent.cloned();
+        self.invalidate(idx);
+        Ok(())
+    }
+
+    /// Drops world transforms of node and its descendants, hierarchy is refitted on the next ray query
+    fn invalidate(&mut self, idx: usize) {
+        for i in self.subtree(idx) {
+            self.world.get_mut()[i] = None;
+        }
+        self.refit.set(true);
+    }
+
+    pub fn get(&self, id: &Rc<Uuid>) -> Option<&SceneNode> {
+        self.nodes
+            .iter()
+            .find(|node| node.id().as_ref() == id.as_ref())
+    }
+
+    /// Mutable ref to node with the given id if it exists,
+    /// world transforms of the node and its descendants are recomputed on the next ray query
+    pub fn get_mut(&mut self, id: &Rc<Uuid>) -> Option<&mut SceneNode> {
+        let idx = self.index(id).ok()?;
+        self.invalidate(idx);
+        Some(&mut self.nodes[idx])
+    }
+
+    /// Moves node with the given id on `vec` in the frame of its parent
+    pub fn mv(&mut self, id: &Rc<Uuid>, vec: &Vector) -> ReRes<()> {
+        let idx = self.index(id)?;
+        self.invalidate(idx);
+        self.nodes[idx].mv(vec)
+    }
+
+    /// Rotates node with the given id by Tait-Bryan angles
+    pub fn rotate_3d(&mut self, id: &Rc<Uuid>, x: f64, y: f64, z: f64) -> ReRes<()> {
+        let idx = self.index(id)?;
+        self.invalidate(idx);
+        self.nodes[idx].rotate_3d(x, y, z)
+    }
+
+    /// Rotates node with the given id in the plane of parent axes `from` and `to`
+    pub fn planar_rotate(
+        &mut self,
+        id: &Rc<Uuid>,
+        from: usize,
+        to: usize,
+        angle: f64,
+    ) -> ReRes<()> {
+        let idx = self.index(id)?;
+        self.invalidate(idx);
+        self.nodes[idx].planar_rotate(from, to, angle)
+    }
+
+    /// World transform of node composed with transforms of its ancestors, cached until any of them changes
+    fn world(&self, idx: usize) -> Rc<World> {
+        if let Some(world) = &self.world.borrow()[idx] {
+            return Rc::clone(world);
+        }
+        let node = &self.nodes[idx];
+        let parent = node
+            .parent
+            .as_ref()
+            .and_then(|parent| self.index(parent).ok())
+            .map(|parent| self.world(parent));
+        let (pos, dir) = match parent {
+            Some(parent) => {
+                let shift = (0..3)
+                    .map(|r| {
+                        (0..3)
+                            .map(|c| parent.dir.att(r, c) * node.pos[c])
+                            .sum::<f64>()
+                    })
+                    .collect::<Vec<f64>>();
+                (
+                    Point::new((0..3).map(|i| parent.pos[i] + shift[i]).collect()),
+                    parent.dir.mul(&node.dir),
+                )
+            }
+            None => (node.pos.clone(), node.dir.clone()),
+        };
+        let inv = dir.inv().ok();
+        let world = Rc::new(World { pos, dir, inv });
+        self.world.borrow_mut()[idx] = Some(Rc::clone(&world));
+        world
+    }
+
+    /// Position of node frame origin in the world
+    pub fn world_pos(&self, id: &Rc<Uuid>) -> Option<Point> {
+        Some(self.world(self.index(id).ok()?).pos.clone())
+    }
+
+    /// Orientation of node frame in the world
+    pub fn world_dir(&self, id: &Rc<Uuid>) -> Option<Matrix> {
+        Some(self.world(self.index(id).ok()?).dir.clone())
+    }
+
+    /// Bounds of node object in the world
+    fn world_object_bounds(&self, idx: usize) -> Option<Aabb> {
+        let world = self.world(idx);
+        let bounds = self.nodes[idx].object.as_ref()?.bounds()?;
+        Some(world_bounds(&world.pos, &world.dir, &bounds))
+    }
+
+    /// Hit of node object in the world
+    fn world_object_hit(
+        &self,
+        cs: &CoordSys,
+        idx: usize,
+        inc: &Point,
+        dir: &Vector,
+    ) -> Option<Hit<'_>> {
+        let node = &self.nodes[idx];
+        let object = node.object.as_ref()?;
+        let world = self.world(idx);
+        let inv = world.inv.as_ref()?;
+        let (local_inc, local_dir) = local_ray(&world.pos, inv, inc, dir);
+        let local = object.hit(cs, &local_inc, &local_dir)?;
+        Some(world_hit(inc, dir, Rc::clone(node.id()), inv, local))
+    }
+
+    /// Ground of node object in the world, objects rotated relative to the world aren't walked over
+    fn world_object_ground(&self, idx: usize, x: f64, y: f64) -> Option<f64> {
+        let object = self.nodes[idx].object.as_ref()?;
+        let world = self.world(idx);
+        let upright = (0..3)
+            .all(|r| (0..3).all(|c| aeq(world.dir.att(r, c), &if r == c { 1.0 } else { 0.0 })));
+        if !upright {
+            return None;
+        }
+        let height = object.ground(x - world.pos[0], y - world.pos[1])?;
+        Some(height + world.pos[2])
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl AsScene for SceneGraph {
+    /// The nearest hit of node objects found with hierarchy that is built or refitted if needed
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        {
+            let mut bvh = self.bvh.borrow_mut();
+            let bounds = || {
+                (0..self.nodes.len())
+                    .map(|i| self.world_object_bounds(i))
+                    .collect::<Vec<Option<Aabb>>>()
+            };
+            match bvh.as_mut() {
+                None => *bvh = Some(Bvh::build(&bounds())),
+                Some(bvh) if self.refit.get() => bvh.refit(&bounds()),
+                _ => {}
+            }
+            self.refit.set(false);
+        }
+        self.bvh
+            .borrow()
+            .as_ref()
+            .unwrap()
+            .nearest(inc, dir, |i| self.world_object_hit(cs, i, inc, dir))
+    }
+
+    fn shade(&self, cs: &CoordSys, dir: &Vector, hit: &Hit) -> Option<Cell> {
+        hit.shade(self.lighting.as_ref(), cs, dir)
+    }
+
+    fn background(&self, _cs: &CoordSys, _dir: &Vector) -> Option<Cell> {
+        self.background
+    }
+
+    /// Sweeps `mv` from `pos` and shortens it to stop at `radius` before the nearest object on the way.
+    /// Node objects having ground under the end of `mv` are walked over like in `SceneList::validate_mv`
+    fn validate_mv(&self, cs: &CoordSys, pos: &Point, mv: &mut Vector) {
+        let ground = |idx: usize, mv: &Vector| {
+            self.world_object_ground(idx, pos[0] + mv.at(0), pos[1] + mv.at(1))
+        };
+        let hit = Hit::nearest(
+            (0..self.nodes.len())
+                .filter(|idx| ground(*idx, mv).is_none())
+                .map(|idx| self.world_object_hit(cs, idx, pos, mv)),
+        );
+        if let Some(hit) = hit {
+            hit.stop(cs, mv, self.radius);
+        }
+        let height = (0..self.nodes.len())
+            .filter_map(|idx| ground(idx, mv))
+            .reduce(f64::max);
+        if let Some(height) = height {
+            *mv.at_mut(2) = height - pos[2];
+        }
+    }
+}
+
+impl Default for SceneGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,158 @@
+use {
+    crate::{engn::*, errs::ReRes},
+    crossterm::{
+        cursor::MoveTo,
+        event::Event,
+        style::{Color, Colors, Print, SetColors},
+        terminal::{Clear, ClearType},
+        QueueableCommand,
+    },
+    std::{collections::VecDeque, time::Duration},
+};
+
+/// In-memory terminal that captures each flushed frame as `Vec<String>`
+/// and gives scripted events instead of listening to the real input.
+/// Bytes are counted as if the same `crossterm` commands were written into the real console
+#[derive(Debug, Clone)]
+pub struct Headless {
+    pub(crate) size: (u16, u16),
+    pub(crate) cursor: (u16, u16),
+    pub(crate) screen: Vec<Vec<char>>,
+    pub(crate) colors: Vec<Vec<(Option<Color>, Option<Color>)>>,
+    pub(crate) curr_colors: (Option<Color>, Option<Color>),
+    pub(crate) frames: Vec<Vec<String>>,
+    pub(crate) events: VecDeque<Event>,
+    pub(crate) out: Vec<u8>,
+}
+
+impl Headless {
+    /// Constructs blank terminal of the given size without any scripted events
+    pub fn new(rows: u16, cols: u16) -> Self {
+        Self {
+            size: (rows, cols),
+            cursor: (0, 0),
+            screen: vec![vec![' '; cols as usize]; rows as usize],
+            colors: vec![vec![(None, None); cols as usize]; rows as usize],
+            curr_colors: (None, None),
+            frames: vec![],
+            events: VecDeque::new(),
+            out: vec![],
+        }
+    }
+
+    /// Appends events that will be given by `listen` in the same order
+    pub fn script(mut self, events: Vec<Event>) -> Self {
+        self.events.extend(events);
+        self
+    }
+
+    /// Appends single event that will be given by `listen` after all the already scripted ones
+    pub fn push_event(&mut self, event: Event) {
+        self.events.push_back(event);
+    }
+
+    /// All the flushed frames from the oldest to the newest
+    pub fn frames(&self) -> &Vec<Vec<String>> {
+        &self.frames
+    }
+
+    /// The last flushed frame if it exists
+    pub fn frame(&self) -> Option<&Vec<String>> {
+        self.frames.last()
+    }
+
+    /// Foreground and background colors of the cell in the given position,
+    /// `None` stands for the default color
+    pub fn colors(&self, row: usize, col: usize) -> (Option<Color>, Option<Color>) {
+        self.colors[row][col]
+    }
+
+    /// Current content of terminal including not flushed output
+    pub fn screen(&self) -> Vec<String> {
+        self.screen
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect()
+    }
+}
+
+impl AsTerminal for Headless {
+    fn init(&mut self) -> ReRes<(u16, u16)> {
+        Ok(self.size)
+    }
+
+    fn size(&self) -> ReRes<(u16, u16)> {
+        Ok(self.size)
+    }
+
+    /// Scripted `Event::Resize` resizes the screen keeping its content that still fits
+    fn listen(&mut self) -> ReRes<Option<Event>> {
+        let event = self.events.pop_front();
+        if let Some(Event::Resize(cols, rows)) = event {
+            self.size = (rows, cols);
+            let (rows, cols) = (rows as usize, cols as usize);
+            self.screen.resize(rows, vec![]);
+            self.screen
+                .iter_mut()
+                .for_each(|line| line.resize(cols, ' '));
+            self.colors.resize(rows, vec![]);
+            self.colors
+                .iter_mut()
+                .for_each(|line| line.resize(cols, (None, None)));
+        }
+        Ok(event)
+    }
+
+    /// Scripted events are given at once without waiting
+    fn poll(&mut self, _timeout: Duration) -> ReRes<Option<Event>> {
+        self.listen()
+    }
+
+    fn move_cursor(&mut self, row: u16, col: u16) -> ReRes<()> {
+        self.out.queue(MoveTo(col, row))?;
+        self.cursor = (row, col);
+        Ok(())
+    }
+
+    /// Prints characters into the screen without wrapping, those out of screen are dropped
+    fn print(&mut self, s: &str) -> ReRes<()> {
+        self.out.queue(Print(s))?;
+        let (row, mut col) = (self.cursor.0 as usize, self.cursor.1 as usize);
+        for ch in s.chars() {
+            if let Some(cell) = self.screen.get_mut(row).and_then(|line| line.get_mut(col)) {
+                *cell = ch;
+                self.colors[row][col] = self.curr_colors;
+            }
+            col += 1;
+        }
+        self.cursor.1 = col.min(u16::MAX as usize) as u16;
+        Ok(())
+    }
+
+    fn set_colors(&mut self, fg: Option<Color>, bg: Option<Color>) -> ReRes<()> {
+        self.out.queue(SetColors(Colors::new(
+            fg.unwrap_or(Color::Reset),
+            bg.unwrap_or(Color::Reset),
+        )))?;
+        self.curr_colors = (fg, bg);
+        Ok(())
+    }
+
+    fn clear(&mut self) -> ReRes<()> {
+        self.out.queue(Clear(ClearType::All))?;
+        for line in &mut self.screen {
+            line.fill(' ');
+        }
+        for line in &mut self.colors {
+            line.fill(self.curr_colors);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> ReRes<usize> {
+        self.frames.push(self.screen());
+        let bytes = self.out.len();
+        self.out.clear();
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,94 @@
+use {
+    crate::{engn::*, math::*},
+    std::rc::Rc,
+    uuid::Uuid,
+};
+
+/// Record of ray hitting some surface of the scene: what is hit, where and how it's to be shaded
+#[derive(Debug, Clone)]
+pub struct Hit<'s> {
+    /// Coefficient of ray direction resizing to get the hit point
+    pub dist: f64,
+    /// Hit point on the surface
+    pub pt: Point,
+    /// Unit normal of the surface in the hit point if surface defines it
+    pub normal: Option<Vector>,
+    /// `Uuid` of the hit entity
+    pub id: Rc<Uuid>,
+    /// Texture coordinates within [0, 1] of the hit point if surface defines them
+    pub uv: Option<(f64, f64)>,
+    /// Barycentric coordinates of the hit point within the hit triangle, weights of its vertices in their order
+    pub bary: Option<[f64; 3]>,
+    /// Index of the hit face of compound surface like mesh
+    pub face: Option<usize>,
+    /// Charcoal the hit surface is drawn with if it has one
+    pub charcoal: Option<&'s Charcoal>,
+}
+
+impl<'s> Hit<'s> {
+    /// Hit of entity with `id` by ray from `inc` along `dir` at distance `dist`,
+    /// without normal, texture and barycentric coordinates, face and charcoal
+    pub fn new(inc: &Point, dir: &Vector, dist: f64, id: Rc<Uuid>) -> Self {
+        Self {
+            dist,
+            pt: inc.clone().mv(&dir.clone().resize(dist)).unwrap(),
+            normal: None,
+            id,
+            uv: None,
+            bary: None,
+            face: None,
+            charcoal: None,
+        }
+    }
+
+    pub fn with_normal(mut self, normal: Vector) -> Self {
+        self.normal = Some(normal);
+        self
+    }
+
+    pub fn with_uv(mut self, uv: (f64, f64)) -> Self {
+        self.uv = Some(uv);
+        self
+    }
+
+    pub fn with_charcoal(mut self, charcoal: &'s Charcoal) -> Self {
+        self.charcoal = Some(charcoal);
+        self
+    }
+
+    /// Charcoal cell of the hit lit according to normal if both `lighting` and normal are given,
+    /// otherwise it's faded with distance. `None` if hit has no charcoal
+    pub fn shade(&self, lighting: Option<&Lighting>, cs: &CoordSys, dir: &Vector) -> Option<Cell> {
+        let charcoal = self.charcoal?;
+        match (lighting, &self.normal) {
+            (Some(lighting), Some(normal)) => {
+                Some(lighting.shade(charcoal, cs, &self.pt, normal, dir, self.dist))
+            }
+            _ => Some(charcoal.paint(self.dist)),
+        }
+    }
+
+    /// Shortens movement `mv` that has this hit on the way, so it stops at `radius` before the hit
+    pub fn stop(&self, cs: &CoordSys, mv: &mut Vector, radius: f64) {
+        let len = match cs.len(&mv.coord) {
+            Ok(len) if !aeq(&len, &0.0) => len,
+            _ => return,
+        };
+        let free = self.dist * len - radius;
+        if free < len {
+            *mv = mv.clone().resize(free.max(0.0) / len);
+        }
+    }
+
+    /// Value interpolated within the hit triangle from the given values in its vertices,
+    /// `None` if the hit has no barycentric coordinates
+    pub fn interpolate(&self, values: [f64; 3]) -> Option<f64> {
+        let bary = self.bary?;
+        Some((0..3).map(|i| bary[i] * values[i]).sum())
+    }
+
+    /// The hit with the minimal distance, `None` if there are no hits
+    pub fn nearest(hits: impl IntoIterator<Item = Option<Self>>) -> Option<Self> {
+        hits.into_iter().flatten().min_by_key(|hit| Float(hit.dist))
+    }
+}
@@ -0,0 +1,230 @@
+//! Instances of shared prototypes: many objects are placed with their own affine transforms while
+//! the geometry of prototype is stored once. Rays are transformed into the frame of prototype,
+//! so the prototype is collided as is and its hit is transformed back into the world
+
+use {
+    crate::{
+        engn::*,
+        errs::{MathErr::*, ReErr::*, ReRes},
+        math::*,
+    },
+    std::{cell::RefCell, collections::HashMap, rc::Rc},
+    uuid::Uuid,
+};
+
+/// Prototype placed with transform `pt -> pos + dir * pt` of its frame into the world.
+/// Hits have `Uuid` of instance, charcoal of instance overrides the charcoal of prototype
+#[derive(Debug)]
+pub struct Instance {
+    pub(crate) entity: Entity,
+    pub(crate) prototype: Rc<dyn AsCollided>,
+    pub(crate) pos: Point,
+    pub(crate) dir: Matrix,
+    pub(crate) inv: RefCell<Option<Matrix>>,
+    pub charcoal: Option<Charcoal>,
+}
+
+impl Instance {
+    /// Instance of `prototype` moved by `pos` without rotation and scaling
+    pub fn new(entity: Entity, prototype: Rc<dyn AsCollided>, pos: Point) -> Self {
+        Self {
+            entity,
+            prototype,
+            pos,
+            dir: Matrix::identity(3),
+            inv: RefCell::new(None),
+            charcoal: None,
+        }
+    }
+
+    /// Sets linear part of transform: rotation, scaling or shear. Degenerate transform is rejected
+    pub fn with_transform(mut self, dir: Matrix) -> ReRes<Self> {
+        dir.ag_failed()?;
+        for dim in [dir.rows(), dir.cols()] {
+            if dim != 3 {
+                return Err(MathErr(DimMismatch { lhs: dim, rhs: 3 }));
+            }
+        }
+        dir.inv()?;
+        self.dir = dir;
+        *self.inv.get_mut() = None;
+        Ok(self)
+    }
+
+    pub fn with_charcoal(mut self, charcoal: Charcoal) -> Self {
+        self.charcoal = Some(charcoal);
+        self
+    }
+
+    pub fn prototype(&self) -> &Rc<dyn AsCollided> {
+        &self.prototype
+    }
+
+    /// Inverse of linear part of transform, `None` if it's degenerate
+    fn inverse(&self) -> Option<Matrix> {
+        if self.inv.borrow().is_none() {
+            *self.inv.borrow_mut() = self.dir.inv().ok();
+        }
+        self.inv.borrow().clone()
+    }
+
+    /// Ray in the frame of prototype
+    fn local_ray(&self, inc: &Point, dir: &Vector) -> Option<(Point, Vector)> {
+        Some(local_ray(&self.pos, &self.inverse()?, inc, dir))
+    }
+}
+
+/// `m * v` for 3x3 matrix, transposed if `t`
+fn apply(m: &Matrix, v: [f64; 3], t: bool) -> Vector {
+    let at = |r, c| match t {
+        true => *m.att(c, r),
+        false => *m.att(r, c),
+    };
+    Vector::new(
+        (0..3)
+            .map(|r| (0..3).map(|c| at(r, c) * v[c]).sum())
+            .collect(),
+    )
+}
+
+/// Ray from `inc` along `dir` brought into the frame placed at `pos` with inverse `inv` of its linear part
+pub(crate) fn local_ray(pos: &Point, inv: &Matrix, inc: &Point, dir: &Vector) -> (Point, Vector) {
+    let rel = [0, 1, 2].map(|i| inc[i] - pos[i]);
+    (
+        apply(inv, rel, false),
+        apply(inv, [dir.at(0), dir.at(1), dir.at(2)], false),
+    )
+}
+
+/// Hit in the frame with inverse `inv` of its linear part brought back to the world ray from `inc` along `dir`.
+/// Normal is transformed by inverse transpose, texture and barycentric coordinates and face are kept
+pub(crate) fn world_hit<'s>(
+    inc: &Point,
+    dir: &Vector,
+    id: Rc<Uuid>,
+    inv: &Matrix,
+    local: Hit<'s>,
+) -> Hit<'s> {
+    let mut hit = Hit::new(inc, dir, local.dist, id);
+    hit.normal = local.normal.map(|normal| {
+        let world = apply(inv, [normal.at(0), normal.at(1), normal.at(2)], true);
+        let len = (0..3).map(|i| world.at(i).powi(2)).sum::<f64>().sqrt();
+        world.resize(1.0 / len)
+    });
+    hit.uv = local.uv;
+    hit.bary = local.bary;
+    hit.face = local.face;
+    hit.charcoal = local.charcoal;
+    hit
+}
+
+/// Bounds of `bounds` corners transformed by `pt -> pos + dir * pt`
+pub(crate) fn world_bounds(pos: &Point, dir: &Matrix, bounds: &Aabb) -> Aabb {
+    let (min, max) = (bounds.min(), bounds.max());
+    let (mut lo, mut hi) = (vec![f64::INFINITY; 3], vec![f64::NEG_INFINITY; 3]);
+    for corner in 0..8 {
+        let local = [0, 1, 2].map(|i| match corner >> i & 1 {
+            0 => min[i],
+            _ => max[i],
+        });
+        let world = apply(dir, local, false);
+        for i in 0..3 {
+            lo[i] = lo[i].min(pos[i] + world.at(i));
+            hi[i] = hi[i].max(pos[i] + world.at(i));
+        }
+    }
+    Aabb::new(lo, hi)
+}
+
+impl AsEntity for Instance {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for Instance {
+    /// Transform is affine, so coefficient of ray resizing is the same in both frames
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        let (inc, dir) = self.local_ray(inc, dir)?;
+        self.prototype.collide(cs, &inc, &dir)
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        match &self.charcoal {
+            Some(charcoal) => Some(charcoal.ignite(dist)),
+            None => self.prototype.charmap(dist),
+        }
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref().or(self.prototype.charcoal())
+    }
+
+    /// Bounds of transformed corners of prototype bounds
+    fn bounds(&self) -> Option<Aabb> {
+        Some(world_bounds(
+            &self.pos,
+            &self.dir,
+            &self.prototype.bounds()?,
+        ))
+    }
+
+    fn intervals(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Vec<Interval>> {
+        let (inc, dir) = self.local_ray(inc, dir)?;
+        self.prototype.intervals(cs, &inc, &dir)
+    }
+
+    fn is_solid(&self) -> bool {
+        self.prototype.is_solid()
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+
+    /// Hit of prototype in the world: normal is transformed by inverse transpose of transform,
+    /// texture and barycentric coordinates and face are kept
+    fn hit(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        let inv = self.inverse()?;
+        let (local_inc, local_dir) = local_ray(&self.pos, &inv, inc, dir);
+        let local = self.prototype.hit(cs, &local_inc, &local_dir)?;
+        let mut hit = world_hit(inc, dir, Rc::clone(self.entity.id()), &inv, local);
+        hit.charcoal = self.charcoal.as_ref().or(hit.charcoal);
+        Some(hit)
+    }
+}
+
+impl AsGameObject for Instance {
+    fn pos(&self) -> &Point {
+        &self.pos
+    }
+
+    fn pos_mut(&mut self) -> &mut Point {
+        &mut self.pos
+    }
+
+    fn dir(&self) -> &Matrix {
+        &self.dir
+    }
+
+    /// Mutable linear part of transform, its inverse is computed again on the next ray
+    fn dir_mut(&mut self) -> &mut Matrix {
+        *self.inv.get_mut() = None;
+        &mut self.dir
+    }
+
+    /// Rotates instance frame in the plane of world axes `from` and `to`
+    fn planar_rotate(&mut self, from: usize, to: usize, angle: f64) -> ReRes<()> {
+        *self.dir_mut() = Matrix::rotation(from, to, angle, 3).mul(self.dir());
+        self.dir().ag_failed()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+//! Lighting model of scenes: directional and point lights give diffuse term `N·L` by Lambert,
+//! that is summed with ambient light and faded with distance fog. The resulting brightness
+//! within [0, 1] selects character and dims colors of `Charcoal`
+
+use crate::{conf::Conf, engn::*, math::*};
+
+/// Source of light
+#[derive(Debug, Clone, PartialEq)]
+pub enum Light {
+    /// Infinitely far source which light goes along `dir`
+    Directional { dir: Vector, intensity: f64 },
+    /// Source at `pos` which light goes in all directions
+    Point { pos: Point, intensity: f64 },
+}
+
+impl Light {
+    /// Light going along `dir` from infinitely far source
+    pub fn directional(dir: Vector, intensity: f64) -> Self {
+        Self::Directional {
+            dir: dir.to_col(),
+            intensity,
+        }
+    }
+
+    /// Light going from `pos` in all directions
+    pub fn point(pos: Point, intensity: f64) -> Self {
+        Self::Point {
+            pos: pos.to_col(),
+            intensity,
+        }
+    }
+
+    pub fn intensity(&self) -> f64 {
+        match self {
+            Self::Directional { intensity, .. } | Self::Point { intensity, .. } => *intensity,
+        }
+    }
+
+    /// Unit vector from `pt` towards the source, `None` if `pt` is the position of point source
+    pub fn towards(&self, cs: &CoordSys, pt: &Point) -> Option<Vector> {
+        let mut vec = match self {
+            Self::Directional { dir, .. } => dir.clone().resize(-1.0),
+            Self::Point { pos, .. } => pos.df(pt).ok()?,
+        };
+        if aeq(&cs.len(&vec.coord).ok()?, &0.0) {
+            return None;
+        }
+        cs.normalize(&mut vec.coord);
+        Some(vec)
+    }
+
+    /// Diffuse term `N·L` multiplied to intensity, it's zero if surface is turned away from the source.
+    /// `normal` must be of length 1
+    pub fn lambert(&self, cs: &CoordSys, pt: &Point, normal: &Vector) -> f64 {
+        match self.towards(cs, pt) {
+            Some(vec) => match cs.scalar_prod(&normal.coord, &vec.coord) {
+                Ok(cos) => cos.max(0.0) * self.intensity(),
+                Err(_) => 0.0,
+            },
+            None => 0.0,
+        }
+    }
+}
+
+/// Set of lights with ambient light and distance where fog hides everything
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lighting {
+    pub(crate) lights: Vec<Light>,
+    pub(crate) ambient: f64,
+    pub(crate) fog: f64,
+}
+
+impl Lighting {
+    /// Lighting without any sources
+    pub fn new(ambient: f64, fog: f64) -> Self {
+        Self {
+            lights: vec![],
+            ambient,
+            fog,
+        }
+    }
+
+    /// Lighting with `AMBIENT_LIGHT` and `LIGHTS` from `Conf`, fog distance is `DRAW_DISTANCE`
+    pub fn from_conf(conf: &Conf) -> Self {
+        Self {
+            lights: conf.lights.clone(),
+            ambient: conf.ambient,
+            fog: conf.draw_dist,
+        }
+    }
+
+    /// Adds source of light
+    pub fn with_light(mut self, light: Light) -> Self {
+        self.lights.push(light);
+        self
+    }
+
+    pub fn lights(&self) -> &Vec<Light> {
+        &self.lights
+    }
+
+    pub fn ambient(&self) -> f64 {
+        self.ambient
+    }
+
+    pub fn fog(&self) -> f64 {
+        self.fog
+    }
+
+    /// Brightness within [0, 1] of surface point `pt` with unit `normal` seen along `dir` from distance `dist`.
+    /// Normal is turned towards the viewer, so both sides of surface are lit
+    pub fn brightness(
+        &self,
+        cs: &CoordSys,
+        pt: &Point,
+        normal: &Vector,
+        dir: &Vector,
+        dist: f64,
+    ) -> f64 {
+        let normal = match cs.scalar_prod(&normal.coord, &dir.coord) {
+            Ok(cos) if cos > 0.0 => normal.clone().resize(-1.0),
+            _ => normal.clone(),
+        };
+        let diffuse = self
+            .lights
+            .iter()
+            .map(|light| light.lambert(cs, pt, &normal))
+            .sum::<f64>();
+        let fog = 1.0 - dist / self.fog;
+        ((self.ambient + diffuse).clamp(0.0, 1.0) * fog).clamp(0.0, 1.0)
+    }
+
+    /// Cell of `charcoal` shaded with `brightness` of the surface point
+    pub fn shade(
+        &self,
+        charcoal: &Charcoal,
+        cs: &CoordSys,
+        pt: &Point,
+        normal: &Vector,
+        dir: &Vector,
+        dist: f64,
+    ) -> Cell {
+        charcoal.shade(self.brightness(cs, pt, normal, dir, dist))
+    }
+}
+
+impl Default for Lighting {
+    /// Full ambient light without sources, so brightness depends only on fog
+    fn default() -> Self {
+        Self::new(1.0, 100.0)
+    }
+}
@@ -16,6 +16,7 @@ use {
         any::{Any, TypeId},
         cell::RefCell,
         collections::hash_map::{Entry, HashMap},
+        f64::consts::PI,
         ops::{Index, IndexMut},
         rc::Rc,
     },
@@ -29,7 +30,12 @@ pub struct IdPool {
 }
 
 impl IdPool {
-    /// Empty constructor
+    /// Empty constructor of pool that isn't shared
+    pub fn new() -> Self {
+        Self { ids: vec![] }
+    }
+
+    /// Global pool shared within the whole game
     pub fn get() -> &'static mut Self {
         static mut IDPOOL: Option<IdPool> = None;
         unsafe {
@@ -94,7 +100,7 @@ impl AsEntity for Entity {
 ///
 #[derive(Debug)]
 pub struct EntityList {
-    pub(crate) entities: Vec<Rc<RefCell<dyn AsEntity>>>,
+    pub(crate) entities: Vec<Rc<RefCell<dyn AsCollided>>>,
 }
 
 impl EntityList {
@@ -104,9 +110,9 @@ impl EntityList {
 }
 
 impl AsEntityList for EntityList {
-    type Item = Rc<RefCell<dyn AsEntity>>;
+    type Item = Rc<RefCell<dyn AsCollided>>;
 
-    /// Appends new entity that must implement Entity
+    /// Appends new entity that must implement AsCollided
     fn append(&mut self, item: Self::Item) {
         self.entities.push(item);
     }
@@ -205,6 +211,43 @@ impl AsCollided for HypePlane {
             None
         }
     }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    /// Plane bounds half-space behind it, that is opposite to its normal
+    fn intervals(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Vec<Interval>> {
+        let denom = cs.scalar_prod(&dir.coord, &self.normal.coord).ok()?;
+        let numer = cs
+            .scalar_prod(&self.initpt.df(inc).ok()?.coord, &self.normal.coord)
+            .ok()?;
+        Some(if aeq(&denom, &0.0) {
+            match numer > 0.0 {
+                true => vec![(f64::NEG_INFINITY, f64::INFINITY)],
+                false => vec![],
+            }
+        } else if denom > 0.0 {
+            vec![(f64::NEG_INFINITY, numer / denom)]
+        } else {
+            vec![(numer / denom, f64::INFINITY)]
+        })
+    }
+
+    fn is_solid(&self) -> bool {
+        true
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+
+    /// Normal of plane is the same in each point
+    fn normal(&self, cs: &CoordSys, _pt: &Point) -> Option<Vector> {
+        let mut normal = self.normal.clone();
+        cs.normalize(&mut normal.coord);
+        Some(normal)
+    }
 }
 
 impl AsGameObject for HypePlane {
@@ -279,8 +322,9 @@ impl AsEntity for HypeEllipse {
     }
 }
 
-impl AsCollided for HypeEllipse {
-    fn collide(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+impl HypeEllipse {
+    /// Coefficients of quadratic equation on ray resizing coefficient of ray crossing ellipse
+    fn coefs(&self, inc: &Point, dir: &Vector) -> (f64, f64, f64) {
         let inc = self.basis.decompose(&inc.df(&self.center).unwrap());
         let dir = self.basis.decompose(dir);
         let (mut a, mut b, mut c) = (0.0, 0.0, -1.0);
@@ -289,6 +333,13 @@ impl AsCollided for HypeEllipse {
             b += 2.0 * dir.at(i) * inc.at(i) / self.semiaxis[i].powi(2);
             c += (inc.at(i) / self.semiaxis[i]).powi(2);
         }
+        (a, b, c)
+    }
+}
+
+impl AsCollided for HypeEllipse {
+    fn collide(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        let (a, b, c) = self.coefs(inc, dir);
         let d = b * b - 4.0 * a * c;
         if d < 0.0 {
             None
@@ -316,6 +367,77 @@ impl AsCollided for HypeEllipse {
             None
         }
     }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    fn intervals(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Vec<Interval>> {
+        let (a, b, c) = self.coefs(inc, dir);
+        Some(match solve_quadratic(a, b, c)[..] {
+            [enter, exit] => vec![(enter, exit)],
+            _ => vec![],
+        })
+    }
+
+    fn is_solid(&self) -> bool {
+        true
+    }
+
+    /// Semiaxes directed along basis vectors give half size along each axis:
+    /// square root of sum of squared projections
+    fn bounds(&self) -> Option<Aabb> {
+        let dim = self.center.dim();
+        let extent = (0..dim)
+            .map(|j| {
+                (0..dim)
+                    .map(|i| (self.basis.basis.att(i, j) * self.semiaxis[i]).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .collect::<Vec<f64>>();
+        Some(Aabb::around(&self.center, &extent))
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+
+    /// Normal is gradient of ellipse equation: decomposed point divided by squared semiaxes,
+    /// that is brought back from the ellipse basis with transposed inversed basis matrix
+    fn normal(&self, cs: &CoordSys, pt: &Point) -> Option<Vector> {
+        let local = local(&self.basis, &pt.df(&self.center).ok()?)?;
+        let inv = self.basis.inv()?;
+        let dim = self.center.dim();
+        let mut normal = Vector::new(vec![0.0; dim]);
+        for j in 0..dim {
+            *normal.at_mut(j) = (0..dim)
+                .map(|i| inv.att(i, j) * local.at(i) / self.semiaxis[i].powi(2))
+                .sum();
+        }
+        if aeq(&cs.len(&normal.coord).ok()?, &0.0) {
+            return None;
+        }
+        cs.normalize(&mut normal.coord);
+        Some(normal)
+    }
+
+    /// Spherical coordinates of the point decomposed in the ellipse basis and scaled to the unit sphere:
+    /// longitude and polar angle both mapped into [0, 1]. Defined only in 3D space
+    fn uv(&self, _cs: &CoordSys, pt: &Point) -> Option<(f64, f64)> {
+        if self.center.dim() != 3 {
+            return None;
+        }
+        let local = local(&self.basis, &pt.df(&self.center).ok()?)?;
+        let (x, y, z) = (
+            local.at(0) / self.semiaxis[0],
+            local.at(1) / self.semiaxis[1],
+            local.at(2) / self.semiaxis[2],
+        );
+        let u = 0.5 + y.atan2(x) / (2.0 * PI);
+        let v = z.clamp(-1.0, 1.0).acos() / PI;
+        Some((u, v))
+    }
 }
 
 impl AsGameObject for HypeEllipse {
@@ -332,6 +454,1166 @@ impl AsGameObject for HypeEllipse {
     }
 
     fn dir_mut(&mut self) -> &mut Matrix {
-        &mut self.basis.basis
+        self.basis.basis_mut()
+    }
+}
+
+/// Box in arbitrary dimension space defined with center point, direction vectors of its edges
+/// and half lengths of edges. It's axis-aligned with the default `Basis`
+#[derive(Debug)]
+pub struct HypeBox {
+    pub(crate) entity: Entity,
+    pub(crate) center: Point,
+    pub(crate) basis: Basis,
+    pub(crate) semiaxis: Vec<f64>,
+    pub charcoal: Option<Charcoal>,
+}
+
+impl HypeBox {
+    /// Constructs new `HypeBox`
+    pub fn new(
+        entity: Entity,
+        center: Point,
+        basis: Basis,
+        semiaxis: Vec<f64>,
+        charcoal: Option<Charcoal>,
+    ) -> ReRes<Self> {
+        if center.dim() != basis.basis.dim()? {
+            return Err(MathErr(DimMismatch {
+                lhs: center.dim(),
+                rhs: basis.basis.dim()?,
+            }));
+        } else if basis.basis.dim()? != semiaxis.len() {
+            return Err(MathErr(DimMismatch {
+                lhs: basis.basis.dim()?,
+                rhs: semiaxis.len(),
+            }));
+        }
+        Ok(Self {
+            entity,
+            center,
+            basis,
+            semiaxis,
+            charcoal,
+        })
+    }
+
+    /// Axis-aligned box between the given corners
+    pub fn aligned(
+        entity: Entity,
+        min: Point,
+        max: Point,
+        charcoal: Option<Charcoal>,
+    ) -> ReRes<Self> {
+        let dim = min.dim();
+        if dim != max.dim() {
+            return Err(MathErr(DimMismatch {
+                lhs: dim,
+                rhs: max.dim(),
+            }));
+        }
+        Self::new(
+            entity,
+            Point::new((0..dim).map(|i| (min[i] + max[i]) / 2.0).collect()),
+            Basis::new(Matrix::identity(dim).to_multicol())?,
+            (0..dim).map(|i| (max[i] - min[i]).abs() / 2.0).collect(),
+            charcoal,
+        )
+    }
+
+    /// Range of ray resizing coefficients between the latest entering and the earliest leaving of slabs
+    /// in the box basis, `None` if ray misses the box
+    fn slabs(&self, inc: &Point, dir: &Vector) -> Option<Interval> {
+        let inc = local(&self.basis, &inc.df(&self.center).ok()?)?;
+        let dir = local(&self.basis, dir)?;
+        let (mut near, mut far) = (f64::NEG_INFINITY, f64::INFINITY);
+        for i in 0..self.center.dim() {
+            let (o, d, h) = (inc.at(i), dir.at(i), self.semiaxis[i]);
+            if aeq(&d, &0.0) {
+                if h < o.abs() && !aeq(&o.abs(), &h) {
+                    return None;
+                }
+                continue;
+            }
+            let (t1, t2) = ((-h - o) / d, (h - o) / d);
+            near = near.max(t1.min(t2));
+            far = far.min(t1.max(t2));
+        }
+        match near > far && !aeq(&near, &far) {
+            true => None,
+            false => Some((near, far)),
+        }
+    }
+
+    /// Index of the face axis nearest to the point decomposed in the box basis
+    fn face(&self, local: &Vector) -> usize {
+        (0..self.center.dim())
+            .max_by_key(|i| Float((local.at(*i) / self.semiaxis[*i]).abs()))
+            .unwrap()
+    }
+}
+
+impl AsEntity for HypeBox {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for HypeBox {
+    /// Slab method in the box basis: ray is inside the box between the latest entering
+    /// and the earliest leaving of slabs. It's leaving distance if ray starts inside the box
+    fn collide(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        let (near, far) = self.slabs(inc, dir)?;
+        match near < 0.0 {
+            true => validate_collision(far),
+            false => validate_collision(near),
+        }
+    }
+
+    fn intervals(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Vec<Interval>> {
+        Some(self.slabs(inc, dir).into_iter().collect())
+    }
+
+    fn is_solid(&self) -> bool {
+        true
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        if let Some(charcoal) = &self.charcoal {
+            Some(charcoal.ignite(dist))
+        } else {
+            None
+        }
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    /// Box vertices are sum of semiaxes directed along basis vectors, so half size along each axis
+    /// is sum of absolute projections
+    fn bounds(&self) -> Option<Aabb> {
+        let dim = self.center.dim();
+        let extent = (0..dim)
+            .map(|j| {
+                (0..dim)
+                    .map(|i| (self.basis.basis.att(i, j) * self.semiaxis[i]).abs())
+                    .sum::<f64>()
+            })
+            .collect::<Vec<f64>>();
+        Some(Aabb::around(&self.center, &extent))
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+
+    /// Normal of the face the point lies on, brought back from the box basis
+    /// with transposed inversed basis matrix like normals of `HypeEllipse`
+    fn normal(&self, cs: &CoordSys, pt: &Point) -> Option<Vector> {
+        let local = local(&self.basis, &pt.df(&self.center).ok()?)?;
+        let inv = self.basis.inv()?;
+        let face = self.face(&local);
+        let sign = local.at(face).signum();
+        let dim = self.center.dim();
+        let mut normal = Vector::new(vec![0.0; dim]);
+        for j in 0..dim {
+            *normal.at_mut(j) = sign * inv.att(face, j);
+        }
+        cs.normalize(&mut normal.coord);
+        Some(normal)
+    }
+
+    /// Position within the face the point lies on along two other box axes mapped into [0, 1].
+    /// Defined only in 3D space
+    fn uv(&self, _cs: &CoordSys, pt: &Point) -> Option<(f64, f64)> {
+        if self.center.dim() != 3 {
+            return None;
+        }
+        let local = local(&self.basis, &pt.df(&self.center).ok()?)?;
+        let face = self.face(&local);
+        let (j, k) = ((face + 1) % 3, (face + 2) % 3);
+        let coord = |i: usize| ((local.at(i) / self.semiaxis[i] + 1.0) / 2.0).clamp(0.0, 1.0);
+        Some((coord(j.min(k)), coord(j.max(k))))
+    }
+}
+
+impl AsGameObject for HypeBox {
+    fn pos(&self) -> &Point {
+        &self.center
+    }
+
+    fn pos_mut(&mut self) -> &mut Point {
+        &mut self.center
+    }
+
+    fn dir(&self) -> &Matrix {
+        &self.basis.basis
+    }
+
+    fn dir_mut(&mut self) -> &mut Matrix {
+        self.basis.basis_mut()
+    }
+
+    fn rotate_3d(&mut self, x: f64, y: f64, z: f64) -> ReRes<()> {
+        rotate_basis(&mut self.basis, |basis| {
+            basis.mul(&Matrix::teit_bryan_rotation(x, y, z))
+        })
+    }
+
+    fn planar_rotate(&mut self, from: usize, to: usize, angle: f64) -> ReRes<()> {
+        rotate_basis(&mut self.basis, |basis| {
+            Matrix::rotation(from, to, angle, 3).mul(basis)
+        })
+    }
+}
+
+/// Convex polygon cut out of `HypePlane` in 3D space. Vertices are kept relative to the plane point,
+/// so moving the plane moves the whole polygon
+#[derive(Debug)]
+pub struct HypePolygon {
+    pub(crate) plane: HypePlane,
+    pub(crate) vertices: Vec<Vector>,
+}
+
+impl HypePolygon {
+    /// Constructs polygon on the plane through `initpt` with `normal` bounded by `vertices` in traversal order.
+    /// Vertices must lie on the plane and make convex polygon
+    pub fn new(
+        entity: Entity,
+        initpt: Point,
+        normal: Vector,
+        vertices: Vec<Point>,
+        charcoal: Option<Charcoal>,
+    ) -> ReRes<Self> {
+        if initpt.dim() != 3 {
+            return Err(MathErr(DimMismatch {
+                lhs: initpt.dim(),
+                rhs: 3,
+            }));
+        }
+        let plane = HypePlane::new(entity, initpt, normal, charcoal)?;
+        let mut offsets = vec![];
+        for (i, vertex) in vertices.iter().enumerate() {
+            let offset = vertex.df(&plane.initpt)?;
+            if !aeq(&offset.scalar_prod(&plane.normal)?, &0.0) {
+                return Err(GameErr(VertexOffPlane(i)));
+            }
+            offsets.push(offset);
+        }
+        let polygon = Self {
+            plane,
+            vertices: offsets,
+        };
+        if polygon.vertices.len() < 3 || !polygon.is_convex()? {
+            return Err(GameErr(NonConvexPolygon));
+        }
+        Ok(polygon)
+    }
+
+    /// Polygon on the plane of its first three vertices with normal directed by the right-hand rule
+    pub fn from_vertices(
+        entity: Entity,
+        vertices: Vec<Point>,
+        charcoal: Option<Charcoal>,
+    ) -> ReRes<Self> {
+        if vertices.len() < 3 {
+            return Err(GameErr(NonConvexPolygon));
+        }
+        let normal = vertices[1]
+            .df(&vertices[0])?
+            .vector_prod(&vertices[2].df(&vertices[0])?)?;
+        Self::new(entity, vertices[0].clone(), normal, vertices, charcoal)
+    }
+
+    /// Vertices of polygon in space
+    pub fn vertices(&self) -> Vec<Point> {
+        self.vertices
+            .iter()
+            .map(|offset| self.plane.initpt.clone().mv(offset).unwrap())
+            .collect()
+    }
+
+    /// Turns of edges along the normal, each one is positive for counterclockwise turn seen from normal
+    fn turns(&self, offset: Option<&Vector>) -> ReRes<Vec<f64>> {
+        let n = self.vertices.len();
+        (0..n)
+            .map(|i| {
+                let edge = self.vertices[(i + 1) % n].df(&self.vertices[i])?;
+                let to = match offset {
+                    Some(offset) => offset.df(&self.vertices[i])?,
+                    None => self.vertices[(i + 2) % n].df(&self.vertices[(i + 1) % n])?,
+                };
+                edge.vector_prod(&to)?.scalar_prod(&self.plane.normal)
+            })
+            .collect()
+    }
+
+    /// All non-degenerate turns of edges have the same sign
+    fn is_convex(&self) -> ReRes<bool> {
+        let turns = self.turns(None)?;
+        Ok(turns.iter().all(|t| aeq(t, &0.0) || *t > 0.0)
+            || turns.iter().all(|t| aeq(t, &0.0) || *t < 0.0))
+    }
+
+    /// Point given by its offset from the plane point is on the same side of all the edges
+    fn contains(&self, offset: &Vector) -> bool {
+        match self.turns(Some(offset)) {
+            Ok(turns) => {
+                turns.iter().all(|t| aeq(t, &0.0) || *t > 0.0)
+                    || turns.iter().all(|t| aeq(t, &0.0) || *t < 0.0)
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+impl AsEntity for HypePolygon {
+    fn id(&self) -> &Rc<Uuid> {
+        self.plane.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.plane.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.plane.props_mut()
+    }
+}
+
+impl AsCollided for HypePolygon {
+    /// Hit of the plane that is rejected if it's outside the polygon
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        let t = self.plane.collide(cs, inc, dir)?;
+        let pt = inc.clone().mv(&dir.clone().resize(t)).ok()?;
+        match self.contains(&pt.df(&self.plane.initpt).ok()?) {
+            true => Some(t),
+            false => None,
+        }
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.plane.charmap(dist)
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.plane.charcoal()
+    }
+
+    fn normal(&self, cs: &CoordSys, pt: &Point) -> Option<Vector> {
+        self.plane.normal(cs, pt)
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        let vertices = self.vertices();
+        Some(Aabb::new(
+            (0..3)
+                .map(|i| vertices.iter().map(|v| v[i]).fold(f64::INFINITY, f64::min))
+                .collect(),
+            (0..3)
+                .map(|i| {
+                    vertices
+                        .iter()
+                        .map(|v| v[i])
+                        .fold(f64::NEG_INFINITY, f64::max)
+                })
+                .collect(),
+        ))
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+}
+
+impl AsGameObject for HypePolygon {
+    fn pos(&self) -> &Point {
+        &self.plane.initpt
+    }
+
+    fn pos_mut(&mut self) -> &mut Point {
+        &mut self.plane.initpt
+    }
+
+    fn dir(&self) -> &Matrix {
+        &self.plane.normal.coord
+    }
+
+    fn dir_mut(&mut self) -> &mut Matrix {
+        &mut self.plane.normal.coord
+    }
+
+    /// Rotates normal and vertices around the plane point
+    fn rotate_3d(&mut self, x: f64, y: f64, z: f64) -> ReRes<()> {
+        rotate_vectors(
+            &Matrix::teit_bryan_rotation(x, y, z),
+            self.vertices.iter_mut().chain([&mut self.plane.normal]),
+        )
+    }
+
+    /// Rotates normal and vertices around the plane point
+    fn planar_rotate(&mut self, from: usize, to: usize, angle: f64) -> ReRes<()> {
+        rotate_vectors(
+            &Matrix::rotation(from, to, angle, 3),
+            self.vertices.iter_mut().chain([&mut self.plane.normal]),
+        )
+    }
+}
+
+/// Disc cut out of `HypePlane`, that is points of the plane within `radius` from the plane point
+#[derive(Debug)]
+pub struct HypeDisc {
+    pub(crate) plane: HypePlane,
+    pub(crate) radius: f64,
+}
+
+impl HypeDisc {
+    /// Constructs disc with `center` on the plane with `normal` and positive `radius`
+    pub fn new(
+        entity: Entity,
+        center: Point,
+        normal: Vector,
+        radius: f64,
+        charcoal: Option<Charcoal>,
+    ) -> ReRes<Self> {
+        ag_not_positive("radius", radius)?;
+        Ok(Self {
+            plane: HypePlane::new(entity, center, normal, charcoal)?,
+            radius,
+        })
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+impl AsEntity for HypeDisc {
+    fn id(&self) -> &Rc<Uuid> {
+        self.plane.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.plane.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.plane.props_mut()
+    }
+}
+
+impl AsCollided for HypeDisc {
+    /// Hit of the plane that is rejected if it's farther than radius from the center
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        let t = self.plane.collide(cs, inc, dir)?;
+        let pt = inc.clone().mv(&dir.clone().resize(t)).ok()?;
+        let dist = cs.len(&pt.df(&self.plane.initpt).ok()?.coord).ok()?;
+        match dist < self.radius || aeq(&dist, &self.radius) {
+            true => Some(t),
+            false => None,
+        }
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.plane.charmap(dist)
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.plane.charcoal()
+    }
+
+    fn normal(&self, cs: &CoordSys, pt: &Point) -> Option<Vector> {
+        self.plane.normal(cs, pt)
+    }
+
+    /// Position in polar coordinates around the center: angle within turn and distance within radius.
+    /// Defined only in 3D space
+    fn uv(&self, cs: &CoordSys, pt: &Point) -> Option<(f64, f64)> {
+        if self.plane.initpt.dim() != 3 {
+            return None;
+        }
+        let offset = pt.df(&self.plane.initpt).ok()?;
+        let normal = self.plane.normal.clone().normalize();
+        let axis = (0..3).min_by_key(|i| Float(normal.at(*i).abs()))?;
+        let mut helper = Vector::new(vec![0.0; 3]);
+        *helper.at_mut(axis) = 1.0;
+        let u = normal.vector_prod(&helper).ok()?.normalize();
+        let v = normal.vector_prod(&u).ok()?;
+        let angle = offset
+            .scalar_prod(&v)
+            .ok()?
+            .atan2(offset.scalar_prod(&u).ok()?);
+        let dist = cs.len(&offset.coord).ok()?;
+        Some((
+            angle.rem_euclid(2.0 * PI) / (2.0 * PI),
+            (dist / self.radius).clamp(0.0, 1.0),
+        ))
+    }
+
+    /// Disc extends along each axis by radius times sine of the angle between the axis and normal
+    fn bounds(&self) -> Option<Aabb> {
+        let normal = self.plane.normal.clone().normalize();
+        let extent = (0..normal.dim())
+            .map(|i| self.radius * (1.0 - normal.at(i).powi(2)).max(0.0).sqrt())
+            .collect::<Vec<f64>>();
+        Some(Aabb::around(&self.plane.initpt, &extent))
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+}
+
+impl AsGameObject for HypeDisc {
+    fn pos(&self) -> &Point {
+        &self.plane.initpt
+    }
+
+    fn pos_mut(&mut self) -> &mut Point {
+        &mut self.plane.initpt
+    }
+
+    fn dir(&self) -> &Matrix {
+        &self.plane.normal.coord
+    }
+
+    fn dir_mut(&mut self) -> &mut Matrix {
+        &mut self.plane.normal.coord
+    }
+
+    fn rotate_3d(&mut self, x: f64, y: f64, z: f64) -> ReRes<()> {
+        rotate_vectors(
+            &Matrix::teit_bryan_rotation(x, y, z),
+            [&mut self.plane.normal],
+        )
+    }
+
+    fn planar_rotate(&mut self, from: usize, to: usize, angle: f64) -> ReRes<()> {
+        rotate_vectors(
+            &Matrix::rotation(from, to, angle, 3),
+            [&mut self.plane.normal],
+        )
+    }
+}
+
+/// Replaces vectors by their products with `rotation`, vectors are kept if any product fails
+fn rotate_vectors<'a>(
+    rotation: &Matrix,
+    vectors: impl IntoIterator<Item = &'a mut Vector>,
+) -> ReRes<()> {
+    rotation.ag_failed()?;
+    let vectors = vectors.into_iter().collect::<Vec<_>>();
+    let rotated = vectors
+        .iter()
+        .map(|vec| {
+            let coord = rotation.mul(&vec.coord).to_col();
+            coord.ag_failed()?;
+            Ok(coord)
+        })
+        .collect::<ReRes<Vec<Matrix>>>()?;
+    for (vec, coord) in vectors.into_iter().zip(rotated) {
+        vec.coord = coord;
+    }
+    Ok(())
+}
+
+/// Replaces basis by `rotate` of its matrix computing inverse of the new basis
+fn rotate_basis(basis: &mut Basis, rotate: impl FnOnce(&Matrix) -> Matrix) -> ReRes<()> {
+    let rotated = rotate(&basis.basis);
+    rotated.ag_failed()?;
+    *basis = Basis::new(rotated.to_multicol())?;
+    Ok(())
+}
+
+/// Point or direction decomposed in the local basis, `None` if basis is degenerate
+fn local(basis: &Basis, vec: &Vector) -> Option<Vector> {
+    let local = basis.decompose(vec);
+    local.coord.ag_failed().ok()?;
+    Some(local)
+}
+
+/// Validates that `center` and `basis` are of 3D space, where solids of revolution are defined,
+/// and basis isn't degenerate
+fn ag_not_3d_solid(center: &Point, basis: &Basis) -> ReRes<()> {
+    for dim in [center.dim(), basis.basis.dim()?] {
+        if dim != 3 {
+            return Err(MathErr(DimMismatch { lhs: dim, rhs: 3 }));
+        }
+    }
+    match basis.inv() {
+        Some(_) => Ok(()),
+        None => Err(MathErr(NullDeterminant)),
+    }
+}
+
+/// Validates that size `val` named `key` is positive
+fn ag_not_positive(key: &'static str, val: f64) -> ReRes<()> {
+    match val > 0.0 {
+        true => Ok(()),
+        false => Err(GameErr(NonPositiveSize { key, val })),
+    }
+}
+
+/// Unit normal from gradient of surface equation in the local basis,
+/// brought back with transposed inversed basis matrix
+fn basis_normal(cs: &CoordSys, basis: &Basis, grad: [f64; 3]) -> Option<Vector> {
+    let inv = basis.inv()?;
+    let mut normal = Vector::new(vec![0.0; 3]);
+    for j in 0..3 {
+        *normal.at_mut(j) = (0..3).map(|i| inv.att(i, j) * grad[i]).sum();
+    }
+    if aeq(&cs.len(&normal.coord).ok()?, &0.0) {
+        return None;
+    }
+    cs.normalize(&mut normal.coord);
+    Some(normal)
+}
+
+/// Bounds of the local box with the given half sizes `extent` around local point `offset`
+fn basis_bounds(center: &Point, basis: &Basis, offset: [f64; 3], extent: [f64; 3]) -> Aabb {
+    let world = (0..3)
+        .map(|j| {
+            center[j]
+                + (0..3)
+                    .map(|i| basis.basis.att(i, j) * offset[i])
+                    .sum::<f64>()
+        })
+        .collect::<Vec<f64>>();
+    let extent = (0..3)
+        .map(|j| {
+            (0..3)
+                .map(|i| (basis.basis.att(i, j) * extent[i]).abs())
+                .sum::<f64>()
+        })
+        .collect::<Vec<f64>>();
+    Aabb::around(&Point::new(world), &extent)
+}
+
+/// Longitude around the local z axis and relative height, both mapped into [0, 1]
+fn revolution_uv(local: &Vector, height: f64) -> (f64, f64) {
+    let u = (local.at(1).atan2(local.at(0)) / (2.0 * PI)).rem_euclid(1.0);
+    (u, (local.at(2) / height).clamp(0.0, 1.0))
+}
+
+/// Sorted crossings of the whole ray line with the capped frustum along the local z axis from `z = 0`
+/// with radius `radii[0]` up to `z = height` with radius `radii[1]`: side is the cone quadric, caps are discs
+fn frustum_crossings(inc: &Vector, dir: &Vector, radii: [f64; 2], height: f64) -> Vec<f64> {
+    let (o, d) = (
+        [inc.at(0), inc.at(1), inc.at(2)],
+        [dir.at(0), dir.at(1), dir.at(2)],
+    );
+    let k = (radii[1] - radii[0]) / height;
+    let r = radii[0] + k * o[2];
+    let a = d[0] * d[0] + d[1] * d[1] - k * k * d[2] * d[2];
+    let b = 2.0 * (o[0] * d[0] + o[1] * d[1] - r * k * d[2]);
+    let c = o[0] * o[0] + o[1] * o[1] - r * r;
+    let z = |t: f64| o[2] + t * d[2];
+    let side = solve_quadratic(a, b, c)
+        .into_iter()
+        .filter(|t| (0.0..=height).contains(&z(*t)));
+
+    let caps = [(0.0, radii[0]), (height, radii[1])]
+        .into_iter()
+        .filter(|(_, radius)| *radius > 0.0 && !aeq(&d[2], &0.0))
+        .map(|(z0, radius)| ((z0 - o[2]) / d[2], radius))
+        .filter(|(t, radius)| {
+            let (x, y) = (o[0] + t * d[0], o[1] + t * d[1]);
+            x * x + y * y <= radius * radius
+        })
+        .map(|(t, _)| t);
+
+    let mut crossings = side.chain(caps).collect::<Vec<f64>>();
+    crossings.sort_by_key(|t| Float(*t));
+    crossings.dedup_by(|l, r| aeq(l, r));
+    crossings
+}
+
+/// The nearest crossing with frustum ahead of ray
+fn frustum_collide(inc: &Vector, dir: &Vector, radii: [f64; 2], height: f64) -> Option<f64> {
+    frustum_crossings(inc, dir, radii, height)
+        .into_iter()
+        .find(|t| *t >= 0.0)
+        .and_then(validate_collision)
+}
+
+/// Sorted crossings of convex or ring solid paired into intervals, unpaired tangent crossing is dropped
+fn pair_crossings(crossings: Vec<f64>) -> Vec<Interval> {
+    crossings
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect()
+}
+
+/// Gradient of frustum surface in the local point: cap normal on caps, cone gradient on the side
+fn frustum_grad(local: &Vector, radii: [f64; 2], height: f64) -> [f64; 3] {
+    let eps = 1e-9 * height.max(1.0);
+    let (x, y, z) = (local.at(0), local.at(1), local.at(2));
+    let k = (radii[1] - radii[0]) / height;
+    let r = radii[0] + k * z;
+    let on_side = (x * x + y * y).sqrt() >= r - eps;
+    if z.abs() < eps && !on_side {
+        [0.0, 0.0, -1.0]
+    } else if (z - height).abs() < eps && !on_side {
+        [0.0, 0.0, 1.0]
+    } else {
+        [x, y, -r * k]
+    }
+}
+
+/// Finite cylinder with caps in 3D space. Its axis goes from `center` of the bottom cap
+/// along the third basis vector, cross section is ellipse if basis isn't orthonormal
+#[derive(Debug)]
+pub struct HypeCylinder {
+    pub(crate) entity: Entity,
+    pub(crate) center: Point,
+    pub(crate) basis: Basis,
+    pub(crate) radius: f64,
+    pub(crate) height: f64,
+    pub charcoal: Option<Charcoal>,
+}
+
+impl HypeCylinder {
+    /// Constructs cylinder of the given `radius` and `height` along the axis
+    pub fn new(
+        entity: Entity,
+        center: Point,
+        basis: Basis,
+        radius: f64,
+        height: f64,
+        charcoal: Option<Charcoal>,
+    ) -> ReRes<Self> {
+        ag_not_3d_solid(&center, &basis)?;
+        ag_not_positive("radius", radius)?;
+        ag_not_positive("height", height)?;
+        Ok(Self {
+            entity,
+            center,
+            basis,
+            radius,
+            height,
+            charcoal,
+        })
+    }
+}
+
+impl AsEntity for HypeCylinder {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for HypeCylinder {
+    fn collide(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        let inc = local(&self.basis, &inc.df(&self.center).ok()?)?;
+        let dir = local(&self.basis, dir)?;
+        frustum_collide(&inc, &dir, [self.radius; 2], self.height)
+    }
+
+    fn intervals(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Vec<Interval>> {
+        let inc = local(&self.basis, &inc.df(&self.center).ok()?)?;
+        let dir = local(&self.basis, dir)?;
+        Some(pair_crossings(frustum_crossings(
+            &inc,
+            &dir,
+            [self.radius; 2],
+            self.height,
+        )))
+    }
+
+    fn is_solid(&self) -> bool {
+        true
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.charcoal.as_ref().map(|charcoal| charcoal.ignite(dist))
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        let (r, h) = (self.radius, self.height / 2.0);
+        Some(basis_bounds(
+            &self.center,
+            &self.basis,
+            [0.0, 0.0, h],
+            [r, r, h],
+        ))
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+
+    fn normal(&self, cs: &CoordSys, pt: &Point) -> Option<Vector> {
+        let local = local(&self.basis, &pt.df(&self.center).ok()?)?;
+        let grad = frustum_grad(&local, [self.radius; 2], self.height);
+        basis_normal(cs, &self.basis, grad)
+    }
+
+    /// Longitude around the axis and relative height
+    fn uv(&self, _cs: &CoordSys, pt: &Point) -> Option<(f64, f64)> {
+        let local = local(&self.basis, &pt.df(&self.center).ok()?)?;
+        Some(revolution_uv(&local, self.height))
+    }
+}
+
+impl AsGameObject for HypeCylinder {
+    fn pos(&self) -> &Point {
+        &self.center
+    }
+
+    fn pos_mut(&mut self) -> &mut Point {
+        &mut self.center
+    }
+
+    fn dir(&self) -> &Matrix {
+        &self.basis.basis
+    }
+
+    fn dir_mut(&mut self) -> &mut Matrix {
+        self.basis.basis_mut()
+    }
+
+    fn rotate_3d(&mut self, x: f64, y: f64, z: f64) -> ReRes<()> {
+        rotate_basis(&mut self.basis, |basis| {
+            basis.mul(&Matrix::teit_bryan_rotation(x, y, z))
+        })
+    }
+
+    fn planar_rotate(&mut self, from: usize, to: usize, angle: f64) -> ReRes<()> {
+        rotate_basis(&mut self.basis, |basis| {
+            Matrix::rotation(from, to, angle, 3).mul(basis)
+        })
+    }
+}
+
+/// Finite cone with caps in 3D space. Its axis goes from `center` of the bottom cap along
+/// the third basis vector, radius changes linearly from the bottom one to the top one.
+/// It's truncated cone unless one of radii is zero
+#[derive(Debug)]
+pub struct HypeCone {
+    pub(crate) entity: Entity,
+    pub(crate) center: Point,
+    pub(crate) basis: Basis,
+    pub(crate) radii: [f64; 2],
+    pub(crate) height: f64,
+    pub charcoal: Option<Charcoal>,
+}
+
+impl HypeCone {
+    /// Constructs cone with the bottom and the top `radii` and `height` along the axis.
+    /// One of radii may be zero for the cone with apex
+    pub fn new(
+        entity: Entity,
+        center: Point,
+        basis: Basis,
+        radii: [f64; 2],
+        height: f64,
+        charcoal: Option<Charcoal>,
+    ) -> ReRes<Self> {
+        ag_not_3d_solid(&center, &basis)?;
+        for radius in radii {
+            if radius < 0.0 || radius.is_nan() {
+                return Err(GameErr(NonPositiveSize {
+                    key: "radii",
+                    val: radius,
+                }));
+            }
+        }
+        ag_not_positive("radii", radii[0].max(radii[1]))?;
+        ag_not_positive("height", height)?;
+        Ok(Self {
+            entity,
+            center,
+            basis,
+            radii,
+            height,
+            charcoal,
+        })
+    }
+}
+
+impl AsEntity for HypeCone {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for HypeCone {
+    fn collide(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        let inc = local(&self.basis, &inc.df(&self.center).ok()?)?;
+        let dir = local(&self.basis, dir)?;
+        frustum_collide(&inc, &dir, self.radii, self.height)
+    }
+
+    fn intervals(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Vec<Interval>> {
+        let inc = local(&self.basis, &inc.df(&self.center).ok()?)?;
+        let dir = local(&self.basis, dir)?;
+        Some(pair_crossings(frustum_crossings(
+            &inc,
+            &dir,
+            self.radii,
+            self.height,
+        )))
+    }
+
+    fn is_solid(&self) -> bool {
+        true
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.charcoal.as_ref().map(|charcoal| charcoal.ignite(dist))
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        let (r, h) = (self.radii[0].max(self.radii[1]), self.height / 2.0);
+        Some(basis_bounds(
+            &self.center,
+            &self.basis,
+            [0.0, 0.0, h],
+            [r, r, h],
+        ))
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+
+    fn normal(&self, cs: &CoordSys, pt: &Point) -> Option<Vector> {
+        let local = local(&self.basis, &pt.df(&self.center).ok()?)?;
+        let grad = frustum_grad(&local, self.radii, self.height);
+        basis_normal(cs, &self.basis, grad)
+    }
+
+    /// Longitude around the axis and relative height
+    fn uv(&self, _cs: &CoordSys, pt: &Point) -> Option<(f64, f64)> {
+        let local = local(&self.basis, &pt.df(&self.center).ok()?)?;
+        Some(revolution_uv(&local, self.height))
+    }
+}
+
+impl AsGameObject for HypeCone {
+    fn pos(&self) -> &Point {
+        &self.center
+    }
+
+    fn pos_mut(&mut self) -> &mut Point {
+        &mut self.center
+    }
+
+    fn dir(&self) -> &Matrix {
+        &self.basis.basis
+    }
+
+    fn dir_mut(&mut self) -> &mut Matrix {
+        self.basis.basis_mut()
+    }
+
+    fn rotate_3d(&mut self, x: f64, y: f64, z: f64) -> ReRes<()> {
+        rotate_basis(&mut self.basis, |basis| {
+            basis.mul(&Matrix::teit_bryan_rotation(x, y, z))
+        })
+    }
+
+    fn planar_rotate(&mut self, from: usize, to: usize, angle: f64) -> ReRes<()> {
+        rotate_basis(&mut self.basis, |basis| {
+            Matrix::rotation(from, to, angle, 3).mul(basis)
+        })
+    }
+}
+
+/// Torus in 3D space around the third basis vector through `center`. Tube of `minor` radius
+/// goes around the circle of `major` radius in the plane of the first two basis vectors
+#[derive(Debug)]
+pub struct HypeTorus {
+    pub(crate) entity: Entity,
+    pub(crate) center: Point,
+    pub(crate) basis: Basis,
+    pub(crate) major: f64,
+    pub(crate) minor: f64,
+    pub charcoal: Option<Charcoal>,
+}
+
+impl HypeTorus {
+    /// Constructs torus with `major` radius of the tube circle and `minor` radius of the tube,
+    /// tube can't be wider than the circle
+    pub fn new(
+        entity: Entity,
+        center: Point,
+        basis: Basis,
+        major: f64,
+        minor: f64,
+        charcoal: Option<Charcoal>,
+    ) -> ReRes<Self> {
+        ag_not_3d_solid(&center, &basis)?;
+        ag_not_positive("major", major)?;
+        ag_not_positive("minor", minor)?;
+        if minor > major {
+            return Err(GameErr(SelfCrossingTorus { major, minor }));
+        }
+        Ok(Self {
+            entity,
+            center,
+            basis,
+            major,
+            minor,
+            charcoal,
+        })
+    }
+
+    /// Sorted crossings of the whole ray line with torus. Substitutes ray into
+    /// `(|p|^2 + R^2 - r^2)^2 = 4 R^2 (x^2 + y^2)` and solves the quartic,
+    /// direction is normalized in the torus basis to keep coefficients well scaled
+    fn crossings(&self, inc: &Point, dir: &Vector) -> Option<Vec<f64>> {
+        let inc = local(&self.basis, &inc.df(&self.center).ok()?)?;
+        let dir = local(&self.basis, dir)?;
+        let (o, mut d) = (
+            [inc.at(0), inc.at(1), inc.at(2)],
+            [dir.at(0), dir.at(1), dir.at(2)],
+        );
+        let len = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+        if aeq(&len, &0.0) {
+            return None;
+        }
+        d.iter_mut().for_each(|c| *c /= len);
+
+        let rr = 4.0 * self.major * self.major;
+        let od = o[0] * d[0] + o[1] * d[1] + o[2] * d[2];
+        let k = o[0] * o[0] + o[1] * o[1] + o[2] * o[2] + self.major.powi(2) - self.minor.powi(2);
+        let roots = solve_quartic(
+            1.0,
+            4.0 * od,
+            4.0 * od * od + 2.0 * k - rr * (d[0] * d[0] + d[1] * d[1]),
+            4.0 * od * k - 2.0 * rr * (o[0] * d[0] + o[1] * d[1]),
+            k * k - rr * (o[0] * o[0] + o[1] * o[1]),
+        );
+        Some(roots.into_iter().map(|t| t / len).collect())
+    }
+}
+
+impl AsEntity for HypeTorus {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for HypeTorus {
+    fn collide(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        self.crossings(inc, dir)?
+            .into_iter()
+            .find(|t| *t >= 0.0)
+            .and_then(validate_collision)
+    }
+
+    fn intervals(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Vec<Interval>> {
+        Some(pair_crossings(self.crossings(inc, dir)?))
+    }
+
+    fn is_solid(&self) -> bool {
+        true
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.charcoal.as_ref().map(|charcoal| charcoal.ignite(dist))
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        let (r, h) = (self.major + self.minor, self.minor);
+        Some(basis_bounds(&self.center, &self.basis, [0.0; 3], [r, r, h]))
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+
+    /// Gradient of torus equation
+    fn normal(&self, cs: &CoordSys, pt: &Point) -> Option<Vector> {
+        let local = local(&self.basis, &pt.df(&self.center).ok()?)?;
+        let (x, y, z) = (local.at(0), local.at(1), local.at(2));
+        let k = x * x + y * y + z * z + self.major.powi(2) - self.minor.powi(2);
+        let rr = 2.0 * self.major * self.major;
+        basis_normal(cs, &self.basis, [x * (k - rr), y * (k - rr), z * k])
+    }
+
+    /// Longitude around the axis and angle around the tube, both mapped into [0, 1]
+    fn uv(&self, _cs: &CoordSys, pt: &Point) -> Option<(f64, f64)> {
+        let local = local(&self.basis, &pt.df(&self.center).ok()?)?;
+        let (x, y, z) = (local.at(0), local.at(1), local.at(2));
+        let u = (y.atan2(x) / (2.0 * PI)).rem_euclid(1.0);
+        let v = (z.atan2((x * x + y * y).sqrt() - self.major) / (2.0 * PI)).rem_euclid(1.0);
+        Some((u, v))
+    }
+}
+
+impl AsGameObject for HypeTorus {
+    fn pos(&self) -> &Point {
+        &self.center
+    }
+
+    fn pos_mut(&mut self) -> &mut Point {
+        &mut self.center
+    }
+
+    fn dir(&self) -> &Matrix {
+        &self.basis.basis
+    }
+
+    fn dir_mut(&mut self) -> &mut Matrix {
+        self.basis.basis_mut()
+    }
+
+    fn rotate_3d(&mut self, x: f64, y: f64, z: f64) -> ReRes<()> {
+        rotate_basis(&mut self.basis, |basis| {
+            basis.mul(&Matrix::teit_bryan_rotation(x, y, z))
+        })
+    }
+
+    fn planar_rotate(&mut self, from: usize, to: usize, angle: f64) -> ReRes<()> {
+        rotate_basis(&mut self.basis, |basis| {
+            Matrix::rotation(from, to, angle, 3).mul(basis)
+        })
     }
 }
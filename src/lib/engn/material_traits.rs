@@ -9,7 +9,6 @@ use {
         grid::*,
         math::*,
     },
-    either::Either,
     std::{
         any::{Any, TypeId},
         cell::RefCell,
@@ -23,6 +22,9 @@ use {
 pub type PropKey = &'static str;
 pub type PropVal = Box<dyn Any>;
 
+/// Range of ray resizing coefficients from entering solid to leaving it, bounds may be infinite
+pub type Interval = (f64, f64);
+
 /// For material that can be indexed inside the `Game` instance with `Uuid` and can store properties within `HashMap`
 pub trait AsEntity {
     /// UUID of entity
@@ -78,6 +80,59 @@ pub trait AsCollided: AsEntity {
     fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64>;
 
     fn charmap(&self, dist: f64) -> Option<char>;
+
+    /// Unit normal of surface in the point `pt` lying on it, `None` if material doesn't define normals
+    fn normal(&self, _cs: &CoordSys, _pt: &Point) -> Option<Vector> {
+        None
+    }
+
+    /// Texture coordinates within [0, 1] of the point `pt` lying on surface,
+    /// `None` if material doesn't define them
+    fn uv(&self, _cs: &CoordSys, _pt: &Point) -> Option<(f64, f64)> {
+        None
+    }
+
+    /// Charcoal material is drawn with, `None` if material has no own charcoal
+    fn charcoal(&self) -> Option<&Charcoal> {
+        None
+    }
+
+    /// Axis-aligned bounds of material, `None` if it's unbounded
+    fn bounds(&self) -> Option<Aabb> {
+        None
+    }
+
+    /// Sorted disjoint intervals of the whole line of ray, including its part behind `inc`,
+    /// where it's inside material. `None` if material isn't solid, eg it's flat or open surface
+    fn intervals(&self, _cs: &CoordSys, _inc: &Point, _dir: &Vector) -> Option<Vec<Interval>> {
+        None
+    }
+
+    /// Material reports `intervals` for any ray, so it can be operand of `Csg`
+    fn is_solid(&self) -> bool {
+        false
+    }
+
+    /// Height that the viewer moving over the point `(x, y)` is kept at,
+    /// `None` if material isn't the ground to walk over there
+    fn ground(&self, _x: f64, _y: f64) -> Option<f64> {
+        None
+    }
+
+    /// Material as movable game object if it's such one
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        None
+    }
+
+    /// Full record of collision with ray: distance, point, normal, texture coordinates and charcoal
+    fn hit(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        let dist = self.collide(cs, inc, dir)?;
+        let mut hit = Hit::new(inc, dir, dist, Rc::clone(self.id()));
+        hit.normal = self.normal(cs, &hit.pt);
+        hit.uv = self.uv(cs, &hit.pt);
+        hit.charcoal = self.charcoal();
+        Some(hit)
+    }
 }
 
 impl std::fmt::Debug for dyn AsCollided {
@@ -162,8 +217,46 @@ pub trait AsEntityList {
 }
 
 pub trait AsScene {
-    /// Computes minimal distance to entities
-    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Either<f64, char>;
+    /// The nearest hit of ray going from `inc` along `dir`, `None` if ray hits nothing
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>>;
+
+    /// Cell to be drawn for the given `hit` of ray going along `dir`.
+    /// It's charcoal of the hit faded with distance by default,
+    /// `None` leaves shading to renderer that paints by distance with its own charmap
+    fn shade(&self, _cs: &CoordSys, _dir: &Vector, hit: &Hit) -> Option<Cell> {
+        hit.charcoal.map(|charcoal| charcoal.paint(hit.dist))
+    }
+
+    /// Cell to be drawn where ray going along `dir` hits nothing,
+    /// `None` leaves it to renderer
+    fn background(&self, _cs: &CoordSys, _dir: &Vector) -> Option<Cell> {
+        None
+    }
 
     fn validate_mv(&self, cs: &CoordSys, pos: &Point, mv: &mut Vector);
 }
+
+/// For shape given by signed distance function: distance to the surface that is negative inside the shape.
+/// Any closure `Fn(&[f64; 3]) -> f64` is such shape. Distance mustn't overestimate the true one,
+/// otherwise sphere tracing may step over the surface
+pub trait AsSdf {
+    /// Signed distance from the point `pt` to the surface
+    fn dist(&self, pt: &[f64; 3]) -> f64;
+
+    /// Axis-aligned bounds of shape, `None` if it's unbounded or unknown
+    fn bounds(&self) -> Option<Aabb> {
+        None
+    }
+}
+
+impl<F: Fn(&[f64; 3]) -> f64> AsSdf for F {
+    fn dist(&self, pt: &[f64; 3]) -> f64 {
+        self(pt)
+    }
+}
+
+impl std::fmt::Debug for dyn AsSdf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SDF bounded by {:?}", self.bounds())
+    }
+}
@@ -0,0 +1,465 @@
+//! Triangles and triangle meshes in 3D space, rays are intersected by Möller–Trumbore algorithm
+//! giving barycentric coordinates of the hit point. `Mesh` owns vertex and index buffers in its own frame,
+//! they're transformed into the world once after moving or rotating and are reused by all the rays
+
+use {
+    crate::{
+        engn::*,
+        errs::{GameErr::*, MathErr::*, ReErr::*, ReRes},
+        math::*,
+    },
+    std::{cell::RefCell, collections::HashMap, rc::Rc},
+    uuid::Uuid,
+};
+
+/// Determinant below which ray is treated as parallel to triangle
+const PARALLEL_EPS: f64 = 1e-12;
+
+type V3 = [f64; 3];
+
+fn sub(l: &V3, r: &V3) -> V3 {
+    [l[0] - r[0], l[1] - r[1], l[2] - r[2]]
+}
+
+fn dot(l: &V3, r: &V3) -> f64 {
+    l[0] * r[0] + l[1] * r[1] + l[2] * r[2]
+}
+
+fn cross(l: &V3, r: &V3) -> V3 {
+    [
+        l[1] * r[2] - l[2] * r[1],
+        l[2] * r[0] - l[0] * r[2],
+        l[0] * r[1] - l[1] * r[0],
+    ]
+}
+
+fn v3(pt: &Point) -> V3 {
+    [pt[0], pt[1], pt[2]]
+}
+
+fn unit(v: &V3) -> Option<Vector> {
+    let len = dot(v, v).sqrt();
+    match aeq(&len, &0.0) {
+        true => None,
+        false => Some(Vector::new(vec![v[0] / len, v[1] / len, v[2] / len])),
+    }
+}
+
+/// Ray resizing coefficient and barycentric coordinates of the hit point with triangle `v`.
+/// Both sides of triangle are hit
+pub(crate) fn moller_trumbore(inc: &V3, dir: &V3, v: [&V3; 3]) -> Option<(f64, [f64; 3])> {
+    let (e1, e2) = (sub(v[1], v[0]), sub(v[2], v[0]));
+    let p = cross(dir, &e2);
+    let det = dot(&e1, &p);
+    if det.abs() < PARALLEL_EPS {
+        return None;
+    }
+    let s = sub(inc, v[0]);
+    let u = dot(&s, &p) / det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = cross(&s, &e1);
+    let w = dot(dir, &q) / det;
+    if w < 0.0 || 1.0 < u + w {
+        return None;
+    }
+    let t = validate_collision(dot(&e2, &q) / det)?;
+    Some((t, [1.0 - u - w, u, w]))
+}
+
+/// Single triangle given by its vertices, normal is directed by the right-hand rule
+#[derive(Debug)]
+pub struct Triangle {
+    pub(crate) entity: Entity,
+    pub(crate) vertices: [Point; 3],
+    pub charcoal: Option<Charcoal>,
+}
+
+impl Triangle {
+    /// Constructs triangle in 3D space
+    pub fn new(entity: Entity, vertices: [Point; 3], charcoal: Option<Charcoal>) -> ReRes<Self> {
+        for vertex in &vertices {
+            if vertex.dim() != 3 {
+                return Err(MathErr(DimMismatch {
+                    lhs: vertex.dim(),
+                    rhs: 3,
+                }));
+            }
+        }
+        Ok(Self {
+            entity,
+            vertices,
+            charcoal,
+        })
+    }
+
+    pub fn vertices(&self) -> &[Point; 3] {
+        &self.vertices
+    }
+}
+
+impl AsEntity for Triangle {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for Triangle {
+    fn collide(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        let v = self.vertices.iter().map(v3).collect::<Vec<V3>>();
+        moller_trumbore(&v3(inc), &v3(dir), [&v[0], &v[1], &v[2]]).map(|(t, _)| t)
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.charcoal.as_ref().map(|charcoal| charcoal.ignite(dist))
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    fn normal(&self, _cs: &CoordSys, _pt: &Point) -> Option<Vector> {
+        let v = self.vertices.iter().map(v3).collect::<Vec<V3>>();
+        unit(&cross(&sub(&v[1], &v[0]), &sub(&v[2], &v[0])))
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        let v = &self.vertices;
+        Some(Aabb::new(
+            (0..3).map(|i| v[0][i].min(v[1][i]).min(v[2][i])).collect(),
+            (0..3).map(|i| v[0][i].max(v[1][i]).max(v[2][i])).collect(),
+        ))
+    }
+
+    /// Hit with barycentric coordinates of the hit point
+    fn hit(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        let v = self.vertices.iter().map(v3).collect::<Vec<V3>>();
+        let (t, bary) = moller_trumbore(&v3(inc), &v3(dir), [&v[0], &v[1], &v[2]])?;
+        let mut hit = Hit::new(inc, dir, t, Rc::clone(self.entity.id()));
+        hit.normal = self.normal(cs, &hit.pt);
+        hit.bary = Some(bary);
+        hit.charcoal = self.charcoal.as_ref();
+        Some(hit)
+    }
+}
+
+/// Vertices and normals of mesh transformed into the world with hierarchy over its faces
+#[derive(Debug)]
+struct MeshCache {
+    vertices: Vec<V3>,
+    face_normals: Vec<Option<Vector>>,
+    vertex_normals: Vec<V3>,
+    normals: Vec<V3>,
+    bvh: Bvh,
+}
+
+/// Mesh of triangles given by vertex buffer in the mesh frame and index buffer of faces.
+/// Frame is positioned at `pos` and oriented with columns of `dir`, world vertices are `pos + dir * vertex`.
+/// Faces may refer to explicit normals in their vertices and may be gathered into named groups
+#[derive(Debug)]
+pub struct Mesh {
+    pub(crate) entity: Entity,
+    pub(crate) vertices: Vec<Point>,
+    pub(crate) faces: Vec<[usize; 3]>,
+    pub(crate) normals: Vec<Vector>,
+    pub(crate) normal_faces: Vec<Option<[usize; 3]>>,
+    pub(crate) groups: Vec<(String, Vec<usize>)>,
+    pub(crate) pos: Point,
+    pub(crate) dir: Matrix,
+    pub(crate) smooth: bool,
+    pub(crate) cache: RefCell<Option<MeshCache>>,
+    pub charcoal: Option<Charcoal>,
+}
+
+impl Mesh {
+    /// Constructs mesh in 3D space with frame at the origin, each face is triple of indices in `vertices`
+    pub fn new(
+        entity: Entity,
+        vertices: Vec<Point>,
+        faces: Vec<[usize; 3]>,
+        charcoal: Option<Charcoal>,
+    ) -> ReRes<Self> {
+        if let Some(vertex) = vertices.iter().find(|vertex| vertex.dim() != 3) {
+            return Err(MathErr(DimMismatch {
+                lhs: vertex.dim(),
+                rhs: 3,
+            }));
+        }
+        if let Some(idx) = faces.iter().flatten().find(|idx| vertices.len() <= **idx) {
+            return Err(GameErr(InvalidMeshIndex(*idx)));
+        }
+        Ok(Self {
+            entity,
+            vertices,
+            normal_faces: vec![None; faces.len()],
+            faces,
+            normals: vec![],
+            groups: vec![],
+            pos: Point::default(),
+            dir: Matrix::identity(3),
+            smooth: false,
+            cache: RefCell::new(None),
+            charcoal,
+        })
+    }
+
+    /// Makes normals of hits interpolated from vertex normals instead of flat face ones
+    pub fn with_smooth(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self
+    }
+
+    /// Sets explicit normals in the mesh frame, each pair is face index and indices of its vertices normals.
+    /// Such faces are shaded with normals interpolated from the given ones whether mesh is smooth or not
+    pub fn with_normals(
+        mut self,
+        normals: Vec<Vector>,
+        normal_faces: Vec<(usize, [usize; 3])>,
+    ) -> ReRes<Self> {
+        if let Some(normal) = normals.iter().find(|normal| normal.dim() != 3) {
+            return Err(MathErr(DimMismatch {
+                lhs: normal.dim(),
+                rhs: 3,
+            }));
+        }
+        for (face, idx) in normal_faces {
+            if self.faces.len() <= face {
+                return Err(GameErr(InvalidMeshIndex(face)));
+            }
+            if let Some(idx) = idx.iter().find(|idx| normals.len() <= **idx) {
+                return Err(GameErr(InvalidMeshIndex(*idx)));
+            }
+            self.normal_faces[face] = Some(idx);
+        }
+        self.normals = normals;
+        *self.cache.get_mut() = None;
+        Ok(self)
+    }
+
+    /// Adds named group of faces given by their indices
+    pub fn with_group(mut self, name: &str, faces: Vec<usize>) -> ReRes<Self> {
+        if let Some(face) = faces.iter().find(|face| self.faces.len() <= **face) {
+            return Err(GameErr(InvalidMeshIndex(*face)));
+        }
+        self.groups.push((name.to_string(), faces));
+        Ok(self)
+    }
+
+    /// Indices of faces of the group with the given name
+    pub fn group(&self, name: &str) -> Option<&Vec<usize>> {
+        self.groups
+            .iter()
+            .find(|(group, _)| group == name)
+            .map(|(_, faces)| faces)
+    }
+
+    /// Name of the first group containing the face with the given index
+    pub fn group_of(&self, face: usize) -> Option<&str> {
+        self.groups
+            .iter()
+            .find(|(_, faces)| faces.contains(&face))
+            .map(|(group, _)| group.as_str())
+    }
+
+    pub fn groups(&self) -> &Vec<(String, Vec<usize>)> {
+        &self.groups
+    }
+
+    pub fn vertices(&self) -> &Vec<Point> {
+        &self.vertices
+    }
+
+    pub fn faces(&self) -> &Vec<[usize; 3]> {
+        &self.faces
+    }
+
+    /// Vertex of the face `face` with index `i` within it transformed into the world
+    pub fn world_vertex(&self, face: usize, i: usize) -> Point {
+        self.ensure_cache();
+        let cache = self.cache.borrow();
+        let v = cache.as_ref().unwrap().vertices[self.faces[face][i]];
+        Point::new(v.to_vec())
+    }
+
+    /// Transforms vertices into the world and builds hierarchy over faces if they aren't up to date
+    fn ensure_cache(&self) {
+        if self.cache.borrow().is_some() {
+            return;
+        }
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|vertex| {
+                let (pos, rotated) = (v3(&self.pos), self.rotate(&v3(vertex)));
+                [
+                    pos[0] + rotated[0],
+                    pos[1] + rotated[1],
+                    pos[2] + rotated[2],
+                ]
+            })
+            .collect::<Vec<V3>>();
+        let normals = self
+            .normals
+            .iter()
+            .map(|normal| self.rotate(&[normal.at(0), normal.at(1), normal.at(2)]))
+            .collect();
+
+        let mut vertex_normals = vec![[0.0; 3]; vertices.len()];
+        let mut face_normals = vec![];
+        let mut bounds = vec![];
+        for face in &self.faces {
+            let v = face.map(|idx| vertices[idx]);
+            let area = cross(&sub(&v[1], &v[0]), &sub(&v[2], &v[0]));
+            for idx in face {
+                for i in 0..3 {
+                    vertex_normals[*idx][i] += area[i];
+                }
+            }
+            face_normals.push(unit(&area));
+            bounds.push(Some(Aabb::new(
+                (0..3).map(|i| v[0][i].min(v[1][i]).min(v[2][i])).collect(),
+                (0..3).map(|i| v[0][i].max(v[1][i]).max(v[2][i])).collect(),
+            )));
+        }
+
+        *self.cache.borrow_mut() = Some(MeshCache {
+            vertices,
+            face_normals,
+            vertex_normals,
+            normals,
+            bvh: Bvh::build(&bounds),
+        });
+    }
+
+    /// Vector of the mesh frame in the world axes
+    fn rotate(&self, v: &V3) -> V3 {
+        let mut world = [0.0; 3];
+        for (r, coord) in world.iter_mut().enumerate() {
+            *coord = (0..3).map(|c| self.dir.att(r, c) * v[c]).sum::<f64>();
+        }
+        world
+    }
+
+    /// Hit of the single face without normal and charcoal
+    fn hit_face(
+        &self,
+        cache: &MeshCache,
+        inc: &Point,
+        dir: &Vector,
+        face: usize,
+    ) -> Option<Hit<'_>> {
+        let v = self.faces[face].map(|idx| &cache.vertices[idx]);
+        let (t, bary) = moller_trumbore(&v3(inc), &v3(dir), v)?;
+        let mut hit = Hit::new(inc, dir, t, Rc::clone(self.entity.id()));
+        hit.bary = Some(bary);
+        hit.face = Some(face);
+        Some(hit)
+    }
+}
+
+impl AsEntity for Mesh {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for Mesh {
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        self.hit(cs, inc, dir).map(|hit| hit.dist)
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.charcoal.as_ref().map(|charcoal| charcoal.ignite(dist))
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        self.ensure_cache();
+        let cache = self.cache.borrow();
+        cache.as_ref().unwrap().bvh.bounds().cloned()
+    }
+
+    fn as_game_object_mut(&mut self) -> Option<&mut dyn AsGameObject> {
+        Some(self)
+    }
+
+    /// The nearest hit of faces with barycentric coordinates and index of the hit face.
+    /// Normal is interpolated from explicit normals of face if it has them, otherwise it's either
+    /// flat normal of face or interpolated from vertex normals if mesh is smooth
+    fn hit(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        self.ensure_cache();
+        let cache = self.cache.borrow();
+        let cache = cache.as_ref().unwrap();
+        let mut hit = cache
+            .bvh
+            .nearest(inc, dir, |face| self.hit_face(cache, inc, dir, face))?;
+        let face = hit.face.unwrap();
+        let interpolate = |idx: &[usize; 3], normals: &Vec<V3>| {
+            let bary = hit.bary.unwrap();
+            let mut normal = [0.0; 3];
+            for (k, idx) in idx.iter().enumerate() {
+                for i in 0..3 {
+                    normal[i] += bary[k] * normals[*idx][i];
+                }
+            }
+            unit(&normal)
+        };
+        let normal = match (&self.normal_faces[face], self.smooth) {
+            (Some(idx), _) => interpolate(idx, &cache.normals),
+            (None, true) => interpolate(&self.faces[face], &cache.vertex_normals),
+            (None, false) => cache.face_normals[face].clone(),
+        };
+        hit.normal = normal;
+        hit.charcoal = self.charcoal.as_ref();
+        Some(hit)
+    }
+}
+
+impl AsGameObject for Mesh {
+    fn pos(&self) -> &Point {
+        &self.pos
+    }
+
+    /// Mutable position of mesh frame, world vertices are transformed again on the next ray
+    fn pos_mut(&mut self) -> &mut Point {
+        *self.cache.get_mut() = None;
+        &mut self.pos
+    }
+
+    fn dir(&self) -> &Matrix {
+        &self.dir
+    }
+
+    /// Mutable orientation of mesh frame, world vertices are transformed again on the next ray
+    fn dir_mut(&mut self) -> &mut Matrix {
+        *self.cache.get_mut() = None;
+        &mut self.dir
+    }
+
+    /// Rotates mesh frame in the plane of world axes `from` and `to`
+    fn planar_rotate(&mut self, from: usize, to: usize, angle: f64) -> ReRes<()> {
+        *self.dir_mut() = Matrix::rotation(from, to, angle, 3).mul(self.dir());
+        self.dir().ag_failed()?;
+        Ok(())
+    }
+}
@@ -1,37 +1,86 @@
 //! `rustyengine` core!
 //! Has the following features:
-//! 1. Console drawing with ASCI characters that can be specified
+//! 1. Console drawing with ASCI characters that can be specified and optional colors, either into the real console
+//! or into the in-memory terminal that captures frames
 //! 2. Traits for materials that can be treaten as game entites, eg planes, ellipses, empty entities,
 //! that for example can keeps game state variables. Define yourself entities by implemeting provided traits.
 //! 3. Traits for materials stores that can be processed during event handling
 //! 4. Traits for events and event sustems as well as simple event queue
 //! 5. Game object defined with the given implementation of provided traits and parameters from
 //! `Conf` that stands for configuration read from `.toml` file
+//! 6. Golden-frame snapshots of scenes rendered off-terminal
+//! 7. Scene list composing arbitrary collided materials, accelerated with bounding volume hierarchy
+//! 8. Tile maps of grid-aligned worlds traversed by rays cell by cell
+//! 9. Lighting of surfaces with directional and point lights by their normals
+//! 10. Triangles and triangle meshes with barycentric coordinates of hits, loaded from Wavefront OBJ files
+//! 11. Constructive solid geometry combining solids with union, intersection and difference
+//! 12. Heightmap terrain loaded from PGM images or generated procedurally
+//! 13. Shapes given by signed distance functions rendered by sphere tracing
+//! 14. Instances sharing geometry of prototype with their own transforms
+//! 15. Scene graph of objects attached to each other with transforms relative to parents
+//! 16. Camera paths of keyframes played as flythroughs
 
+pub mod bvh;
 pub mod camera;
 pub mod canvas;
 pub mod charcoal;
+pub mod color;
 pub mod console;
+pub mod csg;
 pub mod event;
 pub mod event_traits;
 pub mod game;
+pub mod graph;
+pub mod headless;
+pub mod hit;
+pub mod instance;
+pub mod light;
 pub mod material;
 pub mod material_traits;
+pub mod mesh;
+pub mod obj;
+pub mod path;
+pub mod scene;
+pub mod sdf;
+pub mod snapshot;
+pub mod terminal_traits;
+pub mod terrain;
+pub mod tilemap;
 
 #[cfg(test)]
 mod test;
 
 // re-exports in scope of namespace `engn`
 pub use {
-    camera::Camera,
-    canvas::Canvas,
+    bvh::{Aabb, Bvh},
+    camera::{Camera, Projection},
+    canvas::{Canvas, RenderMode},
     charcoal::Charcoal,
+    color::{Cell, ColorMode, Rgb},
+    console::Console,
+    csg::{Csg, CsgOp},
     event::{EventQueue, MovementEvent, MovementEventSys},
     event_traits::{AsEvent, AsEventSys},
     game::Game,
-    material::{Entity, EntityList, HypeEllipse, HypePlane, IdPool},
+    graph::{SceneGraph, SceneNode},
+    headless::Headless,
+    hit::Hit,
+    instance::Instance,
+    light::{Light, Lighting},
+    material::{
+        Entity, EntityList, HypeBox, HypeCone, HypeCylinder, HypeDisc, HypeEllipse, HypePlane,
+        HypePolygon, HypeTorus, IdPool,
+    },
     material_traits::{
-        validate_collision, AsCollided, AsEntity, AsEntityList, AsGameObject, AsScene, PropKey,
-        PropVal,
+        validate_collision, AsCollided, AsEntity, AsEntityList, AsGameObject, AsScene, AsSdf,
+        Interval, PropKey, PropVal,
     },
+    mesh::{Mesh, Triangle},
+    path::{CameraPath, Keyframe},
+    scene::SceneList,
+    sdf::{Sdf, SdfBlend, SdfBox, SdfCapsule, SdfSmooth, SdfSphere, SdfTorus},
+    snapshot::{assert_snapshot, Snapshot},
+    terminal_traits::AsTerminal,
+    terrain::Terrain,
+    tilemap::TileMap,
 };
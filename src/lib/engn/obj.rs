@@ -0,0 +1,154 @@
+//! Loader of Wavefront OBJ models into `Mesh`. Vertices, normals, faces and groups are read,
+//! polygons are triangulated by fan from their first vertex. Texture coordinates, materials and
+//! other statements are skipped
+
+use {
+    crate::{
+        engn::*,
+        errs::{LoadErr::*, ReErr::*, ReRes},
+        math::*,
+    },
+    std::{fs::read_to_string, path::Path},
+};
+
+/// Vertex of face statement: index of position and optional index of normal, both 0-based
+type FaceVertex = (usize, Option<usize>);
+
+/// Parsed statements of OBJ file
+#[derive(Default)]
+struct ObjData {
+    vertices: Vec<Point>,
+    normals: Vec<Vector>,
+    faces: Vec<[usize; 3]>,
+    normal_faces: Vec<(usize, [usize; 3])>,
+    groups: Vec<(String, Vec<usize>)>,
+    current: Vec<usize>,
+}
+
+impl ObjData {
+    /// Coordinates `x y z` following statement keyword, extra coordinate like `w` is ignored
+    fn coords(line: usize, args: &[&str]) -> ReRes<Vec<f64>> {
+        if args.len() < 3 {
+            return Err(LoadErr(MissingCoords { line, expected: 3 }));
+        }
+        args[..3]
+            .iter()
+            .map(|arg| {
+                arg.parse::<f64>()
+                    .map_err(|_| LoadErr(InvalidNumber { line }))
+            })
+            .collect()
+    }
+
+    /// Resolves 1-based or negative relative OBJ index into 0-based index of `len` elements
+    fn index(line: usize, arg: &str, len: usize) -> ReRes<usize> {
+        let idx = arg
+            .parse::<isize>()
+            .map_err(|_| LoadErr(InvalidNumber { line }))?;
+        let resolved = match idx {
+            idx if idx > 0 => idx - 1,
+            idx => len as isize + idx,
+        };
+        match idx != 0 && 0 <= resolved && resolved < len as isize {
+            true => Ok(resolved as usize),
+            false => Err(LoadErr(InvalidIndex { line, idx })),
+        }
+    }
+
+    /// Vertex of face given as `v`, `v/vt`, `v//vn` or `v/vt/vn`
+    fn face_vertex(&self, line: usize, arg: &str) -> ReRes<FaceVertex> {
+        let mut parts = arg.split('/');
+        let vertex = Self::index(line, parts.next().unwrap(), self.vertices.len())?;
+        let normal = match parts.nth(1) {
+            Some(normal) if !normal.is_empty() => {
+                Some(Self::index(line, normal, self.normals.len())?)
+            }
+            _ => None,
+        };
+        Ok((vertex, normal))
+    }
+
+    fn face(&mut self, line: usize, args: &[&str]) -> ReRes<()> {
+        if args.len() < 3 {
+            return Err(LoadErr(DegenerateFace { line }));
+        }
+        let polygon = args
+            .iter()
+            .map(|arg| self.face_vertex(line, arg))
+            .collect::<ReRes<Vec<FaceVertex>>>()?;
+        for i in 1..polygon.len() - 1 {
+            let fan = [polygon[0], polygon[i], polygon[i + 1]];
+            let face = self.faces.len();
+            self.faces.push(fan.map(|(vertex, _)| vertex));
+            if let [(_, Some(n0)), (_, Some(n1)), (_, Some(n2))] = fan {
+                self.normal_faces.push((face, [n0, n1, n2]));
+            }
+            for group in &self.current {
+                self.groups[*group].1.push(face);
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes the given groups current, they're created if they haven't been encountered yet
+    fn group(&mut self, names: &[&str]) {
+        self.current.clear();
+        for name in names {
+            let group = match self.groups.iter().position(|(group, _)| group == name) {
+                Some(group) => group,
+                None => {
+                    self.groups.push((name.to_string(), vec![]));
+                    self.groups.len() - 1
+                }
+            };
+            self.current.push(group);
+        }
+    }
+}
+
+impl Mesh {
+    /// Reads mesh from OBJ file, see `Mesh::parse_obj`
+    pub fn from_obj(
+        entity: Entity,
+        path: impl AsRef<Path>,
+        charcoal: Option<Charcoal>,
+    ) -> ReRes<Self> {
+        match read_to_string(path) {
+            Ok(content) => Self::parse_obj(entity, &content, charcoal),
+            Err(err) => Err(LoadErr(UnreadableFile(err.kind()))),
+        }
+    }
+
+    /// Parses mesh from OBJ `content`. Faces are added to groups named in preceding `g` or `o` statement,
+    /// faces with normals in all their vertices get explicit normals
+    pub fn parse_obj(entity: Entity, content: &str, charcoal: Option<Charcoal>) -> ReRes<Self> {
+        let mut data = ObjData::default();
+        for (idx, line) in content.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = line.split('#').next().unwrap();
+            let mut args = line.split_whitespace();
+            let Some(keyword) = args.next() else {
+                continue;
+            };
+            let args = args.collect::<Vec<&str>>();
+            match keyword {
+                "v" => data
+                    .vertices
+                    .push(Point::new(ObjData::coords(line_no, &args)?)),
+                "vn" => data
+                    .normals
+                    .push(Vector::new(ObjData::coords(line_no, &args)?)),
+                "f" => data.face(line_no, &args)?,
+                "g" | "o" => data.group(&args),
+                _ => {}
+            }
+        }
+
+        let mut mesh = Self::new(entity, data.vertices, data.faces, charcoal)?
+            .with_normals(data.normals, data.normal_faces)?;
+        for (name, faces) in data.groups {
+            mesh = mesh.with_group(&name, faces)?;
+        }
+        Ok(mesh)
+    }
+}
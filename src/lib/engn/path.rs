@@ -0,0 +1,273 @@
+//! Camera paths for scripted flythroughs: keyframes of camera pose are interpolated with Catmull-Rom
+//! splines for position, spherical linear interpolation of quaternions for orientation and linearly
+//! for fields of view. `Game::play` shows the path frame by frame until its end or the skip key
+
+use {
+    crate::{engn::*, errs::ReRes, math::*},
+    crossterm::event::KeyCode,
+};
+
+/// Default number of frames per second of playback
+const FPS: f64 = 30.0;
+
+/// Pose of camera at `time` in seconds from the start of path. Orientation is yaw, pitch and roll
+/// as in `Camera::orient`, fields of view are kept as they are if `fov` is `None`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframe {
+    pub time: f64,
+    pub pos: Point,
+    pub orientation: (f64, f64, f64),
+    pub fov: Option<(f64, f64)>,
+}
+
+impl Keyframe {
+    /// Keyframe looking along x axis
+    pub fn new(time: f64, pos: Point) -> Self {
+        Self {
+            time,
+            pos,
+            orientation: (0.0, 0.0, 0.0),
+            fov: None,
+        }
+    }
+
+    /// Current pose of `camera`
+    pub fn from_camera(time: f64, camera: &Camera) -> Self {
+        Self {
+            time,
+            pos: camera.pos().clone(),
+            orientation: (camera.yaw(), camera.pitch(), camera.roll()),
+            fov: Some(camera.fov()),
+        }
+    }
+
+    pub fn with_orientation(mut self, yaw: f64, pitch: f64, roll: f64) -> Self {
+        self.orientation = (yaw, pitch, roll);
+        self
+    }
+
+    pub fn with_fov(mut self, wfov: f64, hfov: f64) -> Self {
+        self.fov = Some((wfov, hfov));
+        self
+    }
+
+    /// Moves, turns and zooms `camera` into this pose
+    pub fn apply(&self, camera: &mut Camera) -> ReRes<()> {
+        camera.mv(&self.pos.df(camera.pos())?)?;
+        let (yaw, pitch, roll) = self.orientation;
+        camera.orient(yaw, pitch, roll);
+        if let Some((wfov, hfov)) = self.fov {
+            if camera.fov() != (wfov, hfov) {
+                camera.set_fov(wfov, hfov);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Keyframes sorted by time, playback shows `fps` frames per second and stops on `skip` key
+/// jumping to the last keyframe
+#[derive(Debug, Clone)]
+pub struct CameraPath {
+    pub(crate) keys: Vec<Keyframe>,
+    pub(crate) fps: f64,
+    pub(crate) skip: KeyCode,
+}
+
+impl CameraPath {
+    /// Empty path played at 30 frames per second and skipped with `Esc`
+    pub fn new() -> Self {
+        Self {
+            keys: vec![],
+            fps: FPS,
+            skip: KeyCode::Esc,
+        }
+    }
+
+    /// Adds keyframe keeping them sorted by time, keyframe of the same time as existing one replaces it
+    pub fn with_key(mut self, key: Keyframe) -> Self {
+        self.push(key);
+        self
+    }
+
+    pub fn with_fps(mut self, fps: f64) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    pub fn with_skip(mut self, skip: KeyCode) -> Self {
+        self.skip = skip;
+        self
+    }
+
+    /// Adds keyframe keeping them sorted by time, keyframe of the same time as existing one replaces it
+    pub fn push(&mut self, key: Keyframe) {
+        match self.keys.binary_search_by(|k| k.time.total_cmp(&key.time)) {
+            Ok(i) => self.keys[i] = key,
+            Err(i) => self.keys.insert(i, key),
+        }
+    }
+
+    pub fn keys(&self) -> &Vec<Keyframe> {
+        &self.keys
+    }
+
+    pub fn fps(&self) -> f64 {
+        self.fps
+    }
+
+    pub fn skip(&self) -> KeyCode {
+        self.skip
+    }
+
+    /// Time from the start of path to its last keyframe
+    pub fn duration(&self) -> f64 {
+        self.keys.last().map_or(0.0, |key| key.time)
+    }
+
+    /// Number of frames of playback including both the first and the last keyframes,
+    /// it's the last keyframe only if `fps` isn't positive
+    pub fn frames(&self) -> usize {
+        match (self.keys.first(), self.fps > 0.0) {
+            (None, _) => 0,
+            (Some(first), true) => ((self.duration() - first.time) * self.fps).ceil() as usize + 1,
+            (Some(_), false) => 1,
+        }
+    }
+
+    /// Pose at the given `time` clamped into the path, `None` if there are no keyframes
+    pub fn sample(&self, time: f64) -> Option<Keyframe> {
+        let (first, last) = (self.keys.first()?, self.keys.last()?);
+        if time <= first.time {
+            return Some(Keyframe {
+                time,
+                ..first.clone()
+            });
+        }
+        if time >= last.time {
+            return Some(Keyframe {
+                time,
+                ..last.clone()
+            });
+        }
+        let i = self.keys.partition_point(|key| key.time <= time) - 1;
+        let (k1, k2) = (&self.keys[i], &self.keys[i + 1]);
+        let (k0, k3) = (
+            i.checked_sub(1).map(|i| &self.keys[i]),
+            self.keys.get(i + 2),
+        );
+        let t = (time - k1.time) / (k2.time - k1.time);
+
+        let pos = Point::new(
+            (0..k1.pos.dim())
+                .map(|d| {
+                    let (p1, p2) = (k1.pos[d], k2.pos[d]);
+                    let p0 = k0.map_or(2.0 * p1 - p2, |k| k.pos[d]);
+                    let p3 = k3.map_or(2.0 * p2 - p1, |k| k.pos[d]);
+                    catmull_rom(p0, p1, p2, p3, t)
+                })
+                .collect(),
+        );
+        let orientation = Quat::from_euler(k1.orientation)
+            .slerp(&Quat::from_euler(k2.orientation), t)
+            .to_euler();
+        let fov = match (k1.fov, k2.fov) {
+            (Some(f1), Some(f2)) => Some((lerp(f1.0, f2.0, t), lerp(f1.1, f2.1, t))),
+            (fov, None) | (None, fov) => fov,
+        };
+        Some(Keyframe {
+            time,
+            pos,
+            orientation,
+            fov,
+        })
+    }
+
+    /// Time of the playback frame `frame`
+    pub fn frame_time(&self, frame: usize) -> f64 {
+        match (self.keys.first(), self.fps > 0.0) {
+            (Some(first), true) => (first.time + frame as f64 / self.fps).min(self.duration()),
+            _ => self.duration(),
+        }
+    }
+}
+
+impl Default for CameraPath {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// Uniform Catmull-Rom spline through `p1` at `t` 0 and `p2` at `t` 1, `p0` and `p3` set its tangents.
+/// Missing neighbours of the first and the last keyframes are reflected ones, so path doesn't slow down at its ends
+fn catmull_rom(p0: f64, p1: f64, p2: f64, p3: f64, t: f64) -> f64 {
+    let (t2, t3) = (t * t, t * t * t);
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+/// Unit quaternion `w + xi + yj + zk` of rotation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Quat([f64; 4]);
+
+impl Quat {
+    fn mul(&self, rhs: &Self) -> Self {
+        let ([w1, x1, y1, z1], [w2, x2, y2, z2]) = (self.0, rhs.0);
+        Self([
+            w1 * w2 - x1 * x2 - y1 * y2 - z1 * z2,
+            w1 * x2 + x1 * w2 + y1 * z2 - z1 * y2,
+            w1 * y2 - x1 * z2 + y1 * w2 + z1 * x2,
+            w1 * z2 + x1 * y2 - y1 * x2 + z1 * w2,
+        ])
+    }
+
+    /// Rotation of `Camera::orient`: yaw around z axis, pitch lifting x axis towards z axis
+    /// and roll lifting y axis towards z axis, applied from the last one
+    pub(crate) fn from_euler((yaw, pitch, roll): (f64, f64, f64)) -> Self {
+        let z = Self([(yaw / 2.0).cos(), 0.0, 0.0, (yaw / 2.0).sin()]);
+        let y = Self([(pitch / 2.0).cos(), 0.0, -(pitch / 2.0).sin(), 0.0]);
+        let x = Self([(roll / 2.0).cos(), (roll / 2.0).sin(), 0.0, 0.0]);
+        z.mul(&y).mul(&x)
+    }
+
+    /// Yaw, pitch and roll of rotation, pitch is within [-PI / 2, PI / 2]
+    pub(crate) fn to_euler(&self) -> (f64, f64, f64) {
+        let [w, x, y, z] = self.0;
+        let (m00, m10) = (1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y + w * z));
+        let (m20, m21, m22) = (
+            2.0 * (x * z - w * y),
+            2.0 * (y * z + w * x),
+            1.0 - 2.0 * (x * x + y * y),
+        );
+        (m10.atan2(m00), m20.clamp(-1.0, 1.0).asin(), m21.atan2(m22))
+    }
+
+    /// Spherical linear interpolation along the shorter arc, `t` 0 is `self` and 1 is `other`
+    pub(crate) fn slerp(&self, other: &Self, t: f64) -> Self {
+        let mut dot = (0..4).map(|i| self.0[i] * other.0[i]).sum::<f64>();
+        let mut other = other.0;
+        if dot < 0.0 {
+            dot = -dot;
+            other = other.map(|c| -c);
+        }
+        let (a, b) = match dot > 1.0 - 1e-9 {
+            true => (1.0 - t, t),
+            false => {
+                let theta = dot.acos();
+                (
+                    ((1.0 - t) * theta).sin() / theta.sin(),
+                    (t * theta).sin() / theta.sin(),
+                )
+            }
+        };
+        let q = [0, 1, 2, 3].map(|i| a * self.0[i] + b * other[i]);
+        let len = q.iter().map(|c| c * c).sum::<f64>().sqrt();
+        Self(q.map(|c| c / len))
+    }
+}
@@ -0,0 +1,227 @@
+use {
+    crate::{engn::*, errs::ReRes, math::*},
+    std::{
+        cell::{Cell as Flag, RefCell},
+        collections::HashMap,
+        rc::Rc,
+    },
+    uuid::Uuid,
+};
+
+/// Scene composed of arbitrary `AsCollided` objects, that can be added and removed at runtime.
+/// Ray gets the nearest hit of all the objects, each object is drawn with charcoal given to the scene for it
+/// or with its own one, optionally lit with `Lighting`. Ray queries go through `Bvh` that is built lazily,
+/// it's rebuilt after adding or removing objects and refitted after moving them
+#[derive(Debug)]
+pub struct SceneList {
+    pub(crate) objects: Vec<Box<dyn AsCollided>>,
+    pub(crate) charcoals: HashMap<Uuid, Charcoal>,
+    pub(crate) lighting: Option<Lighting>,
+    pub(crate) background: Option<Cell>,
+    pub(crate) radius: f64,
+    pub(crate) accel: bool,
+    pub(crate) bvh: RefCell<Option<Bvh>>,
+    pub(crate) refit: Flag<bool>,
+}
+
+impl SceneList {
+    /// Empty scene without lighting, background cell is left to renderer and movement isn't kept off objects
+    pub fn new() -> Self {
+        Self {
+            objects: vec![],
+            charcoals: HashMap::new(),
+            lighting: None,
+            background: None,
+            radius: 0.0,
+            accel: true,
+            bvh: RefCell::new(None),
+            refit: Flag::new(false),
+        }
+    }
+
+    /// Turns on or off `Bvh` acceleration, each ray tests all the objects without it
+    pub fn with_bvh(mut self, accel: bool) -> Self {
+        self.accel = accel;
+        self
+    }
+
+    /// Surfaces having normals are lit, others are faded with distance
+    pub fn with_lighting(mut self, lighting: Lighting) -> Self {
+        self.lighting = Some(lighting);
+        self
+    }
+
+    /// Cell drawn where rays hit nothing
+    pub fn with_background(mut self, cell: Cell) -> Self {
+        self.background = Some(cell);
+        self
+    }
+
+    /// Distance that `validate_mv` keeps between moving point and objects
+    pub fn with_radius(mut self, radius: f64) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Adds object drawn with the given charcoal or with its own one if `None`, returns id of the object
+    pub fn add(&mut self, object: Box<dyn AsCollided>, charcoal: Option<Charcoal>) -> Rc<Uuid> {
+        let id = Rc::clone(object.id());
+        if let Some(charcoal) = charcoal {
+            self.charcoals.insert(*id, charcoal);
+        }
+        self.objects.push(object);
+        *self.bvh.get_mut() = None;
+        id
+    }
+
+    /// Mutable ref to object with the given id if it exists, hierarchy is refitted on the next ray query
+    pub fn get_mut(&mut self, id: &Rc<Uuid>) -> Option<&mut Box<dyn AsCollided>> {
+        self.refit.set(true);
+        self.objects
+            .iter_mut()
+            .find(|object| object.id().as_ref() == id.as_ref())
+    }
+
+    /// Moves object with the given id on `vec` if it's `AsGameObject`, returns whether it's moved
+    pub fn mv(&mut self, id: &Rc<Uuid>, vec: &Vector) -> ReRes<bool> {
+        match self
+            .get_mut(id)
+            .and_then(|object| object.as_game_object_mut())
+        {
+            Some(object) => object.mv(vec).map(|_| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Nearest hit of objects found with hierarchy that is built or refitted if needed
+    fn accelerated(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        {
+            let mut bvh = self.bvh.borrow_mut();
+            let bounds = || {
+                self.objects
+                    .iter()
+                    .map(|object| object.bounds())
+                    .collect::<Vec<Option<Aabb>>>()
+            };
+            match bvh.as_mut() {
+                None => *bvh = Some(Bvh::build(&bounds())),
+                Some(bvh) if self.refit.get() => bvh.refit(&bounds()),
+                _ => {}
+            }
+            self.refit.set(false);
+        }
+        self.bvh
+            .borrow()
+            .as_ref()
+            .unwrap()
+            .nearest(inc, dir, |i| self.objects[i].hit(cs, inc, dir))
+    }
+
+    /// Charcoal the object with the given id is drawn with
+    pub fn charcoal(&self, id: &Uuid) -> Option<&Charcoal> {
+        match self.charcoals.get(id) {
+            Some(charcoal) => Some(charcoal),
+            None => self
+                .objects
+                .iter()
+                .find(|object| object.id().as_ref() == id)
+                .and_then(|object| object.charcoal()),
+        }
+    }
+
+    /// Replaces charcoal the object with the given id is drawn with
+    pub fn set_charcoal(&mut self, id: &Uuid, charcoal: Charcoal) {
+        self.charcoals.insert(*id, charcoal);
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+}
+
+impl AsEntityList for SceneList {
+    type Item = Box<dyn AsCollided>;
+
+    /// Appends object drawn with its own charcoal
+    fn append(&mut self, item: Self::Item) {
+        self.add(item, None);
+    }
+
+    /// Removes object with the given `Uuid` and its charcoal
+    fn remove(&mut self, id: &Rc<Uuid>) {
+        self.objects
+            .retain(|object| object.id().as_ref() != id.as_ref());
+        self.charcoals.remove(id);
+        *self.bvh.get_mut() = None;
+    }
+
+    fn get(&self, id: &Rc<Uuid>) -> Option<&Self::Item> {
+        self.objects
+            .iter()
+            .find(|object| object.id().as_ref() == id.as_ref())
+    }
+
+    fn exec(&self, f: fn(&Self::Item)) {
+        for object in &self.objects {
+            f(object)
+        }
+    }
+}
+
+impl AsScene for SceneList {
+    /// The nearest hit of all the objects with charcoal of the scene replacing the own one of object
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        let mut hit = match self.accel {
+            true => self.accelerated(cs, inc, dir),
+            false => Hit::nearest(self.objects.iter().map(|object| object.hit(cs, inc, dir))),
+        }?;
+        if let Some(charcoal) = self.charcoals.get(&hit.id) {
+            hit.charcoal = Some(charcoal);
+        }
+        Some(hit)
+    }
+
+    fn shade(&self, cs: &CoordSys, dir: &Vector, hit: &Hit) -> Option<Cell> {
+        hit.shade(self.lighting.as_ref(), cs, dir)
+    }
+
+    fn background(&self, _cs: &CoordSys, _dir: &Vector) -> Option<Cell> {
+        self.background
+    }
+
+    /// Sweeps `mv` from `pos` and shortens it to stop at `radius` before the nearest object on the way.
+    /// Objects having `AsCollided::ground` under the end of `mv`, like `Terrain`, are walked over instead:
+    /// they aren't swept and `mv` is lifted or lowered to end at the highest ground
+    fn validate_mv(&self, cs: &CoordSys, pos: &Point, mv: &mut Vector) {
+        let ground = |object: &Box<dyn AsCollided>, mv: &Vector| {
+            object.ground(pos[0] + mv.at(0), pos[1] + mv.at(1))
+        };
+        let hit = Hit::nearest(
+            self.objects
+                .iter()
+                .filter(|object| ground(object, mv).is_none())
+                .map(|object| object.hit(cs, pos, mv)),
+        );
+        if let Some(hit) = hit {
+            hit.stop(cs, mv, self.radius);
+        }
+        let height = self
+            .objects
+            .iter()
+            .filter_map(|object| ground(object, mv))
+            .reduce(f64::max);
+        if let Some(height) = height {
+            *mv.at_mut(2) = height - pos[2];
+        }
+    }
+}
+
+impl Default for SceneList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,347 @@
+//! Shapes given by signed distance functions, rendered by sphere tracing: ray steps forward by distance
+//! to the nearest surface until it's close enough to the surface or goes farther than the draw distance.
+//! Primitives are combined with smooth union, intersection and difference or blended with each other
+
+use {
+    crate::{conf::Conf, engn::*, math::*},
+    std::{collections::HashMap, rc::Rc},
+    uuid::Uuid,
+};
+
+/// Default limit of sphere tracing steps
+const MAX_STEPS: usize = 256;
+
+/// Default distance to surface that is treated as hit
+const HIT_EPS: f64 = 1e-6;
+
+/// Step of central differences approximating normals
+const NORMAL_EPS: f64 = 1e-6;
+
+fn sub(l: &[f64; 3], r: &[f64; 3]) -> [f64; 3] {
+    [l[0] - r[0], l[1] - r[1], l[2] - r[2]]
+}
+
+fn len(v: &[f64; 3]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+/// Sphere with `center` and `radius`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdfSphere {
+    pub center: [f64; 3],
+    pub radius: f64,
+}
+
+impl AsSdf for SdfSphere {
+    fn dist(&self, pt: &[f64; 3]) -> f64 {
+        len(&sub(pt, &self.center)) - self.radius
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        Some(Aabb::around(
+            &Point::new(self.center.to_vec()),
+            &[self.radius; 3],
+        ))
+    }
+}
+
+/// Axis-aligned box with `center` and half of its sides `extent`, edges are rounded by `rounding`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdfBox {
+    pub center: [f64; 3],
+    pub extent: [f64; 3],
+    pub rounding: f64,
+}
+
+impl AsSdf for SdfBox {
+    fn dist(&self, pt: &[f64; 3]) -> f64 {
+        let q = sub(pt, &self.center);
+        let q = [0, 1, 2].map(|i| q[i].abs() - self.extent[i] + self.rounding);
+        let outside = len(&q.map(|x| x.max(0.0)));
+        let inside = q[0].max(q[1]).max(q[2]).min(0.0);
+        outside + inside - self.rounding
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        Some(Aabb::around(
+            &Point::new(self.center.to_vec()),
+            &self.extent,
+        ))
+    }
+}
+
+/// Torus with `center` lying in plane parallel to xy, `major` is radius of the tube circle, `minor` of the tube
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdfTorus {
+    pub center: [f64; 3],
+    pub major: f64,
+    pub minor: f64,
+}
+
+impl AsSdf for SdfTorus {
+    fn dist(&self, pt: &[f64; 3]) -> f64 {
+        let q = sub(pt, &self.center);
+        let ring = (q[0] * q[0] + q[1] * q[1]).sqrt() - self.major;
+        (ring * ring + q[2] * q[2]).sqrt() - self.minor
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        let extent = self.major + self.minor;
+        Some(Aabb::around(
+            &Point::new(self.center.to_vec()),
+            &[extent, extent, self.minor],
+        ))
+    }
+}
+
+/// Segment between `from` and `to` thickened by `radius`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdfCapsule {
+    pub from: [f64; 3],
+    pub to: [f64; 3],
+    pub radius: f64,
+}
+
+impl AsSdf for SdfCapsule {
+    fn dist(&self, pt: &[f64; 3]) -> f64 {
+        let (p, axis) = (sub(pt, &self.from), sub(&self.to, &self.from));
+        let sq = axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2];
+        let h = match sq > 0.0 {
+            true => ((p[0] * axis[0] + p[1] * axis[1] + p[2] * axis[2]) / sq).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+        len(&[0, 1, 2].map(|i| p[i] - axis[i] * h)) - self.radius
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        let r = self.radius;
+        Some(Aabb::new(
+            (0..3).map(|i| self.from[i].min(self.to[i]) - r).collect(),
+            (0..3).map(|i| self.from[i].max(self.to[i]) + r).collect(),
+        ))
+    }
+}
+
+/// Operands combined with `CsgOp` whose seams are smoothed within distance `k`, sharp if `k` is zero
+#[derive(Debug)]
+pub struct SdfSmooth {
+    pub op: CsgOp,
+    pub left: Box<dyn AsSdf>,
+    pub right: Box<dyn AsSdf>,
+    pub k: f64,
+}
+
+impl SdfSmooth {
+    pub fn union(left: Box<dyn AsSdf>, right: Box<dyn AsSdf>, k: f64) -> Self {
+        Self {
+            op: CsgOp::Union,
+            left,
+            right,
+            k,
+        }
+    }
+
+    pub fn intersection(left: Box<dyn AsSdf>, right: Box<dyn AsSdf>, k: f64) -> Self {
+        Self {
+            op: CsgOp::Intersection,
+            left,
+            right,
+            k,
+        }
+    }
+
+    /// Left shape with the right one carved out of it
+    pub fn difference(left: Box<dyn AsSdf>, right: Box<dyn AsSdf>, k: f64) -> Self {
+        Self {
+            op: CsgOp::Difference,
+            left,
+            right,
+            k,
+        }
+    }
+}
+
+/// Polynomial smooth minimum, it's the plain minimum for operands farther than `k` from each other
+fn smooth_min(a: f64, b: f64, k: f64) -> f64 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+    let h = (0.5 + 0.5 * (b - a) / k).clamp(0.0, 1.0);
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+impl AsSdf for SdfSmooth {
+    fn dist(&self, pt: &[f64; 3]) -> f64 {
+        let (l, r) = (self.left.dist(pt), self.right.dist(pt));
+        match self.op {
+            CsgOp::Union => smooth_min(l, r, self.k),
+            CsgOp::Intersection => -smooth_min(-l, -r, self.k),
+            CsgOp::Difference => -smooth_min(-l, r, self.k),
+        }
+    }
+
+    /// Union is bounded by bounds of both operands grown by `k`, intersection and difference by the left one
+    fn bounds(&self) -> Option<Aabb> {
+        match self.op {
+            CsgOp::Union => {
+                let bounds = self.left.bounds()?.union(&self.right.bounds()?);
+                Some(Aabb::new(
+                    bounds.min().iter().map(|x| x - self.k).collect(),
+                    bounds.max().iter().map(|x| x + self.k).collect(),
+                ))
+            }
+            CsgOp::Intersection => self.left.bounds().or_else(|| self.right.bounds()),
+            CsgOp::Difference => self.left.bounds(),
+        }
+    }
+}
+
+/// Linear morph between operands, `weight` 0 is the left shape and 1 is the right one
+#[derive(Debug)]
+pub struct SdfBlend {
+    pub left: Box<dyn AsSdf>,
+    pub right: Box<dyn AsSdf>,
+    pub weight: f64,
+}
+
+impl AsSdf for SdfBlend {
+    fn dist(&self, pt: &[f64; 3]) -> f64 {
+        (1.0 - self.weight) * self.left.dist(pt) + self.weight * self.right.dist(pt)
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        Some(self.left.bounds()?.union(&self.right.bounds()?))
+    }
+}
+
+/// Material of shape given by signed distance function, ray is traced not farther than `draw_dist`
+#[derive(Debug)]
+pub struct Sdf {
+    pub(crate) entity: Entity,
+    pub(crate) shape: Box<dyn AsSdf>,
+    pub(crate) draw_dist: f64,
+    pub(crate) steps: usize,
+    pub(crate) eps: f64,
+    pub charcoal: Option<Charcoal>,
+}
+
+impl Sdf {
+    pub fn new(
+        entity: Entity,
+        shape: Box<dyn AsSdf>,
+        draw_dist: f64,
+        charcoal: Option<Charcoal>,
+    ) -> Self {
+        Self {
+            entity,
+            shape,
+            draw_dist,
+            steps: MAX_STEPS,
+            eps: HIT_EPS,
+            charcoal,
+        }
+    }
+
+    /// Material traced up to `DRAW_DISTANCE` of `Conf`
+    pub fn from_conf(
+        entity: Entity,
+        shape: Box<dyn AsSdf>,
+        conf: &Conf,
+        charcoal: Option<Charcoal>,
+    ) -> Self {
+        Self::new(entity, shape, conf.draw_dist, charcoal)
+    }
+
+    /// Limit of sphere tracing steps, ray that doesn't reach the surface within them misses
+    pub fn with_steps(mut self, steps: usize) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Distance to surface that is treated as hit
+    pub fn with_eps(mut self, eps: f64) -> Self {
+        self.eps = eps;
+        self
+    }
+
+    pub fn shape(&self) -> &dyn AsSdf {
+        self.shape.as_ref()
+    }
+
+    /// Coefficient of ray resizing where it reaches the surface. Tracing starts where ray enters bounds
+    /// of shape if they're known
+    fn trace(&self, inc: &Point, dir: &Vector) -> Option<f64> {
+        let (o, d) = ([inc[0], inc[1], inc[2]], [dir.at(0), dir.at(1), dir.at(2)]);
+        let scale = len(&d);
+        if scale == 0.0 {
+            return None;
+        }
+        let (mut t, exit) = match self.shape.bounds() {
+            Some(bounds) => {
+                let (enter, exit) = bounds.intersect(inc, dir)?;
+                (enter.max(0.0), exit)
+            }
+            None => (0.0, f64::INFINITY),
+        };
+        let end = exit.min(self.draw_dist / scale);
+        for _ in 0..self.steps {
+            if t > end {
+                return None;
+            }
+            let dist = self.shape.dist(&[0, 1, 2].map(|i| o[i] + t * d[i]));
+            if dist < self.eps {
+                return Some(t);
+            }
+            t += dist / scale;
+        }
+        None
+    }
+}
+
+impl AsEntity for Sdf {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for Sdf {
+    fn collide(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        self.trace(inc, dir)
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.charcoal.as_ref().map(|charcoal| charcoal.ignite(dist))
+    }
+
+    /// Gradient of distance function approximated by central differences
+    fn normal(&self, _cs: &CoordSys, pt: &Point) -> Option<Vector> {
+        let p = [pt[0], pt[1], pt[2]];
+        let grad = [0, 1, 2].map(|i| {
+            let (mut fwd, mut back) = (p, p);
+            fwd[i] += NORMAL_EPS;
+            back[i] -= NORMAL_EPS;
+            self.shape.dist(&fwd) - self.shape.dist(&back)
+        });
+        let len = len(&grad);
+        match len > 0.0 {
+            true => Some(Vector::new(grad.map(|x| x / len).to_vec())),
+            false => None,
+        }
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        self.shape.bounds()
+    }
+}
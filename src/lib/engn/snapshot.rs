@@ -0,0 +1,144 @@
+//! Golden-frame testing of scenes. `Snapshot` renders `AsScene` from the given camera
+//! at fixed canvas size, then the picture is compared against stored `.txt` file.
+//! Set environment variable `RUSTYENGINE_BLESS=1` to rewrite stored files with the actual pictures
+
+use {
+    crate::{
+        conf::*,
+        engn::{game::init_space, *},
+        errs::ReRes,
+        math::*,
+    },
+    std::{
+        env,
+        f64::consts::PI,
+        fs::{create_dir_all, read_to_string, write},
+        path::Path,
+    },
+};
+
+/// Environment variable that makes `assert_snapshot` store the actual picture instead of comparing
+pub const BLESS_VAR: &str = "RUSTYENGINE_BLESS";
+
+/// Renders scenes off-terminal with parameters from `Conf` at fixed canvas size
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub(crate) conf: Conf,
+    pub(crate) size: (usize, usize),
+}
+
+impl Snapshot {
+    /// Constructs renderer of pictures of the given size as (rows, cols)
+    pub fn new(conf: Conf, size: (usize, usize)) -> Self {
+        Self { conf, size }
+    }
+
+    /// Camera standing at `pos` and looking in the direction [1, 0, 0],
+    /// rotate it to get the needed orientation
+    pub fn camera(&self, pos: Point) -> Camera {
+        let hfov = match self.conf.hfov {
+            Some(val) => val,
+            None => self.conf.render_mode.hfov(self.conf.wfov, self.size),
+        };
+        Camera::new(
+            pos,
+            self.conf.angle_discr,
+            self.conf.wfov * PI,
+            hfov * PI,
+            self.conf.render_mode.samples(self.size),
+            self.conf.draw_dist,
+        )
+        .with_projection(self.conf.projection)
+        .with_view_width(self.conf.view_width)
+    }
+
+    /// Renders `scene` from the given `camera` and returns the picture as it's drawn in terminal
+    pub fn render<Scn: AsScene>(&self, scene: &Scn, camera: &Camera) -> ReRes<Vec<String>> {
+        let cs = init_space(&self.conf)?;
+        let mut canvas = Canvas::new(
+            self.size,
+            self.conf.charmap.clone(),
+            self.conf.draw_dist,
+            self.conf.color_mode,
+        )
+        .with_render_mode(self.conf.render_mode);
+        let mut term = Headless::new((self.size.0 + 3) as u16, self.size.1 as u16);
+        canvas.update(camera, &cs, scene)?;
+        canvas.draw(&mut term)?;
+        Ok(term.frame().unwrap()[3..].to_vec())
+    }
+}
+
+/// Compares `frame` against the snapshot stored in `path` and panics with readable diff on mismatch.
+/// Stores `frame` into `path` instead if `RUSTYENGINE_BLESS` is set to `1`
+pub fn assert_snapshot(path: &str, frame: &[String]) {
+    let bless = env::var(BLESS_VAR).map_or(false, |val| val == "1");
+    if let Err(msg) = check_snapshot(path, frame, bless) {
+        panic!("{}", msg)
+    }
+}
+
+/// Compares or stores snapshot, returns message for the user if they are different
+pub(crate) fn check_snapshot(path: &str, frame: &[String], bless: bool) -> Result<(), String> {
+    if bless {
+        if let Some(dir) = Path::new(path).parent() {
+            create_dir_all(dir).map_err(|err| format!("failed to create {:?}: {}", dir, err))?;
+        }
+        let mut content = frame.join("\n");
+        content.push('\n');
+        return write(path, content).map_err(|err| format!("failed to write {}: {}", path, err));
+    }
+
+    let expected = match read_to_string(path) {
+        Ok(content) => content.lines().map(String::from).collect::<Vec<String>>(),
+        Err(err) => {
+            return Err(format!(
+                "failed to read snapshot {}: {}\nrun with {}=1 to store the actual picture",
+                path, err, BLESS_VAR
+            ))
+        }
+    };
+    match diff(&expected, frame) {
+        Some(diff) => Err(format!(
+            "snapshot {} mismatch, run with {}=1 to store the actual picture\n{}",
+            path, BLESS_VAR, diff
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Line by line difference between pictures with marks under the mismatched characters.
+/// `None` if pictures are equal
+pub fn diff(expected: &[String], actual: &[String]) -> Option<String> {
+    let mut msg = String::new();
+    if expected.len() != actual.len() {
+        msg += &format!(
+            "expected {} rows, actual {} rows\n",
+            expected.len(),
+            actual.len()
+        );
+    }
+    for r in 0..expected.len().max(actual.len()) {
+        let exp = expected.get(r).map_or("", |line| line.as_str());
+        let act = actual.get(r).map_or("", |line| line.as_str());
+        if exp == act {
+            continue;
+        }
+        let (exp_chars, act_chars): (Vec<char>, Vec<char>) =
+            (exp.chars().collect(), act.chars().collect());
+        let marks = (0..exp_chars.len().max(act_chars.len()))
+            .map(|c| match exp_chars.get(c) == act_chars.get(c) {
+                true => ' ',
+                false => '^',
+            })
+            .collect::<String>();
+        msg += &format!(
+            "row {}:\n  expected |{}|\n  actual   |{}|\n            {}\n",
+            r, exp, act, marks
+        );
+    }
+    match msg.is_empty() {
+        true => None,
+        false => Some(msg),
+    }
+}
@@ -0,0 +1,39 @@
+use {
+    crate::errs::ReRes,
+    crossterm::{event::Event, style::Color},
+    std::time::Duration,
+};
+
+/// Trait for terminals that `Canvas` draws into and `Game` queries for size and input.
+/// Output may be queued and is guaranteed to be shown only after `flush`
+pub trait AsTerminal {
+    /// Prepares terminal for drawing and returns its size as (rows, cols)
+    fn init(&mut self) -> ReRes<(u16, u16)>;
+
+    /// Current size of terminal as (rows, cols)
+    fn size(&self) -> ReRes<(u16, u16)>;
+
+    /// Blocks until new event obtained. `None` is returned if there are no more events
+    fn listen(&mut self) -> ReRes<Option<Event>>;
+
+    /// Waits for new event not longer than `timeout`, `None` is returned if there is no event by then.
+    /// Terminal that can't be polled gives no events
+    fn poll(&mut self, _timeout: Duration) -> ReRes<Option<Event>> {
+        Ok(None)
+    }
+
+    /// Moves cursor on the given position
+    fn move_cursor(&mut self, row: u16, col: u16) -> ReRes<()>;
+
+    /// Prints string starting from the current cursor position
+    fn print(&mut self, s: &str) -> ReRes<()>;
+
+    /// Sets colors of the following printed characters, `None` stands for the default color
+    fn set_colors(&mut self, fg: Option<Color>, bg: Option<Color>) -> ReRes<()>;
+
+    /// Clears all the terminal
+    fn clear(&mut self) -> ReRes<()>;
+
+    /// Shows all the queued output, returns number of bytes written since the previous flush
+    fn flush(&mut self) -> ReRes<usize>;
+}
@@ -0,0 +1,397 @@
+//! Heightmap terrain: heights are given in vertices of the square grid over xy plane, each cell is split
+//! into two triangles by its diagonal. Rays step over cells with 2D grid traversal and are collided
+//! only with triangles of crossed cells. Heights are loaded from plain PGM image or generated procedurally
+
+use {
+    super::mesh::moller_trumbore,
+    crate::{
+        engn::*,
+        errs::{GameErr::*, LoadErr::*, ReErr::*, ReRes},
+        math::*,
+    },
+    std::{collections::HashMap, fs::read_to_string, path::Path, rc::Rc},
+    uuid::Uuid,
+};
+
+/// Magic number of plain PGM format
+const PGM_MAGIC: &str = "P2";
+
+/// Terrain with heights `heights.att(r, c)` in vertices `(origin.x + c * cell, origin.y + r * cell)`
+/// added to `origin.z`
+#[derive(Debug)]
+pub struct Terrain {
+    pub(crate) entity: Entity,
+    pub(crate) origin: Point,
+    pub(crate) cell: f64,
+    pub(crate) heights: Matrix,
+    pub(crate) range: (f64, f64),
+    pub(crate) lighting: Option<Lighting>,
+    pub(crate) background: Option<Cell>,
+    pub(crate) eye: f64,
+    pub charcoal: Option<Charcoal>,
+}
+
+impl Terrain {
+    /// Terrain of heights in vertices of square cells with positive side `cell`, there must be at least 2x2 vertices
+    pub fn new(entity: Entity, origin: Point, cell: f64, heights: Matrix) -> ReRes<Self> {
+        heights.ag_failed()?;
+        if cell <= 0.0 {
+            return Err(GameErr(NonPositiveSize {
+                key: "cell",
+                val: cell,
+            }));
+        }
+        if heights.rows() < 2 || heights.cols() < 2 {
+            return Err(GameErr(TerrainTooSmall((heights.rows(), heights.cols()))));
+        }
+        let mut range = (f64::INFINITY, f64::NEG_INFINITY);
+        for r in 0..heights.rows() {
+            for c in 0..heights.cols() {
+                let h = *heights.att(r, c);
+                range = (range.0.min(h), range.1.max(h));
+            }
+        }
+        Ok(Self {
+            entity,
+            origin,
+            cell,
+            heights,
+            range,
+            lighting: None,
+            background: None,
+            eye: 0.0,
+            charcoal: None,
+        })
+    }
+
+    /// Terrain of `size` rows and columns of vertices with heights `height(x, y)` relative to `origin`
+    pub fn generate(
+        entity: Entity,
+        origin: Point,
+        cell: f64,
+        size: [usize; 2],
+        height: impl Fn(f64, f64) -> f64,
+    ) -> ReRes<Self> {
+        let heights = (0..size[0])
+            .map(|r| {
+                (0..size[1])
+                    .map(|c| height(c as f64 * cell, r as f64 * cell))
+                    .collect()
+            })
+            .collect::<Vec<Vec<f64>>>();
+        Self::new(entity, origin, cell, Matrix::from_double(heights))
+    }
+
+    /// Terrain with heights from plain PGM image, see `Terrain::parse_pgm`, scaled so white is `scale` high
+    pub fn from_pgm(
+        entity: Entity,
+        path: impl AsRef<Path>,
+        origin: Point,
+        cell: f64,
+        scale: f64,
+    ) -> ReRes<Self> {
+        let mut heights = Self::read_pgm(path)?;
+        for r in 0..heights.rows() {
+            for c in 0..heights.cols() {
+                *heights.att_mut(r, c) *= scale;
+            }
+        }
+        Self::new(entity, origin, cell, heights)
+    }
+
+    /// Reads grayscale values of plain PGM image, see `Terrain::parse_pgm`
+    pub fn read_pgm(path: impl AsRef<Path>) -> ReRes<Matrix> {
+        match read_to_string(path) {
+            Ok(content) => Self::parse_pgm(&content),
+            Err(err) => Err(LoadErr(UnreadableFile(err.kind()))),
+        }
+    }
+
+    /// Grayscale values of plain PGM image `content` mapped into [0, 1], row of image is row of grid
+    pub fn parse_pgm(content: &str) -> ReRes<Matrix> {
+        let last = content.lines().count().max(1);
+        let mut tokens = content.lines().enumerate().flat_map(|(idx, line)| {
+            let line = line.split('#').next().unwrap();
+            line.split_whitespace().map(move |token| (idx + 1, token))
+        });
+        match tokens.next() {
+            Some((_, PGM_MAGIC)) => {}
+            Some((line, _)) => return Err(LoadErr(UnsupportedFormat { line })),
+            None => return Err(LoadErr(UnsupportedFormat { line: last })),
+        }
+        let mut number = |expected: usize| -> ReRes<(usize, u32)> {
+            match tokens.next() {
+                Some((line, token)) => match token.parse::<u32>() {
+                    Ok(value) => Ok((line, value)),
+                    Err(_) => Err(LoadErr(InvalidNumber { line })),
+                },
+                None => Err(LoadErr(MissingValues {
+                    line: last,
+                    expected,
+                })),
+            }
+        };
+
+        let (line, cols) = number(3)?;
+        if cols == 0 {
+            return Err(LoadErr(InvalidNumber { line }));
+        }
+        let (line, rows) = number(3)?;
+        if rows == 0 {
+            return Err(LoadErr(InvalidNumber { line }));
+        }
+        let (line, max) = number(3)?;
+        if max == 0 {
+            return Err(LoadErr(InvalidNumber { line }));
+        }
+        let total = match (rows as usize).checked_mul(cols as usize) {
+            Some(total) => total,
+            None => return Err(LoadErr(InvalidNumber { line })),
+        };
+
+        let mut heights = vec![];
+        for _ in 0..rows {
+            let mut row = vec![];
+            for _ in 0..cols {
+                let (line, value) = number(total)?;
+                if max < value {
+                    return Err(LoadErr(InvalidNumber { line }));
+                }
+                row.push(value as f64 / max as f64);
+            }
+            heights.push(row);
+        }
+        Ok(Matrix::from_double(heights))
+    }
+
+    pub fn with_charcoal(mut self, charcoal: Charcoal) -> Self {
+        self.charcoal = Some(charcoal);
+        self
+    }
+
+    /// Surfaces are lit according to normals of triangles, otherwise they're faded with distance
+    pub fn with_lighting(mut self, lighting: Lighting) -> Self {
+        self.lighting = Some(lighting);
+        self
+    }
+
+    /// Cell drawn where rays hit nothing
+    pub fn with_background(mut self, cell: Cell) -> Self {
+        self.background = Some(cell);
+        self
+    }
+
+    /// Height of viewer above the ground that `validate_mv` keeps
+    pub fn with_eye(mut self, eye: f64) -> Self {
+        self.eye = eye;
+        self
+    }
+
+    /// Heights in vertices
+    pub fn heights(&self) -> &Matrix {
+        &self.heights
+    }
+
+    /// Number of cells along x and y axes
+    pub fn size(&self) -> [usize; 2] {
+        [self.heights.cols() - 1, self.heights.rows() - 1]
+    }
+
+    /// Height of the ground surface in the point `(x, y)`, `None` if it's out of terrain
+    pub fn height_at(&self, x: f64, y: f64) -> Option<f64> {
+        let (fx, fy) = (
+            (x - self.origin[0]) / self.cell,
+            (y - self.origin[1]) / self.cell,
+        );
+        let size = self.size();
+        if fx < 0.0 || fy < 0.0 || (size[0] as f64) < fx || (size[1] as f64) < fy {
+            return None;
+        }
+        let (c, r) = (
+            (fx as usize).min(size[0] - 1),
+            (fy as usize).min(size[1] - 1),
+        );
+        let (u, v) = (fx - c as f64, fy - r as f64);
+        let h = |dr: usize, dc: usize| *self.heights.att(r + dr, c + dc);
+        let height = match v <= u {
+            true => h(0, 0) + u * (h(0, 1) - h(0, 0)) + v * (h(1, 1) - h(0, 1)),
+            false => h(0, 0) + v * (h(1, 0) - h(0, 0)) + u * (h(1, 1) - h(1, 0)),
+        };
+        Some(self.origin[2] + height)
+    }
+
+    /// Vertex of grid in world coordinates
+    fn vertex(&self, r: usize, c: usize) -> [f64; 3] {
+        [
+            self.origin[0] + c as f64 * self.cell,
+            self.origin[1] + r as f64 * self.cell,
+            self.origin[2] + *self.heights.att(r, c),
+        ]
+    }
+
+    /// The nearest crossing of two triangles of the cell with unit normal of crossed one
+    fn cross_cell(
+        &self,
+        o: &[f64; 3],
+        d: &[f64; 3],
+        r: usize,
+        c: usize,
+    ) -> Option<(f64, [f64; 3])> {
+        let (v00, v01, v11, v10) = (
+            self.vertex(r, c),
+            self.vertex(r, c + 1),
+            self.vertex(r + 1, c + 1),
+            self.vertex(r + 1, c),
+        );
+        [[&v00, &v01, &v11], [&v00, &v11, &v10]]
+            .into_iter()
+            .filter_map(|tri| {
+                let (t, _) = moller_trumbore(o, d, tri)?;
+                let (e1, e2) = (
+                    [
+                        tri[1][0] - tri[0][0],
+                        tri[1][1] - tri[0][1],
+                        tri[1][2] - tri[0][2],
+                    ],
+                    [
+                        tri[2][0] - tri[0][0],
+                        tri[2][1] - tri[0][1],
+                        tri[2][2] - tri[0][2],
+                    ],
+                );
+                let n = [
+                    e1[1] * e2[2] - e1[2] * e2[1],
+                    e1[2] * e2[0] - e1[0] * e2[2],
+                    e1[0] * e2[1] - e1[1] * e2[0],
+                ];
+                let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+                Some((t, [n[0] / len, n[1] / len, n[2] / len]))
+            })
+            .min_by_key(|(t, _)| Float(*t))
+    }
+
+    /// Clips ray by bounds of terrain and steps over cells until some of them is crossed
+    fn traverse(&self, inc: &Point, dir: &Vector) -> Option<(f64, [f64; 3])> {
+        let (enter, exit) = self.bounds()?.intersect(inc, dir)?;
+        let (o, d) = ([inc[0], inc[1], inc[2]], [dir.at(0), dir.at(1), dir.at(2)]);
+        let size = self.size();
+
+        let (mut pos, mut step) = ([0_isize; 2], [0_isize; 2]);
+        let (mut next, mut delta) = ([f64::INFINITY; 2], [f64::INFINITY; 2]);
+        for i in 0..2 {
+            let f = (o[i] + enter * d[i] - self.origin[i]) / self.cell;
+            pos[i] = (f.floor() as isize).clamp(0, size[i] as isize - 1);
+            if d[i] > 0.0 {
+                step[i] = 1;
+                next[i] = (self.origin[i] + (pos[i] + 1) as f64 * self.cell - o[i]) / d[i];
+                delta[i] = self.cell / d[i];
+            } else if d[i] < 0.0 {
+                step[i] = -1;
+                next[i] = (self.origin[i] + pos[i] as f64 * self.cell - o[i]) / d[i];
+                delta[i] = -self.cell / d[i];
+            }
+        }
+
+        loop {
+            if let Some(crossing) = self.cross_cell(&o, &d, pos[1] as usize, pos[0] as usize) {
+                return Some(crossing);
+            }
+            let i = match next[0] < next[1] {
+                true => 0,
+                false => 1,
+            };
+            if !next[i].is_finite() || next[i] > exit {
+                return None;
+            }
+            next[i] += delta[i];
+            pos[i] += step[i];
+            if pos[i] < 0 || size[i] as isize <= pos[i] {
+                return None;
+            }
+        }
+    }
+}
+
+impl AsEntity for Terrain {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for Terrain {
+    fn collide(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        self.traverse(inc, dir).map(|(t, _)| t)
+    }
+
+    fn charmap(&self, dist: f64) -> Option<char> {
+        self.charcoal.as_ref().map(|charcoal| charcoal.ignite(dist))
+    }
+
+    fn charcoal(&self) -> Option<&Charcoal> {
+        self.charcoal.as_ref()
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        let size = self.size();
+        Some(Aabb::new(
+            vec![
+                self.origin[0],
+                self.origin[1],
+                self.origin[2] + self.range.0,
+            ],
+            vec![
+                self.origin[0] + size[0] as f64 * self.cell,
+                self.origin[1] + size[1] as f64 * self.cell,
+                self.origin[2] + self.range.1,
+            ],
+        ))
+    }
+
+    /// Viewer is kept `eye` above the ground surface
+    fn ground(&self, x: f64, y: f64) -> Option<f64> {
+        self.height_at(x, y).map(|height| height + self.eye)
+    }
+
+    /// Hit with normal of the crossed triangle and position within terrain as texture coordinates
+    fn hit(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        let (t, normal) = self.traverse(inc, dir)?;
+        let hit = Hit::new(inc, dir, t, Rc::clone(self.entity.id()));
+        let size = self.size();
+        let uv = (
+            ((hit.pt[0] - self.origin[0]) / (size[0] as f64 * self.cell)).clamp(0.0, 1.0),
+            ((hit.pt[1] - self.origin[1]) / (size[1] as f64 * self.cell)).clamp(0.0, 1.0),
+        );
+        let mut hit = hit.with_normal(Vector::new(normal.to_vec())).with_uv(uv);
+        hit.charcoal = self.charcoal.as_ref();
+        Some(hit)
+    }
+}
+
+impl AsScene for Terrain {
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        self.hit(cs, inc, dir)
+    }
+
+    fn shade(&self, cs: &CoordSys, dir: &Vector, hit: &Hit) -> Option<Cell> {
+        hit.shade(self.lighting.as_ref(), cs, dir)
+    }
+
+    fn background(&self, _cs: &CoordSys, _dir: &Vector) -> Option<Cell> {
+        self.background
+    }
+
+    /// Lifts or lowers `mv`, so it ends `eye` above the ground. Movement out of terrain is kept as is
+    fn validate_mv(&self, _cs: &CoordSys, pos: &Point, mv: &mut Vector) {
+        if let Some(ground) = self.ground(pos[0] + mv.at(0), pos[1] + mv.at(1)) {
+            *mv.at_mut(2) = ground - pos[2];
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use {
+    crate::{engn::*, math::*},
+    std::rc::Rc,
+    uuid::Uuid,
+};
+
+fn ball(center: Vec<f64>, radius: f64) -> HypeEllipse {
+    HypeEllipse::new(
+        Entity::new(Rc::new(Uuid::new_v4())),
+        Point::new(center),
+        Basis::default(),
+        vec![radius; 3],
+        None,
+    )
+    .unwrap()
+}
+
+fn balls() -> Vec<HypeEllipse> {
+    let mut balls = vec![];
+    for x in 0..5 {
+        for y in 0..5 {
+            balls.push(ball(
+                vec![
+                    4.0 + 3.0 * x as f64,
+                    -6.0 + 3.0 * y as f64,
+                    (x + y) as f64 % 3.0,
+                ],
+                1.0,
+            ));
+        }
+    }
+    balls
+}
+
+#[test]
+fn aabb_intersect() {
+    let aabb = Aabb::new(vec![1.0, -1.0, -1.0], vec![3.0, 1.0, 1.0]);
+    let inc = Point::default();
+    assert_eq!(
+        aabb.intersect(&inc, &Vector::new(vec![1.0, 0.0, 0.0])),
+        Some((1.0, 3.0))
+    );
+    assert_eq!(
+        aabb.intersect(&inc, &Vector::new(vec![-1.0, 0.0, 0.0])),
+        None
+    );
+    assert_eq!(
+        aabb.intersect(&inc, &Vector::new(vec![1.0, 2.0, 0.0])),
+        None
+    );
+    assert_eq!(
+        aabb.intersect(
+            &Point::new(vec![2.0, 0.0, 0.0]),
+            &Vector::new(vec![0.0, 0.0, 1.0])
+        ),
+        Some((0.0, 1.0))
+    );
+}
+
+#[test]
+fn aabb_flat_touched() {
+    let aabb = Aabb::new(vec![2.0, 0.0, 0.0], vec![2.0, 5.0, 5.0]);
+    assert_eq!(
+        aabb.intersect(
+            &Point::new(vec![0.0, 1.0, 1.0]),
+            &Vector::new(vec![1.0, 0.0, 0.0])
+        ),
+        Some((2.0, 2.0))
+    );
+}
+
+#[test]
+fn aabb_corners_swapped() {
+    let aabb = Aabb::new(vec![3.0, 1.0], vec![1.0, -1.0]);
+    assert_eq!(aabb.min(), &vec![1.0, -1.0]);
+    assert_eq!(aabb.max(), &vec![3.0, 1.0]);
+}
+
+#[test]
+fn ellipse_bounds() {
+    set_biform_identity();
+    let ellipse = HypeEllipse::new(
+        Entity::new(Rc::new(Uuid::new_v4())),
+        Point::new(vec![3.0, 0.0, 0.0]),
+        Basis::new(
+            Matrix::from_double(vec![
+                vec![0.0, -1.0, 0.0],
+                vec![1.0, 0.0, 0.0],
+                vec![0.0, 0.0, 1.0],
+            ])
+            .to_multicol(),
+        )
+        .unwrap(),
+        vec![4.0, 1.0, 2.0],
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        ellipse.bounds(),
+        Some(Aabb::new(vec![2.0, -4.0, -2.0], vec![4.0, 4.0, 2.0]))
+    );
+}
+
+#[test]
+fn plane_unbounded() {
+    let plane = HypePlane::new(
+        Entity::new(Rc::new(Uuid::new_v4())),
+        Point::default(),
+        Vector::new(vec![0.0, 0.0, 1.0]),
+        None,
+    )
+    .unwrap();
+    assert_eq!(plane.bounds(), None);
+}
+
+#[test]
+fn bvh_nearest_as_naive() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let balls = balls();
+    let bvh = Bvh::build(&balls.iter().map(|b| b.bounds()).collect::<Vec<_>>());
+    assert_eq!(bvh.len(), balls.len());
+    let inc = Point::new(vec![0.0, 0.0, 1.0]);
+    for y in -10..=10 {
+        for z in -4..=4 {
+            let dir = Vector::new(vec![1.0, y as f64 * 0.05, z as f64 * 0.05]);
+            let naive = Hit::nearest(balls.iter().map(|b| b.hit(&cs, &inc, &dir)));
+            let accel = bvh.nearest(&inc, &dir, |i| balls[i].hit(&cs, &inc, &dir));
+            assert_eq!(
+                naive.map(|h| (h.dist, h.id)),
+                accel.map(|h| (h.dist, h.id)),
+                "ray {:?}",
+                dir
+            );
+        }
+    }
+}
+
+#[test]
+fn bvh_prunes_farther_objects() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let balls = (0..16)
+        .map(|i| ball(vec![3.0 + 3.0 * i as f64, 0.0, 0.0], 1.0))
+        .collect::<Vec<_>>();
+    let bvh = Bvh::build(&balls.iter().map(|b| b.bounds()).collect::<Vec<_>>());
+    let mut tested = 0;
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let hit = bvh.nearest(&Point::default(), &dir, |i| {
+        tested += 1;
+        balls[i].hit(&cs, &Point::default(), &dir)
+    });
+    assert_eq!(hit.unwrap().dist, 2.0);
+    assert!(tested <= 4, "tested {} objects", tested);
+}
+
+#[test]
+fn bvh_unbounded_always_tested() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let plane = HypePlane::new(
+        Entity::new(Rc::new(Uuid::new_v4())),
+        Point::new(vec![1.0, 0.0, 0.0]),
+        Vector::new(vec![1.0, 0.0, 0.0]),
+        None,
+    )
+    .unwrap();
+    let ball = ball(vec![5.0, 0.0, 0.0], 1.0);
+    let objects: Vec<&dyn AsCollided> = vec![&ball, &plane];
+    let bvh = Bvh::build(&objects.iter().map(|o| o.bounds()).collect::<Vec<_>>());
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let hit = bvh
+        .nearest(&Point::default(), &dir, |i| {
+            objects[i].hit(&cs, &Point::default(), &dir)
+        })
+        .unwrap();
+    assert_eq!(hit.dist, 1.0);
+}
+
+#[test]
+fn bvh_refit() {
+    let mut bounds = vec![
+        Some(Aabb::new(vec![0.0; 3], vec![1.0; 3])),
+        Some(Aabb::new(vec![5.0; 3], vec![6.0; 3])),
+        Some(Aabb::new(vec![10.0; 3], vec![11.0; 3])),
+    ];
+    let mut bvh = Bvh::build(&bounds);
+    bounds[2] = Some(Aabb::new(vec![20.0; 3], vec![21.0; 3]));
+    bvh.refit(&bounds);
+    assert_eq!(bvh.bounds(), Some(&Aabb::new(vec![0.0; 3], vec![21.0; 3])));
+
+    bounds.push(None);
+    bvh.refit(&bounds);
+    assert_eq!(bvh.len(), 4);
+}
+
+#[test]
+fn scene_list_refits_moved_object() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut scene = SceneList::new();
+    let moved = scene.add(Box::new(ball(vec![5.0, 0.0, 0.0], 1.0)), None);
+    scene.add(Box::new(ball(vec![10.0, 0.0, 0.0], 1.0)), None);
+    scene.add(Box::new(ball(vec![5.0, 5.0, 0.0], 1.0)), None);
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    assert_eq!(
+        scene.collide(&cs, &Point::default(), &dir).unwrap().dist,
+        4.0
+    );
+
+    assert!(scene
+        .mv(&moved, &Vector::new(vec![10.0, 0.0, 0.0]))
+        .unwrap());
+    assert_eq!(
+        scene.collide(&cs, &Point::default(), &dir).unwrap().dist,
+        9.0
+    );
+
+    scene.add(Box::new(ball(vec![3.0, 0.0, 0.0], 1.0)), None);
+    assert_eq!(
+        scene.collide(&cs, &Point::default(), &dir).unwrap().dist,
+        2.0
+    );
+}
@@ -1,5 +1,5 @@
 use {
-    super::super::{camera::rays, camera::rays_df, *},
+    super::super::{camera::init_rays, camera::rays_df, *},
     crate::{conf::*, grid::*, math::*},
     std::f64::consts::{FRAC_PI_4, FRAC_PI_8, PI},
 };
@@ -7,7 +7,7 @@ use {
 #[test]
 fn xy_rays_df_discr_3() {
     let df = vec![1.0];
-    assert_eq!(rays_df(1, PI / 2.0, 3), df)
+    assert!(aeq(&rays_df(1, PI / 2.0, 3)[0], &df[0]))
 }
 
 #[test]
@@ -30,126 +30,338 @@ fn xz_rays_df_discr_4_closest() {
 
 #[test]
 fn rays_discr_3_3_att_0_2() {
-    let rays = rays(PI / 2.0, PI / 2.0, 3, 3);
-    assert_eq!(rays.att(0, 2), &Vector::new(vec![1.0, -1.0, 1.0]));
+    let rays = init_rays(PI / 2.0, PI / 2.0, 3, 3);
+    assert!(rays[0][2]
+        .coord
+        .aeq(&Vector::new(vec![1.0, -1.0, 1.0]).coord));
 }
 
 #[test]
 fn rays_discr_4_3_att_1_1() {
-    let rays = rays(PI / 2.0, PI / 2.0, 4, 3);
-    assert!(rays
-        .att(1, 1)
+    let rays = init_rays(PI / 2.0, PI / 2.0, 4, 3);
+    assert!(rays[1][1]
         .coord
         .aeq(&Vector::new(vec![1.0, (PI / 12.0).tan(), 0.0]).coord));
 }
 
 #[test]
 fn rays_discr_6_4_att_3_4() {
-    let rays = rays(2.0 * PI / 3.0, PI / 4.0, 6, 4);
+    let rays = init_rays(2.0 * PI / 3.0, PI / 4.0, 6, 4);
     let res = Vector::new(vec![1.0, -(PI / 5.0).tan(), -(PI / 8.0).tan()]).coord;
-    assert!(rays.att(3, 4).coord.aeq(&res));
+    assert!(rays[3][4].coord.aeq(&res));
 }
 
 #[test]
 fn rays_discr_6_5_att_center() {
-    let rays = rays(2.0 * PI / 3.0, PI / 4.0, 6, 5);
+    let rays = init_rays(2.0 * PI / 3.0, PI / 4.0, 6, 5);
     let res = Vector::new(vec![1.0, -(PI / 15.0).tan(), 0.0]).coord;
-    assert!(rays.att(2, 3).coord.aeq(&res));
+    assert!(rays[2][3].coord.aeq(&res));
 }
 
 #[test]
 fn mv_camera_about_pos() {
-    let mut id_pool = IdPool::new();
-    let mut camera = Camera::new(
-        Entity::new(id_pool.generate()),
-        Point::default(),
-        Vector::new(vec![1.0, 0.0, 0.0]),
-        500.0,
-        2.0 * PI / 3.0,
-        PI / 4.0,
-        6,
-        5,
-    );
-    camera.dir(&Vector::new(vec![1.0, 0.0, 0.0])).unwrap();
+    let mut camera = Camera::new(Point::default(), 2, 2.0 * PI / 3.0, PI / 4.0, (5, 6), 500.0);
+    camera.mv(&Vector::new(vec![1.0, 0.0, 0.0])).unwrap();
     assert_eq!(camera.pos, Point::new(vec![1.0, 0.0, 0.0]))
 }
 
 #[test]
 fn mv_camera_about_rays() {
-    let mut id_pool = IdPool::new();
-    let mut camera = Camera::new(
-        Entity::new(id_pool.generate()),
-        Point::default(),
-        Vector::new(vec![1.0, 0.0, 0.0]),
-        500.0,
-        2.0 * PI / 3.0,
-        PI / 4.0,
-        6,
-        5,
-    );
-    camera.dir(&Vector::new(vec![1.0, 0.0, 0.0])).unwrap();
+    let mut camera = Camera::new(Point::default(), 2, 2.0 * PI / 3.0, PI / 4.0, (5, 6), 500.0);
+    camera.mv(&Vector::new(vec![1.0, 0.0, 0.0])).unwrap();
     assert!(camera
-        .rays
-        .att(2, 3)
+        .ray(2, 3)
         .coord
         .aeq(&Vector::new(vec![1.0, -(PI / 15.0).tan(), 0.0]).coord))
 }
 
 #[test]
 fn rotate_camera_about_dir() {
-    let mut id_pool = IdPool::new();
-    let mut camera = Camera::new(
-        Entity::new(id_pool.generate()),
-        Point::default(),
-        Vector::new(vec![1.0, 0.0, 0.0]),
-        500.0,
-        2.0 * PI / 3.0,
-        PI / 4.0,
-        6,
-        5,
-    );
-    camera.planar_rotate(0, 1, -PI / 4.0).unwrap();
-    assert_eq!(
-        camera.dir,
-        Vector::new(vec![FRAC_PI_4.cos(), -FRAC_PI_4.sin(), 0.0])
-    );
+    let mut camera = Camera::new(Point::default(), 2, 2.0 * PI / 3.0, PI / 4.0, (5, 6), 500.0);
+    camera.rotate_right(1);
+    let dir = camera.dir();
+    assert!(aeq(&dir.0, &FRAC_PI_4.cos()));
+    assert!(aeq(&dir.1, &-FRAC_PI_4.sin()));
 }
 
 #[test]
 fn rotate_camera_twice_about_dir() {
-    let mut id_pool = IdPool::new();
-    let mut camera = Camera::new(
-        Entity::new(id_pool.generate()),
-        Point::default(),
-        Vector::new(vec![1.0, 0.0, 0.0]),
-        500.0,
-        2.0 * PI / 3.0,
-        PI / 4.0,
-        6,
-        5,
-    );
-    camera.planar_rotate(0, 1, -PI / 4.0).unwrap();
-    camera.planar_rotate(0, 1, PI / 8.0).unwrap();
-    assert_eq!(
-        camera.dir,
-        Vector::new(vec![FRAC_PI_8.cos(), -FRAC_PI_8.sin(), 0.0])
-    );
+    let mut camera = Camera::new(Point::default(), 4, 2.0 * PI / 3.0, PI / 4.0, (5, 6), 500.0);
+    camera.rotate_right(2);
+    camera.rotate_left(1);
+    let dir = camera.dir();
+    assert!(aeq(&dir.0, &FRAC_PI_8.cos()));
+    assert!(aeq(&dir.1, &-FRAC_PI_8.sin()));
 }
 
 #[test]
 fn rotate_camera_about_rays() {
-    let mut id_pool = IdPool::new();
+    let mut camera = Camera::new(Point::default(), 2, PI / 2.0, PI / 2.0, (3, 4), 500.0);
+    camera.rotate_up(1);
+    let res = Matrix::col(vec![0.0, -1.0, 2.0_f64.sqrt()]);
+    assert!(camera.ray(0, 3).coord.aeq(&res));
+}
+
+#[test]
+fn look_camera_continuously() {
+    let mut camera = Camera::new(Point::default(), 6, PI / 2.0, PI / 2.0, (3, 3), 500.0);
+    camera.look(0.3, 0.2);
+    let ray = camera.ray(1, 1);
+    let expected = [
+        0.3_f64.cos() * 0.2_f64.cos(),
+        0.3_f64.sin() * 0.2_f64.cos(),
+        0.2_f64.sin(),
+    ];
+    for i in 0..3 {
+        assert!((ray.at(i) - expected[i]).abs() < 1e-12);
+    }
+    assert!(aeq(&camera.dir().0, &0.3_f64.cos()));
+
+    camera.rotate(-0.1, 0.05);
+    assert!((camera.yaw() - 0.2).abs() < 1e-12);
+    assert!((camera.pitch() - 0.25).abs() < 1e-12);
+}
+
+#[test]
+fn clamp_camera_pitch_and_wrap_yaw() {
+    let mut camera = Camera::new(Point::default(), 2, PI / 2.0, PI / 2.0, (3, 3), 500.0);
+    camera.rotate_up(5);
+    assert_eq!(camera.pitch(), PI / 2.0);
+    let ray = camera.ray(1, 1);
+    assert!(ray.at(0).abs() < 1e-12 && (ray.at(2) - 1.0).abs() < 1e-12);
+
+    camera.look(3.0 * PI / 2.0, -PI);
+    assert!((camera.yaw() + PI / 2.0).abs() < 1e-12);
+    assert_eq!(camera.pitch(), -PI / 2.0);
+}
+
+#[test]
+fn rotate_camera_by_discrete_steps() {
+    let mut stepped = Camera::new(Point::default(), 6, PI / 2.0, PI / 3.0, (4, 5), 500.0);
+    let mut continuous = Camera::new(Point::default(), 6, PI / 2.0, PI / 3.0, (4, 5), 500.0);
+    stepped.rotate_left(3);
+    stepped.rotate_down(2);
+    continuous.rotate(PI / 4.0, -PI / 6.0);
+    assert!(aeq(&stepped.angle_step(), &(PI / 12.0)));
+    for r in 0..4 {
+        for c in 0..5 {
+            for i in 0..3 {
+                let df = stepped.ray(r, c).at(i) - continuous.ray(r, c).at(i);
+                assert!(df.abs() < 1e-12);
+            }
+        }
+    }
+}
+
+fn assert_vec(actual: &Vector, expected: [f64; 3]) {
+    for i in 0..3 {
+        assert!(
+            (actual.at(i) - expected[i]).abs() < 1e-12,
+            "{:?} != {expected:?}",
+            actual.coord
+        );
+    }
+}
+
+#[test]
+fn camera_frame_vectors() {
+    let mut camera = Camera::new(Point::default(), 6, PI / 2.0, PI / 2.0, (3, 3), 500.0);
+    assert_vec(&camera.forward(), [1.0, 0.0, 0.0]);
+    assert_vec(&camera.right(), [0.0, -1.0, 0.0]);
+    assert_vec(&camera.up(), [0.0, 0.0, 1.0]);
+
+    camera.orient(PI / 2.0, 0.0, 0.0);
+    assert_vec(&camera.forward(), [0.0, 1.0, 0.0]);
+    assert_vec(&camera.right(), [1.0, 0.0, 0.0]);
+
+    camera.orient(0.7, -0.4, 1.1);
+    let (f, r, u) = (camera.forward(), camera.right(), camera.up());
+    let dot = |l: &Vector, r: &Vector| (0..3).map(|i| l.at(i) * r.at(i)).sum::<f64>();
+    assert!(dot(&f, &r).abs() < 1e-12 && dot(&f, &u).abs() < 1e-12 && dot(&r, &u).abs() < 1e-12);
+    assert!((dot(&f, &f) - 1.0).abs() < 1e-12);
+    assert_vec(
+        &f,
+        [
+            0.7_f64.cos() * 0.4_f64.cos(),
+            0.7_f64.sin() * 0.4_f64.cos(),
+            -0.4_f64.sin(),
+        ],
+    );
+    assert!((camera.roll() - 1.1).abs() < 1e-12);
+}
+
+#[test]
+fn roll_camera() {
+    let mut camera = Camera::new(Point::default(), 6, PI / 2.0, PI / 2.0, (3, 3), 500.0);
+    camera.tilt(PI / 2.0);
+    assert_vec(&camera.forward(), [1.0, 0.0, 0.0]);
+    assert_vec(&camera.up(), [0.0, -1.0, 0.0]);
+    assert_vec(camera.ray(1, 0), [1.0, 0.0, 1.0]);
+
+    camera.look(0.0, 0.3);
+    assert!((camera.roll() - PI / 2.0).abs() < 1e-12);
+    camera.tilt(PI);
+    assert!((camera.roll() + PI / 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn look_camera_at_point() {
     let mut camera = Camera::new(
-        Entity::new(id_pool.generate()),
-        Point::default(),
-        Vector::new(vec![1.0, 0.0, 0.0]),
-        500.0,
+        Point::new(vec![1.0, 1.0, 1.0]),
+        6,
         PI / 2.0,
         PI / 2.0,
-        4,
-        3,
+        (3, 3),
+        500.0,
     );
-    camera.planar_rotate(0, 2, PI / 4.0).unwrap();
-    let res = Matrix::col(vec![0.0, -1.0, 2.0_f64.sqrt()]);
-    assert!(camera.rays.att(0, 3).coord.aeq(&res));
+    camera.tilt(0.5);
+    camera.look_at(&Point::new(vec![1.0, 4.0, 4.0]));
+    assert!((camera.yaw() - PI / 2.0).abs() < 1e-12);
+    assert!((camera.pitch() - PI / 4.0).abs() < 1e-12);
+    assert_eq!(camera.roll(), 0.0);
+    let h = 0.5_f64.sqrt();
+    assert_vec(&camera.forward(), [0.0, h, h]);
+    assert_vec(camera.ray(1, 1), [0.0, h, h]);
+
+    camera.look_at(&Point::new(vec![1.0, 1.0, 1.0]));
+    assert!((camera.yaw() - PI / 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn cell_ray_of_camera() {
+    let mut camera = Camera::new(Point::default(), 6, PI / 2.0, PI / 3.0, (4, 5), 500.0);
+    camera.orient(0.3, 0.2, 0.1);
+    for r in 0..4 {
+        for c in 0..5 {
+            let ray = camera.ray(r, c);
+            assert_vec(
+                &camera.cell_ray(r, c, (1, 1)),
+                [ray.at(0), ray.at(1), ray.at(2)],
+            );
+        }
+    }
+
+    let camera = Camera::new(Point::default(), 6, PI / 2.0, PI / 3.0, (4, 5), 500.0);
+    let z = (PI / 6.0 - 0.5 * PI / 9.0).tan();
+    assert_vec(&camera.cell_ray(0, 2, (2, 1)), [1.0, 0.0, z]);
+    assert_vec(&camera.cell_ray(1, 2, (2, 1)), [1.0, 0.0, -z]);
+}
+
+#[test]
+fn orthographic_camera() {
+    let mut camera = Camera::new(
+        Point::new(vec![1.0, 2.0, 3.0]),
+        6,
+        PI / 2.0,
+        PI / 4.0,
+        (3, 5),
+        500.0,
+    )
+    .with_projection(Projection::Orthographic)
+    .with_view_width(8.0);
+    assert_eq!(camera.projection(), Projection::Orthographic);
+    for r in 0..3 {
+        for c in 0..5 {
+            assert_vec(camera.ray(r, c), [1.0, 0.0, 0.0]);
+        }
+    }
+    assert_vec(camera.origin(0, 0), [1.0, 6.0, 5.0]);
+    assert_vec(camera.origin(1, 2), [1.0, 2.0, 3.0]);
+    assert_vec(camera.origin(2, 4), [1.0, -2.0, 1.0]);
+
+    camera.mv(&Vector::new(vec![0.0, 0.0, 1.0])).unwrap();
+    assert_vec(camera.origin(2, 4), [1.0, -2.0, 2.0]);
+    camera.look(PI / 2.0, 0.0);
+    assert_vec(camera.ray(0, 0), [0.0, 1.0, 0.0]);
+    assert_vec(camera.origin(0, 0), [-3.0, 2.0, 6.0]);
+    let origin = camera.cell_origin(0, 0, (1, 1));
+    assert_vec(&origin, [-3.0, 2.0, 6.0]);
+}
+
+#[test]
+fn fisheye_camera() {
+    let camera = Camera::new(Point::default(), 6, 1.5 * PI, PI / 2.0, (3, 5), 500.0)
+        .with_projection(Projection::Fisheye);
+    assert_vec(camera.ray(1, 2), [1.0, 0.0, 0.0]);
+    let h = 0.5_f64.sqrt();
+    assert_vec(camera.ray(1, 0), [-h, h, 0.0]);
+    let a = 3.0 * PI / 8.0;
+    assert_vec(camera.ray(1, 3), [a.cos(), -a.sin(), 0.0]);
+    assert_vec(camera.ray(0, 2), [h, 0.0, h]);
+    let ray = camera.ray(0, 0);
+    let len = (0..3).map(|i| ray.at(i).powi(2)).sum::<f64>().sqrt();
+    assert!((len - 1.0).abs() < 1e-12);
+    assert_vec(camera.origin(0, 0), [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn panoramic_camera() {
+    let camera = Camera::new(Point::default(), 6, PI / 2.0, PI / 2.0, (3, 5), 500.0)
+        .with_projection(Projection::Panoramic);
+    for c in 0..5 {
+        let phi = (1.0 - (2 * c + 1) as f64 / 5.0) * PI;
+        assert_vec(camera.ray(1, c), [phi.cos(), phi.sin(), 0.0]);
+    }
+    assert_vec(camera.ray(1, 2), [1.0, 0.0, 0.0]);
+    assert_vec(camera.ray(0, 2), [1.0, 0.0, 1.0]);
+    let phi = 0.4 * PI;
+    let ray = camera.cell_ray(0, 1, (1, 1));
+    assert_vec(&ray, [phi.cos(), phi.sin(), 1.0]);
+}
+
+#[test]
+fn panoramic_columns_distinct() {
+    let camera = Camera::new(Point::default(), 6, PI / 2.0, PI / 2.0, (1, 4), 500.0)
+        .with_projection(Projection::Panoramic);
+    let h = 0.5_f64.sqrt();
+    assert_vec(camera.ray(0, 0), [-h, h, 0.0]);
+    assert_vec(camera.ray(0, 1), [h, h, 0.0]);
+    assert_vec(camera.ray(0, 2), [h, -h, 0.0]);
+    assert_vec(camera.ray(0, 3), [-h, -h, 0.0]);
+}
+
+#[test]
+fn zoom_keeps_fov_ratio() {
+    let mut camera = Camera::new(Point::default(), 6, PI / 2.0, PI / 4.0, (3, 5), 500.0);
+    camera.look(FRAC_PI_4, 0.0);
+    camera.zoom(2.0);
+    assert_eq!(camera.fov(), (PI / 4.0, PI / 8.0));
+    assert_eq!(camera.yaw(), FRAC_PI_4);
+    let fresh = Camera::new(Point::default(), 6, PI / 4.0, PI / 8.0, (3, 5), 500.0);
+    camera.look(0.0, 0.0);
+    for r in 0..3 {
+        for c in 0..5 {
+            assert_vec(camera.ray(r, c), [0, 1, 2].map(|i| fresh.ray(r, c).at(i)));
+        }
+    }
+}
+
+#[test]
+fn zoom_out_limited_by_projection() {
+    let mut camera = Camera::new(Point::default(), 6, PI / 2.0, PI / 4.0, (3, 5), 500.0);
+    camera.zoom(0.5);
+    assert_eq!(camera.fov(), (PI / 2.0, PI / 4.0));
+    camera.zoom(0.0);
+    assert_eq!(camera.fov(), (PI / 2.0, PI / 4.0));
+
+    let mut camera = camera.with_projection(Projection::Fisheye);
+    camera.zoom(0.5);
+    assert_eq!(camera.fov(), (PI, PI / 2.0));
+
+    let mut camera = camera.with_projection(Projection::Orthographic);
+    camera.zoom(0.5);
+    assert_eq!(camera.fov(), (PI, PI / 2.0));
+    assert_vec(camera.origin(1, 0), [0.0, 1.0, 0.0]);
+}
+
+#[test]
+fn resize_camera() {
+    let mut camera = Camera::new(Point::default(), 6, PI / 2.0, PI / 2.0, (3, 3), 500.0);
+    camera.look(PI / 2.0, 0.0);
+    camera.resize((5, 7));
+    assert_eq!(camera.size(), (5, 7));
+    assert_eq!(camera.rays.len(), 5);
+    assert_eq!(camera.rays[4].len(), 7);
+    assert_vec(camera.ray(2, 3), [0.0, 1.0, 0.0]);
+    assert_vec(camera.ray(2, 0), [-1.0, 1.0, 0.0]);
 }
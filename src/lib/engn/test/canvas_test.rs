@@ -0,0 +1,231 @@
+use {
+    super::{
+        super::{
+            canvas::{braille, changed_runs, half_block},
+            *,
+        },
+        FloorScene,
+    },
+    crate::{conf::*, math::*},
+    std::f64::consts::PI,
+};
+
+fn chars(line: &str) -> Vec<char> {
+    line.chars().collect()
+}
+
+fn canvas_camera() -> (Canvas<FloorScene>, Camera) {
+    set_biform_identity();
+    let canvas = Canvas::new((5, 9), "#+.".to_string(), 30.0, ColorMode::Mono);
+    let camera = Camera::new(
+        Point::new(vec![0.0, 0.0, 1.0]),
+        2,
+        PI / 2.0,
+        PI / 4.0,
+        (5, 9),
+        30.0,
+    );
+    (canvas, camera)
+}
+
+#[test]
+fn no_changed_runs() {
+    assert_eq!(changed_runs(&chars("abcd"), &chars("abcd")), vec![]);
+}
+
+#[test]
+fn single_changed_run() {
+    assert_eq!(changed_runs(&chars("abXYe"), &chars("abcde")), vec![(2, 4)]);
+}
+
+#[test]
+fn close_changed_runs_merged() {
+    assert_eq!(
+        changed_runs(&chars("Xbcdefgh"), &chars("abcdefgX")),
+        vec![(0, 8)]
+    );
+}
+
+#[test]
+fn far_changed_runs_separated() {
+    assert_eq!(
+        changed_runs(&chars("Xbcdefghi"), &chars("abcdefghX")),
+        vec![(0, 1), (8, 9)]
+    );
+}
+
+#[test]
+fn redraw_nothing_changed() {
+    let (mut canvas, camera) = canvas_camera();
+    let mut term = Headless::new(8, 9);
+    canvas
+        .update(&camera, &CoordSys::default(), &FloorScene::new())
+        .unwrap();
+    canvas.draw(&mut term).unwrap();
+    assert!(canvas.bytes_drawn() > 0);
+    canvas.draw(&mut term).unwrap();
+    assert_eq!(canvas.bytes_drawn(), 0);
+}
+
+#[test]
+fn redraw_less_than_full_draw() {
+    let (mut canvas, mut camera) = canvas_camera();
+    let mut term = Headless::new(8, 9);
+    let (cs, scene) = (CoordSys::default(), FloorScene::new());
+    canvas.update(&camera, &cs, &scene).unwrap();
+    canvas.draw(&mut term).unwrap();
+    let full = canvas.bytes_drawn();
+
+    camera.mv(&Vector::new(vec![0.0, 0.0, 1.0])).unwrap();
+    canvas.update(&camera, &cs, &scene).unwrap();
+    canvas.draw(&mut term).unwrap();
+    assert!(canvas.bytes_drawn() < full);
+    assert_eq!(term.frame().unwrap()[3..], canvas.lines()[..]);
+}
+
+#[test]
+fn full_redraw_after_invalidate() {
+    let (mut canvas, camera) = canvas_camera();
+    let mut term = Headless::new(8, 9);
+    canvas
+        .update(&camera, &CoordSys::default(), &FloorScene::new())
+        .unwrap();
+    canvas.draw(&mut term).unwrap();
+    let full = canvas.bytes_drawn();
+    term.clear().unwrap();
+    term.flush().unwrap();
+    canvas.invalidate();
+    canvas.draw(&mut term).unwrap();
+    assert_eq!(canvas.bytes_drawn(), full);
+    assert_eq!(term.frame().unwrap()[3..], canvas.lines()[..]);
+}
+
+#[test]
+fn resized_canvas_redrawn_fully() {
+    let (mut canvas, camera) = canvas_camera();
+    let mut term = Headless::new(8, 9);
+    canvas
+        .update(&camera, &CoordSys::default(), &FloorScene::new())
+        .unwrap();
+    canvas.draw(&mut term).unwrap();
+    canvas.resize((3, 7));
+    assert_eq!(canvas.size(), (3, 7));
+    assert_eq!(canvas.lines(), vec![" ".repeat(7); 3]);
+    canvas.draw(&mut term).unwrap();
+    let frame = term.frame().unwrap();
+    for r in 0..3 {
+        assert_eq!(frame[r + 3][..7], canvas.lines()[r]);
+    }
+}
+
+/// Floor painted in red by `FloorScene` charcoal
+struct RedFloor(FloorScene);
+
+impl AsScene for RedFloor {
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        self.0.collide(cs, inc, dir)
+    }
+
+    fn shade(&self, cs: &CoordSys, dir: &Vector, hit: &Hit) -> Option<Cell> {
+        let cell = self.0.shade(cs, dir, hit)?;
+        Some(Cell::new(cell.ch, Some(Rgb(255, 0, 0)), None))
+    }
+
+    fn background(&self, cs: &CoordSys, dir: &Vector) -> Option<Cell> {
+        self.0.background(cs, dir)
+    }
+
+    fn validate_mv(&self, _cs: &CoordSys, _pos: &Point, _mv: &mut Vector) {}
+}
+
+#[test]
+fn draw_colored_cells() {
+    set_biform_identity();
+    let mut canvas = Canvas::new((5, 9), "#+.".to_string(), 30.0, ColorMode::Ansi16);
+    let camera = Camera::new(
+        Point::new(vec![0.0, 0.0, 1.0]),
+        2,
+        PI / 2.0,
+        PI / 4.0,
+        (5, 9),
+        30.0,
+    );
+    let mut term = Headless::new(8, 9);
+    canvas
+        .update(&camera, &CoordSys::default(), &RedFloor(FloorScene::new()))
+        .unwrap();
+    canvas.draw(&mut term).unwrap();
+    assert_eq!(term.colors(3, 0), (None, None));
+    assert_eq!(
+        term.colors(7, 0),
+        (Some(crossterm::style::Color::Red), None)
+    );
+}
+
+#[test]
+fn mono_draw_ignores_colors() {
+    set_biform_identity();
+    let (mut mono, camera) = canvas_camera();
+    let mut colored = Canvas::new((5, 9), "#+.".to_string(), 30.0, ColorMode::Mono);
+    let (mut term, mut colored_term) = (Headless::new(8, 9), Headless::new(8, 9));
+    let cs = CoordSys::default();
+    mono.update(&camera, &cs, &FloorScene::new()).unwrap();
+    mono.draw(&mut term).unwrap();
+    colored
+        .update(&camera, &cs, &RedFloor(FloorScene::new()))
+        .unwrap();
+    colored.draw(&mut colored_term).unwrap();
+    assert_eq!(mono.bytes_drawn(), colored.bytes_drawn());
+    assert_eq!(colored_term.colors(7, 0), (None, None));
+}
+
+#[test]
+fn render_mode_samples() {
+    assert_eq!(RenderMode::Chars.samples((5, 9)), (5, 9));
+    assert_eq!(RenderMode::HalfBlock.samples((5, 9)), (10, 9));
+    assert_eq!(RenderMode::Braille.samples((5, 9)), (20, 18));
+}
+
+#[test]
+fn render_mode_hfov() {
+    assert_eq!(RenderMode::Chars.hfov(0.5, (5, 10)), 0.5);
+    assert_eq!(RenderMode::HalfBlock.hfov(0.5, (5, 10)), 0.5);
+    assert_eq!(RenderMode::Braille.hfov(0.5, (5, 10)), 0.5);
+}
+
+#[test]
+fn half_block_cells() {
+    let (red, blue) = (Some(Rgb(255, 0, 0)), Some(Rgb(0, 0, 255)));
+    let sample = |color| vec![Cell::new('#', color, None)];
+    assert_eq!(
+        half_block(&[sample(red), sample(blue)]),
+        Cell::new('▀', red, blue)
+    );
+    assert_eq!(
+        half_block(&[vec![Cell::default()], sample(blue)]),
+        Cell::new('▄', blue, None)
+    );
+    assert_eq!(
+        half_block(&[vec![Cell::default()], vec![Cell::default()]]),
+        Cell::default()
+    );
+}
+
+#[test]
+fn braille_cells() {
+    let (on, off) = (Cell::from('#'), Cell::default());
+    let mut samples = vec![vec![off; 2]; 4];
+    assert_eq!(braille(&samples), Cell::default());
+    samples[0][0] = on;
+    samples[3][1] = on;
+    assert_eq!(braille(&samples), Cell::from('⢁'));
+}
+
+#[test]
+fn braille_dithering() {
+    let dark = Cell::new('#', Some(Rgb(100, 100, 100)), None);
+    let samples = vec![vec![dark; 2]; 4];
+    let cell = braille(&samples);
+    assert_eq!(cell.ch, '⠕');
+    assert_eq!(cell.fg, Some(Rgb(100, 100, 100)));
+}
@@ -0,0 +1,88 @@
+use {
+    super::super::{color::ansi256, *},
+    crossterm::style::Color,
+};
+
+#[test]
+fn mono_quantize() {
+    assert_eq!(ColorMode::Mono.quantize(Rgb(10, 20, 30)), None);
+}
+
+#[test]
+fn truecolor_quantize() {
+    assert_eq!(
+        ColorMode::TrueColor.quantize(Rgb(10, 20, 30)),
+        Some(Color::Rgb {
+            r: 10,
+            g: 20,
+            b: 30
+        })
+    );
+}
+
+#[test]
+fn ansi16_quantize() {
+    assert_eq!(
+        ColorMode::Ansi16.quantize(Rgb(250, 10, 10)),
+        Some(Color::Red)
+    );
+    assert_eq!(
+        ColorMode::Ansi16.quantize(Rgb(100, 120, 110)),
+        Some(Color::DarkGrey)
+    );
+}
+
+#[test]
+fn ansi256_cube() {
+    assert_eq!(ansi256(Rgb(255, 0, 0)), 196);
+    assert_eq!(ansi256(Rgb(0, 95, 135)), 24);
+}
+
+#[test]
+fn ansi256_grayscale() {
+    assert_eq!(ansi256(Rgb(128, 128, 128)), 244);
+    assert_eq!(ansi256(Rgb(0, 0, 0)), 16);
+}
+
+#[test]
+fn color_mode_from_str() {
+    assert_eq!("256".parse::<ColorMode>(), Ok(ColorMode::Ansi256));
+    assert_eq!("truecolor".parse::<ColorMode>(), Ok(ColorMode::TrueColor));
+    assert!("rainbow".parse::<ColorMode>().is_err());
+}
+
+#[test]
+fn rgb_dim() {
+    assert_eq!(Rgb(200, 100, 0).dim(0.5), Rgb(100, 50, 0));
+    assert_eq!(Rgb(200, 100, 0).dim(2.0), Rgb(200, 100, 0));
+}
+
+#[test]
+fn charcoal_paint_fades() {
+    let charcoal = Charcoal::new("ab".to_string(), 10.0).with_fg(Rgb(200, 200, 200));
+    assert_eq!(
+        charcoal.paint(2.5),
+        Cell::new('a', Some(Rgb(150, 150, 150)), None)
+    );
+    assert_eq!(
+        charcoal.paint(10.0),
+        Cell::new('b', Some(Rgb(0, 0, 0)), None)
+    );
+}
+
+#[test]
+fn charcoal_paint_fade_distance() {
+    let charcoal = Charcoal::new("ab".to_string(), 2.0)
+        .with_fade(10.0)
+        .with_bg(Rgb(0, 0, 100));
+    assert_eq!(
+        charcoal.paint(5.0),
+        Cell::new('b', None, Some(Rgb(0, 0, 50)))
+    );
+}
+
+#[test]
+fn charcoal_paint_without_colors() {
+    let charcoal = Charcoal::new("ab".to_string(), 10.0);
+    assert_eq!(charcoal.paint(2.5), Cell::from('a'));
+}
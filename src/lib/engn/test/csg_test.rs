@@ -0,0 +1,271 @@
+use {
+    super::entity,
+    crate::{
+        engn::*,
+        errs::{GameErr::*, ReErr::*},
+        math::*,
+    },
+    std::rc::Rc,
+};
+
+fn cube(min: [f64; 3], max: [f64; 3], chars: &str) -> Box<dyn AsCollided> {
+    Box::new(
+        HypeBox::aligned(
+            entity(),
+            Point::new(min.to_vec()),
+            Point::new(max.to_vec()),
+            Some(Charcoal::new(chars.to_string(), 30.0)),
+        )
+        .unwrap(),
+    )
+}
+
+fn ball(center: [f64; 3], radius: f64) -> Box<dyn AsCollided> {
+    Box::new(
+        HypeEllipse::new(
+            entity(),
+            Point::new(center.to_vec()),
+            Basis::default(),
+            vec![radius; 3],
+            Some(Charcoal::new("o".to_string(), 30.0)),
+        )
+        .unwrap(),
+    )
+}
+
+/// Wall `x` within [4, 5] with the doorway `y` within [-1, 1] and `z` below 2 carved out of it
+fn wall_with_doorway() -> Csg {
+    Csg::difference(
+        entity(),
+        cube([4.0, -5.0, 0.0], [5.0, 5.0, 3.0], "#"),
+        cube([3.0, -1.0, -1.0], [6.0, 1.0, 2.0], "+"),
+    )
+    .unwrap()
+}
+
+#[test]
+fn combine_intervals() {
+    let (l, r) = ([(0.0, 4.0)], [(2.0, 6.0)]);
+    assert_eq!(CsgOp::Union.combine(&l, &r), vec![(0.0, 6.0)]);
+    assert_eq!(CsgOp::Intersection.combine(&l, &r), vec![(2.0, 4.0)]);
+    assert_eq!(CsgOp::Difference.combine(&l, &r), vec![(0.0, 2.0)]);
+    assert_eq!(
+        CsgOp::Difference.combine(&[(0.0, 6.0)], &[(2.0, 4.0)]),
+        vec![(0.0, 2.0), (4.0, 6.0)]
+    );
+    assert_eq!(
+        CsgOp::Intersection.combine(&[(0.0, 1.0)], &[(2.0, 3.0)]),
+        vec![]
+    );
+    assert_eq!(
+        CsgOp::Intersection.combine(&[(f64::NEG_INFINITY, 3.0)], &[(1.0, f64::INFINITY)]),
+        vec![(1.0, 3.0)]
+    );
+    assert_eq!(
+        CsgOp::Difference.combine(&[(1.0, 2.0)], &[(1.0, 2.0)]),
+        vec![]
+    );
+}
+
+#[test]
+fn solid_intervals() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let (inc, dir) = (Point::default(), Vector::new(vec![1.0, 0.0, 0.0]));
+    assert_eq!(
+        ball([5.0, 0.0, 0.0], 1.0).intervals(&cs, &inc, &dir),
+        Some(vec![(4.0, 6.0)])
+    );
+    assert_eq!(
+        cube([-1.0; 3], [1.0; 3], "#").intervals(&cs, &inc, &dir),
+        Some(vec![(-1.0, 1.0)])
+    );
+    let plane =
+        HypePlane::new(entity(), Point::new(vec![2.0, 0.0, 0.0]), dir.clone(), None).unwrap();
+    assert_eq!(
+        plane.intervals(&cs, &inc, &dir),
+        Some(vec![(f64::NEG_INFINITY, 2.0)])
+    );
+    let disc = HypeDisc::new(entity(), Point::default(), dir.clone(), 1.0, None).unwrap();
+    assert_eq!(disc.intervals(&cs, &inc, &dir), None);
+}
+
+#[test]
+fn difference_doorway() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let wall = wall_with_doorway();
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+
+    let hit = wall
+        .hit(&cs, &Point::new(vec![0.0, 3.0, 1.0]), &dir)
+        .unwrap();
+    assert!(aeq(&hit.dist, &4.0));
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '#');
+
+    assert!(wall
+        .collide(&cs, &Point::new(vec![0.0, 0.0, 1.0]), &dir)
+        .is_none());
+    assert!(wall
+        .collide(&cs, &Point::new(vec![0.0, 0.0, 2.5]), &dir)
+        .is_some_and(|dist| aeq(&dist, &4.0)));
+}
+
+#[test]
+fn difference_carved_normal() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let wall = wall_with_doorway();
+    let hit = wall
+        .hit(
+            &cs,
+            &Point::new(vec![4.5, 0.0, 1.0]),
+            &Vector::new(vec![0.0, 1.0, 0.0]),
+        )
+        .unwrap();
+    assert!(aeq(&hit.dist, &1.0));
+    assert_eq!(hit.normal, Some(Vector::new(vec![0.0, -1.0, 0.0])));
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '+');
+}
+
+#[test]
+fn union_and_intersection() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let inc = Point::default();
+
+    let union = Csg::union(
+        entity(),
+        ball([5.0, 0.0, 0.0], 1.0),
+        ball([6.5, 0.0, 0.0], 1.0),
+    )
+    .unwrap();
+    assert_eq!(union.intervals(&cs, &inc, &dir), Some(vec![(4.0, 7.5)]));
+    assert_eq!(union.bounds().unwrap().max()[0], 7.5);
+
+    let lens = Csg::intersection(
+        entity(),
+        ball([5.0, 0.0, 0.0], 1.0),
+        ball([6.5, 0.0, 0.0], 1.0),
+    )
+    .unwrap()
+    .with_charcoal(Charcoal::new("@".to_string(), 30.0));
+    let hit = lens.hit(&cs, &inc, &dir).unwrap();
+    assert!(aeq(&hit.dist, &5.5));
+    assert_eq!(hit.normal, Some(Vector::new(vec![-1.0, 0.0, 0.0])));
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '@');
+}
+
+#[test]
+fn nested_nodes() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let wall: Box<dyn AsCollided> = Box::new(wall_with_doorway());
+    let prop = Csg::union(entity(), wall, ball([4.5, 0.0, 1.0], 0.5)).unwrap();
+    let hit = prop
+        .hit(
+            &cs,
+            &Point::new(vec![0.0, 0.0, 1.0]),
+            &Vector::new(vec![1.0, 0.0, 0.0]),
+        )
+        .unwrap();
+    assert!(aeq(&hit.dist, &4.0));
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), 'o');
+}
+
+#[test]
+fn nested_nodes_normals() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let pair: Box<dyn AsCollided> = Box::new(
+        Csg::union(
+            entity(),
+            cube([4.0, -1.0, -1.0], [5.0, 1.0, 1.0], "#"),
+            cube([5.0, -1.0, -1.0], [6.0, 1.0, 1.0], "#"),
+        )
+        .unwrap(),
+    );
+    let carved = Csg::difference(entity(), pair, ball([6.0, 0.0, 0.0], 0.5)).unwrap();
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let hit = carved.hit(&cs, &Point::default(), &dir).unwrap();
+    assert!(aeq(&hit.dist, &4.0));
+    assert_eq!(hit.normal, Some(Vector::new(vec![-1.0, 0.0, 0.0])));
+
+    let wall: Box<dyn AsCollided> = Box::new(wall_with_doorway());
+    let prop = Csg::union(entity(), wall, ball([0.0, 0.0, 10.0], 0.5)).unwrap();
+    let hit = prop
+        .hit(
+            &cs,
+            &Point::new(vec![4.5, 0.0, 1.0]),
+            &Vector::new(vec![0.0, 1.0, 0.0]),
+        )
+        .unwrap();
+    assert!(aeq(&hit.dist, &1.0));
+    let normal = hit.normal.unwrap();
+    assert!(aeq(&normal.at(0), &0.0) && aeq(&normal.at(1), &-1.0) && aeq(&normal.at(2), &0.0));
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '+');
+}
+
+#[test]
+fn instance_operand_normal() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let prototype: Rc<dyn AsCollided> = Rc::from(ball([0.0; 3], 1.0));
+    let moved = Instance::new(entity(), prototype, Point::new(vec![5.0, 0.0, 0.0]));
+    let node = Csg::difference(entity(), Box::new(moved), ball([6.0, 0.0, 0.0], 0.5)).unwrap();
+    let hit = node
+        .hit(&cs, &Point::default(), &Vector::new(vec![1.0, 0.0, 0.0]))
+        .unwrap();
+    assert!(aeq(&hit.dist, &4.0));
+    let normal = hit.normal.unwrap();
+    assert!(aeq(&normal.at(0), &-1.0) && aeq(&normal.at(1), &0.0));
+}
+
+#[test]
+fn scene_with_csg() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut scene = SceneList::new();
+    scene.add(Box::new(wall_with_doorway()), None);
+    let hit = AsScene::collide(
+        &scene,
+        &cs,
+        &Point::new(vec![0.0, -3.0, 1.0]),
+        &Vector::new(vec![1.0, 0.0, 0.0]),
+    );
+    assert!(hit.is_some_and(|hit| aeq(&hit.dist, &4.0)));
+}
+
+#[test]
+fn non_solid_operands() {
+    let disc = HypeDisc::new(
+        entity(),
+        Point::new(vec![5.0, 0.0, 0.0]),
+        Vector::new(vec![-1.0, 0.0, 0.0]),
+        1.0,
+        None,
+    )
+    .unwrap();
+    let id = **disc.id();
+    let res = Csg::union(entity(), ball([5.0, 0.0, 0.0], 1.0), Box::new(disc));
+    assert_eq!(res.unwrap_err(), GameErr(NonSolidOperand(id)));
+
+    let prototype: Rc<dyn AsCollided> = Rc::new(
+        Mesh::new(
+            entity(),
+            vec![
+                Point::new(vec![0.0, 0.0, 0.0]),
+                Point::new(vec![1.0, 0.0, 0.0]),
+                Point::new(vec![0.0, 1.0, 0.0]),
+            ],
+            vec![[0, 1, 2]],
+            None,
+        )
+        .unwrap(),
+    );
+    let placed = Instance::new(entity(), prototype, Point::new(vec![5.0, 0.0, 0.0]));
+    let id = **placed.id();
+    let res = Csg::difference(entity(), Box::new(placed), ball([5.0, 0.0, 0.0], 1.0));
+    assert_eq!(res.unwrap_err(), GameErr(NonSolidOperand(id)));
+}
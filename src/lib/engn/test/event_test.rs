@@ -1,17 +1,19 @@
 use {
-    super::super::*,
+    super::{super::*, FloorScene},
     crate::{conf::*, math::*},
+    crossterm::event::Event,
+    std::f64::consts::PI,
 };
 
 struct EmtpyEvent(i8);
 
-impl From<console::Event> for EmtpyEvent {
-    fn from(value: console::Event) -> Self {
+impl From<Event> for EmtpyEvent {
+    fn from(value: Event) -> Self {
         EmtpyEvent(0)
     }
 }
 
-impl AsEvent<EntityList> for EmtpyEvent {}
+impl AsEvent<FloorScene> for EmtpyEvent {}
 
 #[test]
 fn push_to_event_queue() {
@@ -30,24 +32,26 @@ fn push_push_to_event_queue() {
 
 #[test]
 fn handle_all_to_event_queue() {
-    let mut game = Game::<EmtpyEvent, EventQueue<EmtpyEvent, EntityList>, EntityList>::default();
-    game.set_entities(EntityList::new());
+    set_biform_identity();
+    let mut camera = Camera::new(Point::default(), 2, PI / 2.0, PI / 2.0, (3, 3), 10.0);
+    let mut scene = FloorScene::new();
     let mut q = EventQueue::new();
     q.push(EmtpyEvent(0));
     q.push(EmtpyEvent(1));
     assert!(q
-        .handle_all(&mut game.camera, game.scene.as_mut().unwrap())
+        .handle_all(&CoordSys::default(), &mut camera, &mut scene)
         .is_ok());
 }
 
 #[test]
 fn handle_all_count_to_event_queue() {
-    let mut game = Game::<EmtpyEvent, EventQueue<EmtpyEvent, EntityList>, EntityList>::default();
-    game.set_entities(EntityList::new());
+    set_biform_identity();
+    let mut camera = Camera::new(Point::default(), 2, PI / 2.0, PI / 2.0, (3, 3), 10.0);
+    let mut scene = FloorScene::new();
     let mut q = EventQueue::new();
     q.push(EmtpyEvent(0));
     q.push(EmtpyEvent(1));
-    q.handle_all(&mut game.camera, game.scene.as_mut().unwrap())
+    q.handle_all(&CoordSys::default(), &mut camera, &mut scene)
         .unwrap();
     assert_eq!(q.events.len(), 0);
 }
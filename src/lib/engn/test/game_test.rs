@@ -1,21 +1,149 @@
 use {
-    super::super::*,
+    super::{
+        super::{canvas::CELL_ASPECT, *},
+        FloorScene,
+    },
     crate::{conf::*, math::*},
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+    std::f64::consts::PI,
 };
 
+type TestGame = Game<MovementEvent<FloorScene>, MovementEventSys, FloorScene, Headless>;
+
+fn key(code: KeyCode) -> Event {
+    Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
 #[test]
 fn given_vert_fov() {
     let mut conf = Conf::default();
     conf.hfov = Some(1.0);
-    let game = Game::<ConsoleEvent, EventQueue<ConsoleEvent, EntityList>, EntityList>::new(conf);
-    assert_eq!(game.unwrap().camera.hfov, 1.0);
+    let game = TestGame::with_term(
+        conf,
+        FloorScene::new(),
+        MovementEventSys::new(1.0),
+        Headless::new(24, 80),
+    );
+    assert_eq!(game.unwrap().camera.hfov, PI);
 }
 
 #[test]
 fn computed_vert_fov() {
     let mut conf = Conf::default();
     conf.wfov = 2.0;
-    conf.wscr = 200;
-    conf.hscr = 100;
-    assert_eq!(conf.comp_hfov(), 1.0);
+    let game = TestGame::with_term(
+        conf,
+        FloorScene::new(),
+        MovementEventSys::new(1.0),
+        Headless::new(24, 42),
+    )
+    .unwrap();
+    assert_eq!(game.canvas().size(), (21, 41));
+    assert_eq!(game.camera.hfov, 21.0 * 2.0 * CELL_ASPECT / 41.0 * PI);
+}
+
+#[test]
+fn update_draws_frame() {
+    let mut conf = Conf::default();
+    conf.initpt = Point::new(vec![0.0, 0.0, 1.0]);
+    let mut game = TestGame::with_term(
+        conf,
+        FloorScene::new(),
+        MovementEventSys::new(1.0),
+        Headless::new(8, 9),
+    )
+    .unwrap();
+    game.update().unwrap();
+
+    let frame = game.term().frame().unwrap();
+    assert_eq!(frame.len(), 8);
+    assert_eq!(frame[..3], vec![" ".repeat(9); 3]);
+    assert_eq!(frame[3..], game.canvas().lines()[..]);
+}
+
+#[test]
+fn run_until_events_exhausted() {
+    let mut game = TestGame::with_term(
+        Conf::default(),
+        FloorScene::new(),
+        MovementEventSys::new(1.0),
+        Headless::new(8, 9).script(vec![key(KeyCode::Char('w')), key(KeyCode::Char('w'))]),
+    )
+    .unwrap();
+    game.run().unwrap();
+    assert_eq!(game.term().frames().len(), 2);
+}
+
+#[test]
+fn half_block_camera_samples() {
+    let mut conf = Conf::default();
+    conf.render_mode = RenderMode::HalfBlock;
+    let game = TestGame::with_term(
+        conf,
+        FloorScene::new(),
+        MovementEventSys::new(1.0),
+        Headless::new(8, 9),
+    )
+    .unwrap();
+    assert_eq!(game.canvas().size(), (5, 9));
+    assert_eq!(game.camera().size, (10, 9));
+}
+
+#[test]
+fn resize_event_refits_canvas_and_camera() {
+    let mut game = TestGame::with_term(
+        Conf::default(),
+        FloorScene::new(),
+        MovementEventSys::new(1.0),
+        Headless::new(8, 9).script(vec![Event::Resize(42, 24)]),
+    )
+    .unwrap();
+    let wfov = game.camera().fov().0;
+    game.run().unwrap();
+    assert_eq!(game.canvas().size(), (21, 41));
+    assert_eq!(game.camera().size(), (21, 41));
+    assert_eq!(
+        game.camera().fov(),
+        (wfov, RenderMode::Chars.hfov(wfov, (21, 41)))
+    );
+    let frame = game.term().frame().unwrap();
+    assert_eq!(frame.len(), 24);
+    for (r, line) in game.canvas().lines().iter().enumerate() {
+        assert_eq!(&frame[r + 3][..41], line);
+    }
+}
+
+#[test]
+fn resize_keeps_given_vert_fov() {
+    let mut conf = Conf::default();
+    conf.hfov = Some(0.25);
+    let mut game = TestGame::with_term(
+        conf,
+        FloorScene::new(),
+        MovementEventSys::new(1.0),
+        Headless::new(8, 9),
+    )
+    .unwrap();
+    game.resize((24, 42)).unwrap();
+    assert_eq!(game.camera().fov().1, 0.25 * PI);
+}
+
+#[test]
+fn zoom_keys_change_fov() {
+    let mut conf = Conf::default();
+    conf.wfov = 0.5;
+    let mut game = TestGame::with_term(
+        conf,
+        FloorScene::new(),
+        MovementEventSys::new(1.0).with_zoom(2.0),
+        Headless::new(8, 9).script(vec![key(KeyCode::Char('='))]),
+    )
+    .unwrap();
+    let (wfov, hfov) = game.camera().fov();
+    game.run().unwrap();
+    assert_eq!(game.camera().fov(), (wfov / 2.0, hfov / 2.0));
+
+    game.term_mut().push_event(key(KeyCode::Char('-')));
+    game.run().unwrap();
+    assert_eq!(game.camera().fov(), (wfov, hfov));
 }
@@ -0,0 +1,190 @@
+use {
+    super::entity,
+    crate::{
+        engn::*,
+        errs::{GameErr::*, ReErr::*},
+        math::*,
+    },
+    std::{f64::consts::PI, rc::Rc},
+    uuid::Uuid,
+};
+
+fn ball(radius: f64) -> Box<dyn AsCollided> {
+    Box::new(
+        HypeEllipse::new(
+            entity(),
+            Point::new(vec![0.0; 3]),
+            Basis::default(),
+            vec![radius; 3],
+            Some(Charcoal::new("o".to_string(), 30.0)),
+        )
+        .unwrap(),
+    )
+}
+
+/// Door 2 wide, 0.2 thick and 3 high with hinge edge on the origin
+fn door() -> Box<dyn AsCollided> {
+    Box::new(
+        HypeBox::aligned(
+            entity(),
+            Point::new(vec![0.0, -0.1, 0.0]),
+            Point::new(vec![2.0, 0.1, 3.0]),
+            Some(Charcoal::new("#".to_string(), 30.0)),
+        )
+        .unwrap(),
+    )
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+/// NPC at (5, 0, 0) carrying lantern 1 ahead along x
+fn npc_with_lantern() -> (SceneGraph, Rc<Uuid>, Rc<Uuid>) {
+    let mut graph = SceneGraph::new();
+    let npc = graph
+        .add(
+            None,
+            SceneNode::new(entity(), Point::new(vec![5.0, 0.0, 0.0])).with_object(ball(0.5)),
+        )
+        .unwrap();
+    let lantern = graph
+        .add(
+            Some(&npc),
+            SceneNode::new(entity(), Point::new(vec![1.0, 0.0, 0.0])).with_object(ball(0.25)),
+        )
+        .unwrap();
+    (graph, npc, lantern)
+}
+
+#[test]
+fn child_follows_parent() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let (mut graph, npc, lantern) = npc_with_lantern();
+    assert_eq!(graph.len(), 2);
+    assert_eq!(graph.get(&lantern).unwrap().parent(), Some(&npc));
+    assert_eq!(graph.world_pos(&lantern).unwrap()[0], 6.0);
+
+    let inc = Point::new(vec![6.0, 0.0, 10.0]);
+    let down = Vector::new(vec![0.0, 0.0, -1.0]);
+    let hit = AsScene::collide(&graph, &cs, &inc, &down).unwrap();
+    assert_eq!(&hit.id, &lantern);
+    assert_close(hit.dist, 9.75);
+
+    graph.mv(&npc, &Vector::new(vec![0.0, 3.0, 0.0])).unwrap();
+    assert!(AsScene::collide(&graph, &cs, &inc, &down).is_none());
+    let inc = Point::new(vec![6.0, 3.0, 10.0]);
+    let hit = AsScene::collide(&graph, &cs, &inc, &down).unwrap();
+    assert_eq!(&hit.id, &lantern);
+    assert_eq!(graph.world_pos(&lantern).unwrap()[1], 3.0);
+}
+
+#[test]
+fn door_on_hinge() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut graph = SceneGraph::new();
+    let hinge = graph
+        .add(
+            None,
+            SceneNode::new(entity(), Point::new(vec![0.0, 5.0, 0.0])),
+        )
+        .unwrap();
+    let leaf = graph
+        .add(
+            Some(&hinge),
+            SceneNode::new(entity(), Point::new(vec![0.0; 3])).with_object(door()),
+        )
+        .unwrap();
+
+    let inc = Point::new(vec![1.0, 0.0, 1.0]);
+    let dir = Vector::new(vec![0.0, 1.0, 0.0]);
+    let hit = AsScene::collide(&graph, &cs, &inc, &dir).unwrap();
+    assert_eq!(&hit.id, &leaf);
+    assert_close(hit.dist, 4.9);
+    assert_close(hit.normal.unwrap().at(1), -1.0);
+
+    graph.planar_rotate(&hinge, 0, 1, PI / 2.0).unwrap();
+    assert!(AsScene::collide(&graph, &cs, &inc, &dir).is_none());
+    let inc = Point::new(vec![-5.0, 6.0, 1.0]);
+    let hit = AsScene::collide(&graph, &cs, &inc, &Vector::new(vec![1.0, 0.0, 0.0])).unwrap();
+    assert_close(hit.dist, 4.9);
+    assert_close(hit.normal.unwrap().at(0), -1.0);
+
+    let world = graph.world_dir(&leaf).unwrap();
+    assert_close(*world.att(1, 0), 1.0);
+}
+
+#[test]
+fn nested_rotations() {
+    let mut graph = SceneGraph::new();
+    let root = graph
+        .add(
+            None,
+            SceneNode::new(entity(), Point::new(vec![1.0, 0.0, 0.0])),
+        )
+        .unwrap();
+    let arm = graph
+        .add(
+            Some(&root),
+            SceneNode::new(entity(), Point::new(vec![2.0, 0.0, 0.0])),
+        )
+        .unwrap();
+    let hand = graph
+        .add(
+            Some(&arm),
+            SceneNode::new(entity(), Point::new(vec![1.0, 0.0, 0.0])),
+        )
+        .unwrap();
+
+    graph.planar_rotate(&root, 0, 1, PI / 2.0).unwrap();
+    graph.planar_rotate(&arm, 0, 1, PI / 2.0).unwrap();
+    let pos = graph.world_pos(&hand).unwrap();
+    assert_close(pos[0], 0.0);
+    assert_close(pos[1], 2.0);
+
+    graph.rotate_3d(&root, 0.0, 0.0, 0.0).unwrap();
+    *graph.get_mut(&arm).unwrap().pos_mut().at_mut(0) = 3.0;
+    let pos = graph.world_pos(&hand).unwrap();
+    assert_close(pos[1], 3.0);
+}
+
+#[test]
+fn attach_and_remove() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let (mut graph, npc, lantern) = npc_with_lantern();
+    let table = graph
+        .add(
+            None,
+            SceneNode::new(entity(), Point::new(vec![0.0, 10.0, 0.0])),
+        )
+        .unwrap();
+
+    assert!(matches!(
+        graph.attach(&npc, Some(&lantern)),
+        Err(GameErr(CyclicNode(_)))
+    ));
+    graph.attach(&lantern, Some(&table)).unwrap();
+    assert!(graph.get(&npc).unwrap().children().is_empty());
+    assert_eq!(graph.world_pos(&lantern).unwrap()[1], 10.0);
+
+    let unknown = Rc::new(Uuid::new_v4());
+    assert!(matches!(
+        graph.add(
+            Some(&unknown),
+            SceneNode::new(entity(), Point::new(vec![0.0; 3]))
+        ),
+        Err(GameErr(UnknownNode(_)))
+    ));
+
+    graph.remove(&table).unwrap();
+    assert_eq!(graph.len(), 1);
+    assert!(graph.get(&lantern).is_none());
+    let inc = Point::new(vec![1.0, 10.0, 0.0]);
+    let dir = Vector::new(vec![0.0, 0.0, -1.0]);
+    let hit = AsScene::collide(&graph, &cs, &Point::new(vec![5.0, 0.0, 5.0]), &dir).unwrap();
+    assert_eq!(&hit.id, &npc);
+    assert!(AsScene::collide(&graph, &cs, &inc, &dir).is_none());
+}
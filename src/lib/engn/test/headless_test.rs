@@ -0,0 +1,50 @@
+use {
+    super::super::*,
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+};
+
+#[test]
+fn headless_print_at_cursor() {
+    let mut term = Headless::new(2, 4);
+    term.move_cursor(1, 1).unwrap();
+    term.print("ab").unwrap();
+    assert_eq!(term.screen(), vec!["    ", " ab "]);
+}
+
+#[test]
+fn headless_print_out_of_screen() {
+    let mut term = Headless::new(2, 4);
+    term.move_cursor(0, 2).unwrap();
+    term.print("abcd").unwrap();
+    term.move_cursor(5, 0).unwrap();
+    term.print("abcd").unwrap();
+    assert_eq!(term.screen(), vec!["  ab", "    "]);
+}
+
+#[test]
+fn headless_frame_on_flush() {
+    let mut term = Headless::new(1, 2);
+    term.print("a").unwrap();
+    assert!(term.frame().is_none());
+    term.flush().unwrap();
+    term.clear().unwrap();
+    term.flush().unwrap();
+    assert_eq!(term.frames(), &vec![vec!["a "], vec!["  "]]);
+}
+
+#[test]
+fn headless_scripted_events() {
+    let event = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut term = Headless::new(1, 1).script(vec![event.clone()]);
+    assert_eq!(term.listen().unwrap(), Some(event));
+    assert_eq!(term.listen().unwrap(), None);
+}
+
+#[test]
+fn headless_resize_event() {
+    let mut term = Headless::new(2, 3).script(vec![Event::Resize(2, 4)]);
+    term.print("abc").unwrap();
+    assert_eq!(term.listen().unwrap(), Some(Event::Resize(2, 4)));
+    assert_eq!(term.size().unwrap(), (4, 2));
+    assert_eq!(term.screen(), vec!["ab", "  ", "  ", "  "]);
+}
@@ -0,0 +1,93 @@
+use {
+    super::FloorScene,
+    crate::{engn::*, math::*},
+    std::{f64::consts::PI, rc::Rc},
+    uuid::Uuid,
+};
+
+#[test]
+fn hit_point() {
+    set_biform_identity();
+    let hit = Hit::new(
+        &Point::new(vec![1.0, 0.0, 0.0]),
+        &Vector::new(vec![0.0, 2.0, 0.0]),
+        1.5,
+        Rc::new(Uuid::new_v4()),
+    );
+    assert_eq!(hit.pt, Point::new(vec![1.0, 3.0, 0.0]));
+    assert!(hit.normal.is_none() && hit.uv.is_none() && hit.charcoal.is_none());
+}
+
+#[test]
+fn hit_nearest() {
+    let (inc, dir) = (Point::default(), Vector::new(vec![1.0, 0.0, 0.0]));
+    let far = Hit::new(&inc, &dir, 5.0, Rc::new(Uuid::new_v4()));
+    let near = Hit::new(&inc, &dir, 2.0, Rc::new(Uuid::new_v4()));
+    let id = Rc::clone(&near.id);
+    let nearest = Hit::nearest([Some(far), None, Some(near)]).unwrap();
+    assert!(Rc::ptr_eq(&nearest.id, &id));
+    assert!(Hit::nearest([None, None]).is_none());
+}
+
+#[test]
+fn plane_hit_record() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let scene = FloorScene::new();
+    let hit = scene
+        .collide(
+            &cs,
+            &Point::new(vec![0.0, 0.0, 2.0]),
+            &Vector::new(vec![1.0, 0.0, -1.0]),
+        )
+        .unwrap();
+    assert_eq!(hit.dist, 2.0);
+    assert_eq!(hit.pt, Point::new(vec![2.0, 0.0, 0.0]));
+    assert_eq!(hit.normal, Some(Vector::new(vec![0.0, 0.0, 1.0])));
+    assert!(Rc::ptr_eq(&hit.id, scene.floor.id()));
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '#');
+}
+
+#[test]
+fn ellipse_hit_uv() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let ellipse = HypeEllipse::new(
+        Entity::new(Rc::new(Uuid::new_v4())),
+        Point::new(vec![3.0, 0.0, 0.0]),
+        Basis::default(),
+        vec![1.0, 1.0, 1.0],
+        None,
+    )
+    .unwrap();
+    let hit = ellipse
+        .hit(&cs, &Point::default(), &Vector::new(vec![1.0, 0.0, 0.0]))
+        .unwrap();
+    let (u, v) = hit.uv.unwrap();
+    assert!(aeq(&u, &1.0) || aeq(&u, &0.0));
+    assert!(aeq(&v, &0.5));
+    assert!(hit.charcoal.is_none());
+
+    let top = ellipse
+        .hit(
+            &cs,
+            &Point::new(vec![3.0, 0.0, 5.0]),
+            &Vector::new(vec![0.0, 0.0, -1.0]),
+        )
+        .unwrap();
+    assert!(aeq(&top.uv.unwrap().1, &0.0));
+    let side = ellipse.uv(&cs, &Point::new(vec![3.0, 1.0, 0.0])).unwrap();
+    assert!(aeq(&side.0, &(0.5 + 0.5 * PI / (2.0 * PI))));
+}
+
+#[test]
+fn scene_miss() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let scene = FloorScene::new();
+    let dir = Vector::new(vec![0.0, 0.0, 1.0]);
+    assert!(scene
+        .collide(&cs, &Point::new(vec![0.0, 0.0, 2.0]), &dir)
+        .is_none());
+    assert_eq!(scene.background(&cs, &dir), Some(Cell::from(' ')));
+}
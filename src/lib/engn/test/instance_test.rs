@@ -0,0 +1,162 @@
+use {
+    super::entity,
+    crate::{
+        engn::*,
+        errs::{MathErr::*, ReErr::*},
+        math::*,
+    },
+    std::{f64::consts::PI, rc::Rc},
+};
+
+fn ball() -> Rc<dyn AsCollided> {
+    Rc::new(
+        HypeEllipse::new(
+            entity(),
+            Point::new(vec![0.0; 3]),
+            Basis::default(),
+            vec![1.0; 3],
+            Some(Charcoal::new("o".to_string(), 30.0)),
+        )
+        .unwrap(),
+    )
+}
+
+/// Pillar 1x1x4 standing on the origin
+fn pillar() -> Rc<dyn AsCollided> {
+    Rc::new(
+        HypeBox::aligned(
+            entity(),
+            Point::new(vec![0.0; 3]),
+            Point::new(vec![1.0, 1.0, 4.0]),
+            Some(Charcoal::new("#".to_string(), 30.0)),
+        )
+        .unwrap(),
+    )
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+#[test]
+fn instances_share_prototype() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let prototype = ball();
+    let instances = (0..5)
+        .map(|i| {
+            Instance::new(
+                entity(),
+                Rc::clone(&prototype),
+                Point::new(vec![0.0, 3.0 * i as f64, 0.0]),
+            )
+        })
+        .collect::<Vec<Instance>>();
+    assert_eq!(Rc::strong_count(&prototype), 6);
+
+    let inc = Point::new(vec![-5.0, 6.0, 0.0]);
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let hits = instances
+        .iter()
+        .filter_map(|instance| instance.hit(&cs, &inc, &dir))
+        .collect::<Vec<Hit>>();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(&hits[0].id, instances[2].id());
+    assert_close(hits[0].dist, 4.0);
+    assert_close(hits[0].pt[1], 6.0);
+    assert_close(hits[0].normal.as_ref().unwrap().at(0), -1.0);
+    assert!(hits[0].charcoal.is_some());
+}
+
+#[test]
+fn scaled_instance() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let scale = Matrix::from_double(vec![
+        vec![2.0, 0.0, 0.0],
+        vec![0.0, 1.0, 0.0],
+        vec![0.0, 0.0, 1.0],
+    ]);
+    let instance = Instance::new(entity(), ball(), Point::new(vec![10.0, 0.0, 0.0]))
+        .with_transform(scale)
+        .unwrap();
+
+    let inc = Point::new(vec![0.0, 0.0, 0.0]);
+    let hit = instance
+        .hit(&cs, &inc, &Vector::new(vec![1.0, 0.0, 0.0]))
+        .unwrap();
+    assert_close(hit.dist, 8.0);
+
+    let dir = Vector::new(vec![9.0, 0.0, 3.0_f64.sqrt() / 2.0]);
+    let hit = instance.hit(&cs, &inc, &dir).unwrap();
+    assert_close(hit.dist, 1.0);
+    let normal = hit.normal.unwrap();
+    let expected = Vector::new(vec![-0.25, 0.0, 3.0_f64.sqrt() / 2.0]);
+    let len = expected.at(0).hypot(expected.at(2));
+    assert_close(normal.at(0), expected.at(0) / len);
+    assert_close(normal.at(2), expected.at(2) / len);
+
+    let bounds = instance.bounds().unwrap();
+    assert_eq!(bounds.min(), &vec![8.0, -1.0, -1.0]);
+    assert_eq!(bounds.max(), &vec![12.0, 1.0, 1.0]);
+}
+
+#[test]
+fn rotated_instance() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut instance = Instance::new(entity(), pillar(), Point::new(vec![5.0, 0.0, 0.0]));
+    instance.planar_rotate(0, 1, PI / 2.0).unwrap();
+
+    let bounds = instance.bounds().unwrap();
+    assert_close(bounds.min()[0], 4.0);
+    assert_close(bounds.max()[0], 5.0);
+    assert_close(bounds.max()[1], 1.0);
+
+    let inc = Point::new(vec![4.5, -5.0, 2.0]);
+    let hit = instance
+        .hit(&cs, &inc, &Vector::new(vec![0.0, 1.0, 0.0]))
+        .unwrap();
+    assert_close(hit.dist, 5.0);
+    assert_close(hit.normal.unwrap().at(1), -1.0);
+
+    instance.mv(&Vector::new(vec![0.0, 0.0, 10.0])).unwrap();
+    let dir = Vector::new(vec![0.0, 1.0, 0.0]);
+    assert!(AsCollided::collide(&instance, &cs, &inc, &dir).is_none());
+}
+
+#[test]
+fn instance_charcoal() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let instance = Instance::new(entity(), ball(), Point::new(vec![0.0; 3]))
+        .with_charcoal(Charcoal::new("*".to_string(), 30.0));
+    assert_eq!(instance.charmap(1.0), Some('*'));
+    let hit = instance
+        .hit(
+            &cs,
+            &Point::new(vec![-3.0, 0.0, 0.0]),
+            &Vector::new(vec![1.0, 0.0, 0.0]),
+        )
+        .unwrap();
+    assert_eq!(hit.charcoal.unwrap().ignite(1.0), '*');
+}
+
+#[test]
+fn invalid_transform() {
+    let flat = Matrix::from_double(vec![
+        vec![1.0, 0.0, 0.0],
+        vec![0.0, 1.0, 0.0],
+        vec![0.0, 0.0, 0.0],
+    ]);
+    let instance = Instance::new(entity(), ball(), Point::new(vec![0.0; 3]));
+    assert!(matches!(
+        instance.with_transform(flat),
+        Err(MathErr(NullDeterminant))
+    ));
+    let instance = Instance::new(entity(), ball(), Point::new(vec![0.0; 3]));
+    assert!(matches!(
+        instance.with_transform(Matrix::identity(2)),
+        Err(MathErr(DimMismatch { lhs: 2, rhs: 3 }))
+    ));
+}
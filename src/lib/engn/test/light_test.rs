@@ -0,0 +1,125 @@
+use crate::{engn::*, math::*};
+
+fn floor_normal() -> Vector {
+    Vector::new(vec![0.0, 0.0, 1.0])
+}
+
+#[test]
+fn directional_light_head_on() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let light = Light::directional(Vector::new(vec![0.0, 0.0, -2.0]), 0.5);
+    assert_eq!(light.lambert(&cs, &Point::default(), &floor_normal()), 0.5);
+}
+
+#[test]
+fn directional_light_from_behind() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let light = Light::directional(Vector::new(vec![0.0, 0.0, 1.0]), 1.0);
+    assert_eq!(light.lambert(&cs, &Point::default(), &floor_normal()), 0.0);
+}
+
+#[test]
+fn point_light_angled() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let light = Light::point(Point::new(vec![1.0, 0.0, 1.0]), 1.0);
+    let lambert = light.lambert(&cs, &Point::default(), &floor_normal());
+    assert!(aeq(&lambert, &(0.5_f64.sqrt())));
+}
+
+#[test]
+fn point_light_on_surface() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let light = Light::point(Point::default(), 1.0);
+    assert_eq!(light.lambert(&cs, &Point::default(), &floor_normal()), 0.0);
+}
+
+#[test]
+fn brightness_edge_on_is_darker() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let lighting = Lighting::new(0.0, 100.0)
+        .with_light(Light::directional(Vector::new(vec![-1.0, 0.0, 0.0]), 1.0));
+    let normal = Vector::new(vec![1.0, 0.0, 0.0]);
+    let head_on = lighting.brightness(
+        &cs,
+        &Point::default(),
+        &normal,
+        &Vector::new(vec![-1.0, 0.0, 0.0]),
+        0.0,
+    );
+    let oblique = lighting.brightness(
+        &cs,
+        &Point::default(),
+        &Vector::new(vec![1.0, 1.0, 0.0]).normalize(),
+        &Vector::new(vec![-1.0, -1.0, 0.0]),
+        0.0,
+    );
+    assert_eq!(head_on, 1.0);
+    assert!(oblique < head_on);
+}
+
+#[test]
+fn brightness_normal_turned_to_viewer() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let lighting = Lighting::new(0.0, 100.0)
+        .with_light(Light::directional(Vector::new(vec![0.0, 0.0, -1.0]), 1.0));
+    let brightness = lighting.brightness(
+        &cs,
+        &Point::default(),
+        &Vector::new(vec![0.0, 0.0, -1.0]),
+        &Vector::new(vec![0.0, 0.0, -1.0]),
+        0.0,
+    );
+    assert_eq!(brightness, 1.0);
+}
+
+#[test]
+fn brightness_fog() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let lighting = Lighting::new(1.0, 10.0);
+    let dir = Vector::new(vec![0.0, 0.0, -1.0]);
+    assert_eq!(
+        lighting.brightness(&cs, &Point::default(), &floor_normal(), &dir, 5.0),
+        0.5
+    );
+    assert_eq!(
+        lighting.brightness(&cs, &Point::default(), &floor_normal(), &dir, 20.0),
+        0.0
+    );
+}
+
+#[test]
+fn brightness_clamped() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let lighting = Lighting::new(0.5, 100.0)
+        .with_light(Light::directional(Vector::new(vec![0.0, 0.0, -1.0]), 1.0));
+    let dir = Vector::new(vec![0.0, 0.0, -1.0]);
+    assert_eq!(
+        lighting.brightness(&cs, &Point::default(), &floor_normal(), &dir, 0.0),
+        1.0
+    );
+}
+
+#[test]
+fn charcoal_shade() {
+    let charcoal = Charcoal::new("@#.".to_string(), 10.0).with_fg(Rgb(200, 100, 0));
+    assert_eq!(
+        charcoal.shade(1.0),
+        Cell::new('@', Some(Rgb(200, 100, 0)), None)
+    );
+    assert_eq!(
+        charcoal.shade(0.5),
+        Cell::new('#', Some(Rgb(100, 50, 0)), None)
+    );
+    assert_eq!(
+        charcoal.shade(0.0),
+        Cell::new('.', Some(Rgb(0, 0, 0)), None)
+    );
+}
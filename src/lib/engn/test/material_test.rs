@@ -1,8 +1,17 @@
 use std::cell::RefCell;
 use {
     super::super::*,
-    crate::{conf::*, engn::*, math::*},
-    std::{any::Any, rc::Rc},
+    crate::{
+        conf::*,
+        engn::*,
+        errs::{GameErr::*, ReErr::*},
+        math::*,
+    },
+    std::{
+        any::Any,
+        f64::consts::{FRAC_PI_2, PI},
+        rc::Rc,
+    },
 };
 
 #[test]
@@ -51,6 +60,7 @@ fn entity_list_get() {
             Entity::new(id.clone()),
             Point::new(vec![1.0, 1.0, 1.0]),
             Vector::new(vec![1.0, 1.0, 1.0]),
+            None,
         )
         .unwrap(),
     )));
@@ -60,7 +70,7 @@ fn entity_list_get() {
             &Point::new(vec![2.0, 2.0, 2.0]),
             &Vector::new(vec![-1.0, -1.0, -1.0])
         ),
-        1.0
+        Some(1.0)
     );
 }
 
@@ -71,7 +81,7 @@ fn hype_plane_pos() {
 
     let pos = Point::new(vec![1.0, 1.0, 1.0]);
     let dir = Vector::new(vec![1.0, 1.0, 1.0]);
-    let mut hype = HypePlane::new(core, pos, dir).unwrap();
+    let mut hype = HypePlane::new(core, pos, dir, None).unwrap();
     let pos = Point::new(vec![1.0, 1.0, 1.0]);
     assert_eq!(hype.initpt, pos);
 }
@@ -83,7 +93,7 @@ fn hype_plane_mv_pos() {
 
     let pos = Point::new(vec![1.0, 1.0, 1.0]);
     let dir = Vector::new(vec![1.0, 1.0, 1.0]);
-    let mut hype = HypePlane::new(core, pos, dir).unwrap();
+    let mut hype = HypePlane::new(core, pos, dir, None).unwrap();
     let mv = Vector::new(vec![2.0, 2.0, 2.0]);
     hype.mv(&mv).unwrap();
 
@@ -99,10 +109,11 @@ fn plane_straight_collision() {
         Entity::new(id_pool.generate()),
         Point::new(vec![3.0, 0.0, 0.0]),
         Vector::new(vec![1.0, 0.0, 0.0]),
+        None,
     )
     .unwrap();
     let dist = plane.collide(&cs, &Point::default(), &Vector::new(vec![1.0, 0.0, 0.0]));
-    assert_eq!(dist, 3.0);
+    assert_eq!(dist, Some(3.0));
 }
 
 #[test]
@@ -114,10 +125,11 @@ fn curve_plane_straight_collision() {
         Entity::new(id_pool.generate()),
         Point::new(vec![3.0, 0.0, 0.0]),
         Vector::new(vec![1.0, 1.0, 0.0]),
+        None,
     )
     .unwrap();
     let dist = plane.collide(&cs, &Point::default(), &Vector::new(vec![1.0, 0.0, 0.0]));
-    assert_eq!(dist, 3.0);
+    assert_eq!(dist, Some(3.0));
 }
 
 #[test]
@@ -128,10 +140,11 @@ fn straight_plane_curve_collision() {
         Entity::new(id_pool.generate()),
         Point::new(vec![3.0, 0.0, 0.0]),
         Vector::new(vec![1.0, 0.0, 0.0]),
+        None,
     )
     .unwrap();
     let dist = plane.collide(&cs, &Point::default(), &Vector::new(vec![1.0, 1.0, 0.0]));
-    assert_eq!(dist, 3.0);
+    assert_eq!(dist, Some(3.0));
 }
 
 #[test]
@@ -142,6 +155,7 @@ fn horizontal_plane_curve_collision() {
         Entity::new(id_pool.generate()),
         Point::default(),
         Vector::new(vec![0.0, 0.0, 1.0]),
+        None,
     )
     .unwrap();
     let dist = plane.collide(
@@ -149,7 +163,7 @@ fn horizontal_plane_curve_collision() {
         &Point::new(vec![0.0, 0.0, 1.0]),
         &Vector::new(vec![3.0, -1.0, -2.0]),
     );
-    assert_eq!(dist, 0.5);
+    assert_eq!(dist, Some(0.5));
 }
 
 #[test]
@@ -160,6 +174,7 @@ fn horizontal_plane_no_collision() {
         Entity::new(id_pool.generate()),
         Point::default(),
         Vector::new(vec![0.0, 0.0, 1.0]),
+        None,
     )
     .unwrap();
     let dist = plane.collide(
@@ -167,7 +182,7 @@ fn horizontal_plane_no_collision() {
         &Point::new(vec![0.0, 0.0, 1.0]),
         &Vector::new(vec![3.0, -1.0, 2.0]),
     );
-    assert_eq!(dist, -1.0);
+    assert_eq!(dist, None);
 }
 
 #[test]
@@ -180,10 +195,11 @@ fn hype_ellipse_sphere_collision() {
         Point::new(vec![3.0, 0.0, 0.0]),
         Basis::default(),
         vec![1.0, 2.0, 3.0],
+        None,
     )
     .unwrap();
     let dist = ellipse.collide(&cs, &Point::default(), &Vector::new(vec![1.0, 0.0, 0.0]));
-    assert_eq!(dist, 2.0);
+    assert_eq!(dist, Some(2.0));
 }
 
 #[test]
@@ -196,10 +212,11 @@ fn hype_ellipse_sphere_inception_collision() {
         Point::new(vec![3.0, 0.0, 0.0]),
         Basis::default(),
         vec![3.0, 3.0, 3.0],
+        None,
     )
     .unwrap();
     let dist = ellipse.collide(&cs, &Point::default(), &Vector::new(vec![0.0, 1.0, 0.0]));
-    assert_eq!(dist, 0.0);
+    assert_eq!(dist, Some(0.0));
 }
 
 #[test]
@@ -212,10 +229,11 @@ fn hype_ellipse_sphere_no_collision() {
         Point::new(vec![3.0, 0.0, 0.0]),
         Basis::default(),
         vec![2.0, 2.0, 2.0],
+        None,
     )
     .unwrap();
     let dist = ellipse.collide(&cs, &Point::default(), &Vector::new(vec![1.0, 1.0, 0.0]));
-    assert_eq!(dist, -1.0);
+    assert_eq!(dist, None);
 }
 
 #[test]
@@ -228,10 +246,11 @@ fn hype_ellipse_hot_dog_collision() {
         Point::new(vec![3.0, 0.0, 0.0]),
         Basis::default(),
         vec![4.0, 1.0, 1.0],
+        None,
     )
     .unwrap();
     let dist = ellipse.collide(&cs, &Point::default(), &Vector::new(vec![1.0, 0.0, 0.0]));
-    assert_eq!(dist, 7.0);
+    assert_eq!(dist, Some(7.0));
 }
 
 #[test]
@@ -244,6 +263,7 @@ fn hype_ellipse_hot_dog_curve_collision() {
         Point::new(vec![3.0, 0.0, 0.0]),
         Basis::default(),
         vec![4.0, 1.0, 1.0],
+        None,
     )
     .unwrap();
     let dist = ellipse.collide(
@@ -251,7 +271,7 @@ fn hype_ellipse_hot_dog_curve_collision() {
         &Point::new(vec![3.0, 2.0, 2.0]),
         &Vector::new(vec![0.0, -1.0, -1.0]),
     );
-    assert_eq!(dist, (8.0_f64.sqrt() - 1.0) / 2.0_f64.sqrt());
+    assert_eq!(dist, Some((8.0_f64.sqrt() - 1.0) / 2.0_f64.sqrt()));
 }
 
 #[test]
@@ -264,6 +284,7 @@ fn hype_ellipse_hot_dog_angled_collision() {
         Point::new(vec![3.0, 0.0, 0.0]),
         Basis::default(),
         vec![4.0, 1.0, 1.0],
+        None,
     )
     .unwrap();
     let dist = ellipse.collide(
@@ -271,7 +292,7 @@ fn hype_ellipse_hot_dog_angled_collision() {
         &Point::new(vec![3.0, 1.0, 2.0]),
         &Vector::new(vec![0.0, -1.0, -1.0]),
     );
-    assert_eq!(dist, 1.0);
+    assert_eq!(dist, Some(1.0));
 }
 
 #[test]
@@ -284,6 +305,7 @@ fn hype_ellipse_hot_dog_no_collision() {
         Point::new(vec![3.0, 0.0, 0.0]),
         Basis::default(),
         vec![4.0, 1.0, 1.0],
+        None,
     )
     .unwrap();
     let dist = ellipse.collide(
@@ -291,5 +313,768 @@ fn hype_ellipse_hot_dog_no_collision() {
         &Point::new(vec![3.0, 1.0, 2.0]),
         &Vector::new(vec![0.0, -1.0, 1.0]),
     );
-    assert_eq!(dist, -1.0);
+    assert_eq!(dist, None);
+}
+
+#[test]
+fn hype_plane_normal() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut id_pool = IdPool::new();
+    let plane = HypePlane::new(
+        Entity::new(id_pool.generate()),
+        Point::new(vec![3.0, 0.0, 0.0]),
+        Vector::new(vec![2.0, 0.0, 0.0]),
+        None,
+    )
+    .unwrap();
+    let normal = plane.normal(&cs, &Point::new(vec![3.0, 5.0, 1.0]));
+    assert_eq!(normal, Some(Vector::new(vec![1.0, 0.0, 0.0])));
+}
+
+#[test]
+fn hype_ellipse_sphere_normal() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut id_pool = IdPool::new();
+    let ellipse = HypeEllipse::new(
+        Entity::new(id_pool.generate()),
+        Point::new(vec![3.0, 0.0, 0.0]),
+        Basis::default(),
+        vec![2.0, 2.0, 2.0],
+        None,
+    )
+    .unwrap();
+    let normal = ellipse.normal(&cs, &Point::new(vec![1.0, 0.0, 0.0]));
+    assert_eq!(normal, Some(Vector::new(vec![-1.0, 0.0, 0.0])));
+}
+
+#[test]
+fn hype_ellipse_rotated_hot_dog_normal() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut id_pool = IdPool::new();
+    let basis = Matrix::from_double(vec![
+        vec![0.0, -1.0, 0.0],
+        vec![1.0, 0.0, 0.0],
+        vec![0.0, 0.0, 1.0],
+    ])
+    .to_multicol();
+    let ellipse = HypeEllipse::new(
+        Entity::new(id_pool.generate()),
+        Point::new(vec![3.0, 0.0, 0.0]),
+        Basis::new(basis).unwrap(),
+        vec![4.0, 1.0, 1.0],
+        None,
+    )
+    .unwrap();
+    let dist = ellipse.collide(
+        &cs,
+        &Point::new(vec![3.0, 10.0, 0.0]),
+        &Vector::new(vec![0.0, -1.0, 0.0]),
+    );
+    assert_eq!(dist, Some(6.0));
+    let tip = ellipse.normal(&cs, &Point::new(vec![3.0, 4.0, 0.0]));
+    assert_eq!(tip, Some(Vector::new(vec![0.0, 1.0, 0.0])));
+    let side = ellipse.normal(&cs, &Point::new(vec![2.0, 0.0, 0.0]));
+    assert_eq!(side, Some(Vector::new(vec![-1.0, 0.0, 0.0])));
+}
+
+fn unit_box(center: Vec<f64>, basis: Basis) -> HypeBox {
+    HypeBox::new(
+        Entity::new(IdPool::new().generate()),
+        Point::new(center),
+        basis,
+        vec![1.0, 2.0, 3.0],
+        None,
+    )
+    .unwrap()
+}
+
+fn rotated_basis() -> Basis {
+    Basis::new(
+        Matrix::from_double(vec![
+            vec![0.0, -1.0, 0.0],
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ])
+        .to_multicol(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn hype_box_dim_mismatch() {
+    let hype = HypeBox::new(
+        Entity::new(IdPool::new().generate()),
+        Point::default(),
+        Basis::default(),
+        vec![1.0, 1.0],
+        None,
+    );
+    assert!(hype.is_err());
+}
+
+#[test]
+fn hype_box_straight_collision() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let hype = unit_box(vec![5.0, 0.0, 0.0], Basis::default());
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    assert_eq!(hype.collide(&cs, &Point::default(), &dir), Some(4.0));
+    let hit = hype.hit(&cs, &Point::default(), &dir).unwrap();
+    assert_eq!(hit.normal, Some(Vector::new(vec![-1.0, 0.0, 0.0])));
+    assert_eq!(hit.uv, Some((0.5, 0.5)));
+}
+
+#[test]
+fn hype_box_curve_collision() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let hype = unit_box(vec![5.0, 0.0, 0.0], Basis::default());
+    let inc = Point::new(vec![5.0, 5.0, 4.0]);
+    let dir = Vector::new(vec![0.0, -1.0, -1.0]);
+    let hit = hype.hit(&cs, &inc, &dir).unwrap();
+    assert_eq!(hit.dist, 3.0);
+    assert_eq!(hit.normal, Some(Vector::new(vec![0.0, 1.0, 0.0])));
+}
+
+#[test]
+fn hype_box_no_collision() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let hype = unit_box(vec![5.0, 0.0, 0.0], Basis::default());
+    let inc = Point::default();
+    assert_eq!(
+        hype.collide(&cs, &inc, &Vector::new(vec![1.0, 1.0, 0.0])),
+        None
+    );
+    assert_eq!(
+        hype.collide(&cs, &inc, &Vector::new(vec![-1.0, 0.0, 0.0])),
+        None
+    );
+    assert_eq!(
+        hype.collide(
+            &cs,
+            &Point::new(vec![0.0, 5.0, 0.0]),
+            &Vector::new(vec![1.0, 0.0, 0.0])
+        ),
+        None
+    );
+}
+
+#[test]
+fn hype_box_inception_collision() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let hype = unit_box(vec![5.0, 0.0, 0.0], Basis::default());
+    let dir = Vector::new(vec![0.0, 0.0, 1.0]);
+    let hit = hype
+        .hit(&cs, &Point::new(vec![5.0, 0.0, 0.0]), &dir)
+        .unwrap();
+    assert_eq!(hit.dist, 3.0);
+    assert_eq!(hit.normal, Some(Vector::new(vec![0.0, 0.0, 1.0])));
+}
+
+#[test]
+fn hype_box_rotated_collision() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let hype = unit_box(vec![5.0, 0.0, 0.0], rotated_basis());
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let hit = hype.hit(&cs, &Point::default(), &dir).unwrap();
+    assert_eq!(hit.dist, 3.0);
+    assert_eq!(hit.normal, Some(Vector::new(vec![-1.0, 0.0, 0.0])));
+    assert_eq!(
+        hype.bounds(),
+        Some(Aabb::new(vec![3.0, -1.0, -3.0], vec![7.0, 1.0, 3.0]))
+    );
+}
+
+#[test]
+fn hype_box_aligned() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let hype = HypeBox::aligned(
+        Entity::new(IdPool::new().generate()),
+        Point::new(vec![2.0, -1.0, -1.0]),
+        Point::new(vec![4.0, 1.0, 1.0]),
+        Some(Charcoal::new("#".to_string(), 10.0)),
+    )
+    .unwrap();
+    let hit = hype
+        .hit(&cs, &Point::default(), &Vector::new(vec![1.0, 0.0, 0.0]))
+        .unwrap();
+    assert_eq!(hit.dist, 2.0);
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '#');
+}
+
+#[test]
+fn hype_box_mv() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut hype = unit_box(vec![5.0, 0.0, 0.0], Basis::default());
+    hype.mv(&Vector::new(vec![2.0, 0.0, 0.0])).unwrap();
+    assert_eq!(
+        hype.collide(&cs, &Point::default(), &Vector::new(vec![1.0, 0.0, 0.0])),
+        Some(6.0)
+    );
+}
+
+#[test]
+fn hype_box_rotate_then_collide() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let mut hype = unit_box(vec![5.0, 0.0, 0.0], Basis::default());
+    assert_eq!(hype.collide(&cs, &Point::default(), &dir), Some(4.0));
+    hype.planar_rotate(0, 1, FRAC_PI_2).unwrap();
+    assert!(aeq(
+        &hype.collide(&cs, &Point::default(), &dir).unwrap(),
+        &3.0
+    ));
+    let hit = hype.hit(&cs, &Point::default(), &dir).unwrap();
+    let normal = hit.normal.unwrap();
+    assert!(aeq(&normal.at(0), &-1.0) && aeq(&normal.at(1), &0.0));
+
+    let mut hype = unit_box(vec![5.0, 0.0, 0.0], Basis::default());
+    hype.rotate_3d(0.0, 0.0, FRAC_PI_2).unwrap();
+    assert!(aeq(
+        &hype.collide(&cs, &Point::default(), &dir).unwrap(),
+        &3.0
+    ));
+
+    let mut hype = unit_box(vec![5.0, 0.0, 0.0], Basis::default());
+    let rotated = Matrix::rotation(0, 1, FRAC_PI_2, 3).mul(hype.dir());
+    *hype.dir_mut() = rotated.to_multicol();
+    let hit = hype.hit(&cs, &Point::default(), &dir).unwrap();
+    assert!(aeq(&hit.dist, &3.0));
+    let normal = hit.normal.unwrap();
+    assert!(aeq(&normal.at(0), &-1.0) && aeq(&normal.at(1), &0.0));
+    *hype.dir_mut() = Matrix::zero(3, 3).to_multicol();
+    assert!(hype.collide(&cs, &Point::default(), &dir).is_none());
+}
+
+/// Square doorway of side 2 in the plane `x = 3` centered at the x axis
+fn doorway() -> HypePolygon {
+    HypePolygon::new(
+        Entity::new(IdPool::new().generate()),
+        Point::new(vec![3.0, 0.0, 0.0]),
+        Vector::new(vec![-1.0, 0.0, 0.0]),
+        vec![
+            Point::new(vec![3.0, -1.0, -1.0]),
+            Point::new(vec![3.0, 1.0, -1.0]),
+            Point::new(vec![3.0, 1.0, 1.0]),
+            Point::new(vec![3.0, -1.0, 1.0]),
+        ],
+        Some(Charcoal::new("#".to_string(), 10.0)),
+    )
+    .unwrap()
+}
+
+#[test]
+fn hype_polygon_collision() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let door = doorway();
+    let inc = Point::default();
+    assert_eq!(
+        door.collide(&cs, &inc, &Vector::new(vec![1.0, 0.0, 0.0])),
+        Some(3.0)
+    );
+    assert!(door
+        .collide(&cs, &inc, &Vector::new(vec![3.0, 1.0, 1.0]))
+        .is_some());
+    assert!(door
+        .collide(&cs, &inc, &Vector::new(vec![3.0, 1.5, 0.0]))
+        .is_none());
+    assert!(door
+        .collide(&cs, &inc, &Vector::new(vec![-1.0, 0.0, 0.0]))
+        .is_none());
+    let hit = door
+        .hit(&cs, &inc, &Vector::new(vec![1.0, 0.25, 0.0]))
+        .unwrap();
+    assert_eq!(hit.normal, Some(Vector::new(vec![-1.0, 0.0, 0.0])));
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '#');
+}
+
+#[test]
+fn hype_polygon_errors() {
+    let entity = || Entity::new(IdPool::new().generate());
+    let off = HypePolygon::new(
+        entity(),
+        Point::default(),
+        Vector::new(vec![0.0, 0.0, 1.0]),
+        vec![
+            Point::new(vec![1.0, 0.0, 0.0]),
+            Point::new(vec![0.0, 1.0, 0.0]),
+            Point::new(vec![0.0, 0.0, 1.0]),
+        ],
+        None,
+    );
+    assert_eq!(off.unwrap_err(), GameErr(VertexOffPlane(2)));
+    let arrow = HypePolygon::from_vertices(
+        entity(),
+        vec![
+            Point::new(vec![0.0, 0.0, 0.0]),
+            Point::new(vec![2.0, 0.0, 0.0]),
+            Point::new(vec![2.0, 2.0, 0.0]),
+            Point::new(vec![1.0, 0.5, 0.0]),
+            Point::new(vec![0.0, 2.0, 0.0]),
+        ],
+        None,
+    );
+    assert_eq!(arrow.unwrap_err(), GameErr(NonConvexPolygon));
+}
+
+#[test]
+fn hype_polygon_from_vertices_bounds() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let floor = HypePolygon::from_vertices(
+        Entity::new(IdPool::new().generate()),
+        vec![
+            Point::new(vec![0.0, 0.0, 0.0]),
+            Point::new(vec![4.0, 0.0, 0.0]),
+            Point::new(vec![0.0, 4.0, 0.0]),
+        ],
+        None,
+    )
+    .unwrap();
+    let down = Vector::new(vec![0.0, 0.0, -1.0]);
+    assert_eq!(
+        floor.collide(&cs, &Point::new(vec![1.0, 1.0, 2.0]), &down),
+        Some(2.0)
+    );
+    assert!(floor
+        .collide(&cs, &Point::new(vec![3.0, 3.0, 2.0]), &down)
+        .is_none());
+    let bounds = floor.bounds().unwrap();
+    assert_eq!(bounds.min(), &vec![0.0, 0.0, 0.0]);
+    assert_eq!(bounds.max(), &vec![4.0, 4.0, 0.0]);
+}
+
+#[test]
+fn hype_polygon_mv_rotate() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut door = doorway();
+    door.mv(&Vector::new(vec![0.0, 5.0, 0.0])).unwrap();
+    let inc = Point::default();
+    assert!(door
+        .collide(&cs, &inc, &Vector::new(vec![1.0, 0.0, 0.0]))
+        .is_none());
+    assert_eq!(
+        door.collide(&cs, &inc, &Vector::new(vec![3.0, 5.0, 0.0])),
+        Some(1.0)
+    );
+    door.planar_rotate(0, 1, PI / 2.0).unwrap();
+    let vertices = door.vertices();
+    assert!(aeq(&vertices[0][0], &4.0) && aeq(&vertices[0][1], &5.0));
+    assert_eq!(
+        door.collide(&cs, &inc, &Vector::new(vec![3.0, 5.0, 0.0])),
+        Some(1.0)
+    );
+    assert!(door
+        .collide(&cs, &inc, &Vector::new(vec![4.5, 5.0, 0.0]))
+        .is_none());
+}
+
+#[test]
+fn hype_polygon_disc_rotate_3d() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let down = Vector::new(vec![0.0, 0.0, -1.0]);
+    let mut door = doorway();
+    door.rotate_3d(0.0, FRAC_PI_2, 0.0).unwrap();
+    assert_eq!(
+        door.collide(&cs, &Point::new(vec![3.5, 0.5, 5.0]), &down),
+        Some(5.0)
+    );
+    assert!(door
+        .collide(&cs, &Point::new(vec![4.5, 0.0, 5.0]), &down)
+        .is_none());
+
+    let mut disc = HypeDisc::new(
+        Entity::new(IdPool::new().generate()),
+        Point::new(vec![0.0, 0.0, 0.0]),
+        Vector::new(vec![0.0, 0.0, 2.0]),
+        2.0,
+        None,
+    )
+    .unwrap();
+    disc.rotate_3d(0.0, FRAC_PI_2, 0.0).unwrap();
+    assert_eq!(
+        disc.collide(
+            &cs,
+            &Point::new(vec![3.0, 1.0, 1.0]),
+            &Vector::new(vec![-1.0, 0.0, 0.0])
+        ),
+        Some(3.0)
+    );
+    assert!(disc
+        .collide(&cs, &Point::new(vec![0.0, 1.0, 3.0]), &down)
+        .is_none());
+}
+
+#[test]
+fn hype_disc_collision() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let disc = HypeDisc::new(
+        Entity::new(IdPool::new().generate()),
+        Point::new(vec![0.0, 0.0, 0.0]),
+        Vector::new(vec![0.0, 0.0, 2.0]),
+        2.0,
+        None,
+    )
+    .unwrap();
+    let down = Vector::new(vec![0.0, 0.0, -1.0]);
+    assert_eq!(
+        disc.collide(&cs, &Point::new(vec![1.0, 1.0, 3.0]), &down),
+        Some(3.0)
+    );
+    assert_eq!(
+        disc.collide(&cs, &Point::new(vec![2.0, 0.0, 3.0]), &down),
+        Some(3.0)
+    );
+    assert!(disc
+        .collide(&cs, &Point::new(vec![1.5, 1.5, 3.0]), &down)
+        .is_none());
+    let hit = disc
+        .hit(&cs, &Point::new(vec![1.0, 0.0, 3.0]), &down)
+        .unwrap();
+    assert_eq!(hit.normal, Some(Vector::new(vec![0.0, 0.0, 1.0])));
+    assert!(aeq(&hit.uv.unwrap().1, &0.5));
+    let bounds = disc.bounds().unwrap();
+    assert_eq!(bounds.min(), &vec![-2.0, -2.0, 0.0]);
+    assert_eq!(bounds.max(), &vec![2.0, 2.0, 0.0]);
+}
+
+#[test]
+fn hype_disc_non_positive_radius() {
+    let disc = HypeDisc::new(
+        Entity::new(IdPool::new().generate()),
+        Point::new(vec![0.0, 0.0, 0.0]),
+        Vector::new(vec![0.0, 0.0, 1.0]),
+        0.0,
+        None,
+    );
+    assert_eq!(
+        disc.unwrap_err(),
+        GameErr(NonPositiveSize {
+            key: "radius",
+            val: 0.0
+        })
+    );
+}
+
+#[test]
+fn hype_cylinder_collision() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let pillar = HypeCylinder::new(
+        Entity::new(IdPool::new().generate()),
+        Point::new(vec![5.0, 0.0, 0.0]),
+        Basis::default(),
+        1.0,
+        4.0,
+        None,
+    )
+    .unwrap();
+    let inc = Point::new(vec![0.0, 0.0, 2.0]);
+    let hit = pillar
+        .hit(&cs, &inc, &Vector::new(vec![1.0, 0.0, 0.0]))
+        .unwrap();
+    assert!(aeq(&hit.dist, &4.0));
+    assert_eq!(hit.normal, Some(Vector::new(vec![-1.0, 0.0, 0.0])));
+    assert!(aeq(&hit.uv.unwrap().0, &0.5) && aeq(&hit.uv.unwrap().1, &0.5));
+    assert!(pillar
+        .collide(&cs, &inc, &Vector::new(vec![1.0, 0.0, 1.0]))
+        .is_none());
+
+    let hit = pillar
+        .hit(
+            &cs,
+            &Point::new(vec![5.5, 0.0, 10.0]),
+            &Vector::new(vec![0.0, 0.0, -1.0]),
+        )
+        .unwrap();
+    assert!(aeq(&hit.dist, &6.0));
+    assert_eq!(hit.normal, Some(Vector::new(vec![0.0, 0.0, 1.0])));
+
+    let inside = pillar.collide(
+        &cs,
+        &Point::new(vec![5.0, 0.0, 1.0]),
+        &Vector::new(vec![0.0, 1.0, 0.0]),
+    );
+    assert!(aeq(&inside.unwrap(), &1.0));
+    let bounds = pillar.bounds().unwrap();
+    assert_eq!(bounds.min(), &vec![4.0, -1.0, 0.0]);
+    assert_eq!(bounds.max(), &vec![6.0, 1.0, 4.0]);
+}
+
+#[test]
+fn hype_cylinder_rotated() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let basis = Basis::new(
+        Matrix::from_double(vec![
+            vec![0.0, 0.0, 1.0],
+            vec![0.0, 1.0, 0.0],
+            vec![-1.0, 0.0, 0.0],
+        ])
+        .to_multicol(),
+    )
+    .unwrap();
+    let log = HypeCylinder::new(
+        Entity::new(IdPool::new().generate()),
+        Point::default(),
+        basis,
+        1.0,
+        4.0,
+        None,
+    )
+    .unwrap();
+    let down = Vector::new(vec![0.0, 0.0, -1.0]);
+    assert!(aeq(
+        &log.collide(&cs, &Point::new(vec![-3.0, 0.0, 5.0]), &down)
+            .unwrap(),
+        &4.0
+    ));
+    assert!(log
+        .collide(&cs, &Point::new(vec![3.0, 0.0, 5.0]), &down)
+        .is_none());
+}
+
+#[test]
+fn hype_cone_collision() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let lamp = HypeCone::new(
+        Entity::new(IdPool::new().generate()),
+        Point::default(),
+        Basis::default(),
+        [2.0, 0.0],
+        2.0,
+        None,
+    )
+    .unwrap();
+    let hit = lamp
+        .hit(
+            &cs,
+            &Point::new(vec![-5.0, 0.0, 1.0]),
+            &Vector::new(vec![1.0, 0.0, 0.0]),
+        )
+        .unwrap();
+    assert!(aeq(&hit.dist, &4.0));
+    let half = 0.5_f64.sqrt();
+    let normal = hit.normal.unwrap();
+    assert!(aeq(&normal.at(0), &-half) && aeq(&normal.at(2), &half));
+
+    let down = Vector::new(vec![0.0, 0.0, -1.0]);
+    assert!(aeq(
+        &lamp
+            .collide(&cs, &Point::new(vec![0.0, 0.0, 5.0]), &down)
+            .unwrap(),
+        &3.0
+    ));
+    assert!(lamp
+        .collide(
+            &cs,
+            &Point::new(vec![0.0, 0.0, -5.0]),
+            &down.clone().resize(-1.0)
+        )
+        .is_some_and(|t| aeq(&t, &5.0)));
+    assert!(lamp
+        .collide(
+            &cs,
+            &Point::new(vec![-5.0, 0.0, 1.5]),
+            &Vector::new(vec![1.0, 0.6, 0.0])
+        )
+        .is_none());
+}
+
+#[test]
+fn hype_torus_collision() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let ring = HypeTorus::new(
+        Entity::new(IdPool::new().generate()),
+        Point::default(),
+        Basis::default(),
+        3.0,
+        1.0,
+        None,
+    )
+    .unwrap();
+    let right = Vector::new(vec![1.0, 0.0, 0.0]);
+    let hit = ring
+        .hit(&cs, &Point::new(vec![-10.0, 0.0, 0.0]), &right)
+        .unwrap();
+    assert!((hit.dist - 6.0).abs() < 1e-9);
+    let normal = hit.normal.unwrap();
+    assert!((normal.at(0) + 1.0).abs() < 1e-9);
+
+    let down = Vector::new(vec![0.0, 0.0, -2.0]);
+    assert!(ring
+        .collide(&cs, &Point::new(vec![0.0, 0.0, 5.0]), &down)
+        .is_none());
+    let dist = ring
+        .collide(&cs, &Point::new(vec![3.0, 0.0, 5.0]), &down)
+        .unwrap();
+    assert!((dist - 2.0).abs() < 1e-9);
+
+    let from_hole = ring
+        .collide(&cs, &Point::default(), &right.clone().resize(2.0))
+        .unwrap();
+    assert!((from_hole - 1.0).abs() < 1e-9);
+    let bounds = ring.bounds().unwrap();
+    assert_eq!(bounds.min(), &vec![-4.0, -4.0, -1.0]);
+    assert_eq!(bounds.max(), &vec![4.0, 4.0, 1.0]);
+}
+
+#[test]
+fn hype_solids_rotate_then_collide() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let inc = Point::new(vec![0.0, 0.0, 0.2]);
+    let entity = || Entity::new(IdPool::new().generate());
+    let center = || Point::new(vec![5.0, 0.0, 0.0]);
+
+    let mut pillar =
+        HypeCylinder::new(entity(), center(), Basis::default(), 1.0, 4.0, None).unwrap();
+    assert!(aeq(&pillar.collide(&cs, &inc, &dir).unwrap(), &4.0));
+    // axis of pillar is turned along x, so ray meets its bottom cap
+    pillar.planar_rotate(0, 2, FRAC_PI_2).unwrap();
+    let hit = pillar.hit(&cs, &inc, &dir).unwrap();
+    assert!(aeq(&hit.dist, &5.0));
+    assert!(aeq(&hit.normal.unwrap().at(0), &-1.0));
+
+    let mut cone =
+        HypeCone::new(entity(), center(), Basis::default(), [1.0, 0.5], 4.0, None).unwrap();
+    assert!(cone.collide(&cs, &inc, &dir).unwrap() > 4.0);
+    cone.planar_rotate(0, 2, FRAC_PI_2).unwrap();
+    let hit = cone.hit(&cs, &inc, &dir).unwrap();
+    assert!(aeq(&hit.dist, &5.0));
+    assert!(aeq(&hit.normal.unwrap().at(0), &-1.0));
+
+    let mut ring = HypeTorus::new(entity(), center(), Basis::default(), 2.0, 0.5, None).unwrap();
+    assert!(aeq(
+        &ring.collide(&cs, &Point::default(), &dir).unwrap(),
+        &2.5
+    ));
+    ring.planar_rotate(0, 2, FRAC_PI_2).unwrap();
+    assert!(ring.collide(&cs, &Point::default(), &dir).is_none());
+}
+
+#[test]
+fn hype_solids_dir_mut_then_collide() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let inc = Point::new(vec![0.0, 0.0, 0.2]);
+    let entity = || Entity::new(IdPool::new().generate());
+    let center = || Point::new(vec![5.0, 0.0, 0.0]);
+    let turned = Matrix::rotation(0, 2, FRAC_PI_2, 3).to_multicol();
+    let mut solids: Vec<Box<dyn AsGameObject>> = vec![
+        Box::new(HypeCylinder::new(entity(), center(), Basis::default(), 1.0, 4.0, None).unwrap()),
+        Box::new(
+            HypeCone::new(entity(), center(), Basis::default(), [1.0, 0.5], 4.0, None).unwrap(),
+        ),
+    ];
+    for solid in solids.iter_mut() {
+        *solid.dir_mut() = turned.clone();
+        let hit = solid.hit(&cs, &inc, &dir).unwrap();
+        assert!(aeq(&hit.dist, &5.0));
+        assert!(aeq(&hit.normal.unwrap().at(0), &-1.0));
+        *solid.dir_mut() = Matrix::zero(3, 3).to_multicol();
+        assert!(solid.collide(&cs, &inc, &dir).is_none());
+    }
+
+    let mut ring = HypeTorus::new(entity(), center(), Basis::default(), 2.0, 0.5, None).unwrap();
+    *ring.dir_mut() = turned;
+    assert!(ring.collide(&cs, &Point::default(), &dir).is_none());
+}
+
+#[test]
+fn hype_torus_dim_mismatch() {
+    let ring = HypeTorus::new(
+        Entity::new(IdPool::new().generate()),
+        Point::new(vec![0.0, 0.0]),
+        Basis::default(),
+        3.0,
+        1.0,
+        None,
+    );
+    assert_eq!(
+        ring.unwrap_err(),
+        MathErr(crate::errs::MathErr::DimMismatch { lhs: 2, rhs: 3 })
+    );
+}
+
+#[test]
+fn hype_solids_invalid_sizes() {
+    let entity = || Entity::new(IdPool::new().generate());
+    let center = || Point::new(vec![0.0; 3]);
+    let cylinder = |radius, height| {
+        HypeCylinder::new(entity(), center(), Basis::default(), radius, height, None)
+    };
+    assert_eq!(
+        cylinder(0.0, 1.0).unwrap_err(),
+        GameErr(NonPositiveSize {
+            key: "radius",
+            val: 0.0
+        })
+    );
+    assert_eq!(
+        cylinder(1.0, -1.0).unwrap_err(),
+        GameErr(NonPositiveSize {
+            key: "height",
+            val: -1.0
+        })
+    );
+
+    let cone =
+        |radii, height| HypeCone::new(entity(), center(), Basis::default(), radii, height, None);
+    assert!(cone([1.0, 0.0], 1.0).is_ok());
+    assert_eq!(
+        cone([-1.0, 1.0], 1.0).unwrap_err(),
+        GameErr(NonPositiveSize {
+            key: "radii",
+            val: -1.0
+        })
+    );
+    assert_eq!(
+        cone([0.0, 0.0], 1.0).unwrap_err(),
+        GameErr(NonPositiveSize {
+            key: "radii",
+            val: 0.0
+        })
+    );
+
+    let torus =
+        |major, minor| HypeTorus::new(entity(), center(), Basis::default(), major, minor, None);
+    assert_eq!(
+        torus(2.0, 0.0).unwrap_err(),
+        GameErr(NonPositiveSize {
+            key: "minor",
+            val: 0.0
+        })
+    );
+    assert_eq!(
+        torus(1.0, 2.0).unwrap_err(),
+        GameErr(SelfCrossingTorus {
+            major: 1.0,
+            minor: 2.0
+        })
+    );
+
+    let mut flat = Basis::default();
+    *flat.basis_mut() = Matrix::zero(3, 3).to_multicol();
+    assert_eq!(
+        HypeCylinder::new(entity(), center(), flat, 1.0, 1.0, None).unwrap_err(),
+        MathErr(crate::errs::MathErr::NullDeterminant)
+    );
 }
@@ -0,0 +1,195 @@
+use {
+    super::entity,
+    crate::{engn::*, errs::*, math::*},
+    std::{f64::consts::PI, rc::Rc},
+};
+
+fn pt(x: f64, y: f64, z: f64) -> Point {
+    Point::new(vec![x, y, z])
+}
+
+fn vec3(x: f64, y: f64, z: f64) -> Vector {
+    Vector::new(vec![x, y, z])
+}
+
+/// Right triangle in the plane `z = 0` with legs along x and y axes
+fn triangle() -> Triangle {
+    Triangle::new(
+        entity(),
+        [pt(0.0, 0.0, 0.0), pt(2.0, 0.0, 0.0), pt(0.0, 2.0, 0.0)],
+        Some(Charcoal::new("#".to_string(), 30.0)),
+    )
+    .unwrap()
+}
+
+/// Two squares of side 2, the one in the plane `z = 0` and the other in the plane `z = 1`
+fn layers() -> Mesh {
+    Mesh::new(
+        entity(),
+        vec![
+            pt(0.0, 0.0, 0.0),
+            pt(2.0, 0.0, 0.0),
+            pt(2.0, 2.0, 0.0),
+            pt(0.0, 2.0, 0.0),
+            pt(0.0, 0.0, 1.0),
+            pt(2.0, 0.0, 1.0),
+            pt(2.0, 2.0, 1.0),
+            pt(0.0, 2.0, 1.0),
+        ],
+        vec![[0, 1, 2], [0, 2, 3], [4, 5, 6], [4, 6, 7]],
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn triangle_hit_bary() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let tri = triangle();
+    let hit = tri
+        .hit(&cs, &pt(0.5, 0.5, 2.0), &vec3(0.0, 0.0, -1.0))
+        .unwrap();
+    assert!(aeq(&hit.dist, &2.0));
+    assert_eq!(hit.pt, pt(0.5, 0.5, 0.0));
+    assert_eq!(hit.normal, Some(vec3(0.0, 0.0, 1.0)));
+    let bary = hit.bary.unwrap();
+    assert!(aeq(&bary[0], &0.5) && aeq(&bary[1], &0.25) && aeq(&bary[2], &0.25));
+    assert!(hit.charcoal.is_some());
+    assert!(aeq(&hit.interpolate([0.0, 4.0, 8.0]).unwrap(), &3.0));
+}
+
+#[test]
+fn triangle_miss() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let tri = triangle();
+    let down = vec3(0.0, 0.0, -1.0);
+    assert!(tri.hit(&cs, &pt(1.5, 1.5, 1.0), &down).is_none());
+    assert!(tri.hit(&cs, &pt(-0.5, 0.5, 1.0), &down).is_none());
+    assert!(tri
+        .hit(&cs, &pt(0.5, 0.5, 1.0), &vec3(1.0, 0.0, 0.0))
+        .is_none());
+    assert!(tri.hit(&cs, &pt(0.5, 0.5, -1.0), &down).is_none());
+}
+
+#[test]
+fn triangle_edge_and_back() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let tri = triangle();
+    let edge = tri.hit(&cs, &pt(1.0, 1.0, 1.0), &vec3(0.0, 0.0, -1.0));
+    assert!(edge.is_some_and(|hit| aeq(&hit.bary.unwrap()[0], &0.0)));
+    let back = tri.hit(&cs, &pt(0.5, 0.5, -1.0), &vec3(0.0, 0.0, 1.0));
+    assert!(back.is_some_and(|hit| aeq(&hit.dist, &1.0)));
+}
+
+#[test]
+fn triangle_bounds() {
+    let bounds = triangle().bounds().unwrap();
+    assert_eq!(bounds.min(), &vec![0.0, 0.0, 0.0]);
+    assert_eq!(bounds.max(), &vec![2.0, 2.0, 0.0]);
+}
+
+#[test]
+fn mesh_invalid_index() {
+    assert_eq!(
+        Mesh::new(entity(), vec![pt(0.0, 0.0, 0.0)], vec![[0, 0, 3]], None).unwrap_err(),
+        ReErr::GameErr(GameErr::InvalidMeshIndex(3))
+    );
+}
+
+#[test]
+fn mesh_nearest_face() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mesh = layers();
+    let hit = mesh
+        .hit(&cs, &pt(1.5, 0.5, 3.0), &vec3(0.0, 0.0, -1.0))
+        .unwrap();
+    assert!(aeq(&hit.dist, &2.0));
+    assert_eq!(hit.face, Some(2));
+    assert_eq!(hit.normal, Some(vec3(0.0, 0.0, 1.0)));
+
+    let hit = mesh
+        .hit(&cs, &pt(0.5, 1.5, -1.0), &vec3(0.0, 0.0, 1.0))
+        .unwrap();
+    assert!(aeq(&hit.dist, &1.0));
+    assert_eq!(hit.face, Some(1));
+    assert!(mesh
+        .hit(&cs, &pt(3.0, 1.0, 3.0), &vec3(0.0, 0.0, -1.0))
+        .is_none());
+}
+
+#[test]
+fn mesh_moved_and_rotated() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut mesh = layers();
+    let down = vec3(0.0, 0.0, -1.0);
+    assert!(mesh.hit(&cs, &pt(1.0, 1.0, 3.0), &down).is_some());
+
+    mesh.mv(&vec3(10.0, 0.0, 0.0)).unwrap();
+    assert!(mesh.hit(&cs, &pt(1.0, 1.0, 3.0), &down).is_none());
+    let hit = mesh.hit(&cs, &pt(11.0, 1.0, 3.0), &down).unwrap();
+    assert!(aeq(&hit.dist, &2.0));
+    assert_eq!(mesh.world_vertex(2, 1), pt(12.0, 0.0, 1.0));
+
+    mesh.planar_rotate(0, 1, PI / 2.0).unwrap();
+    assert!(mesh.hit(&cs, &pt(11.0, 1.0, 3.0), &down).is_none());
+    assert!(mesh.hit(&cs, &pt(9.0, 1.0, 3.0), &down).is_some());
+    let bounds = mesh.bounds().unwrap();
+    assert!(aeq(&bounds.min()[0], &8.0) && aeq(&bounds.max()[0], &10.0));
+}
+
+#[test]
+fn mesh_smooth_normals() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let roof = || {
+        Mesh::new(
+            entity(),
+            vec![
+                pt(0.0, 0.0, 0.0),
+                pt(1.0, 0.0, 1.0),
+                pt(2.0, 0.0, 0.0),
+                pt(0.0, 1.0, 0.0),
+                pt(1.0, 1.0, 1.0),
+                pt(2.0, 1.0, 0.0),
+            ],
+            vec![[0, 1, 4], [0, 4, 3], [1, 2, 5], [1, 5, 4]],
+            None,
+        )
+        .unwrap()
+    };
+    let (inc, down) = (pt(0.5, 0.5, 3.0), vec3(0.0, 0.0, -1.0));
+    let flat = roof().hit(&cs, &inc, &down).unwrap().normal.unwrap();
+    let smooth = roof()
+        .with_smooth(true)
+        .hit(&cs, &inc, &down)
+        .unwrap()
+        .normal
+        .unwrap();
+    let half = 0.5_f64.sqrt();
+    assert!(aeq(&flat.at(0), &-half) && aeq(&flat.at(2), &half));
+    assert!(flat.at(0) < smooth.at(0) && flat.at(2) < smooth.at(2));
+    assert!(aeq(&cs.len(&smooth.coord).unwrap(), &1.0));
+}
+
+#[test]
+fn mesh_groups_and_normals_errors() {
+    let mesh = layers().with_group("bottom", vec![0, 1]).unwrap();
+    assert_eq!(mesh.group("bottom"), Some(&vec![0, 1]));
+    assert_eq!(mesh.group_of(1), Some("bottom"));
+    assert!(mesh.group_of(2).is_none());
+    assert_eq!(
+        layers().with_group("top", vec![2, 4]).unwrap_err(),
+        ReErr::GameErr(GameErr::InvalidMeshIndex(4))
+    );
+    assert_eq!(
+        layers()
+            .with_normals(vec![vec3(0.0, 0.0, 1.0)], vec![(0, [0, 0, 1])])
+            .unwrap_err(),
+        ReErr::GameErr(GameErr::InvalidMeshIndex(1))
+    );
+}
@@ -1,8 +1,84 @@
 #[cfg(test)]
+mod bvh_test;
+#[cfg(test)]
 mod camera_test;
 #[cfg(test)]
+mod canvas_test;
+#[cfg(test)]
+mod color_test;
+#[cfg(test)]
+mod csg_test;
+#[cfg(test)]
 mod event_test;
 #[cfg(test)]
 mod game_test;
+
+#[cfg(test)]
+mod graph_test;
+#[cfg(test)]
+mod headless_test;
+#[cfg(test)]
+mod hit_test;
+
+#[cfg(test)]
+mod instance_test;
+#[cfg(test)]
+mod light_test;
 #[cfg(test)]
 mod material_test;
+#[cfg(test)]
+mod mesh_test;
+#[cfg(test)]
+mod obj_test;
+#[cfg(test)]
+mod path_test;
+#[cfg(test)]
+mod scene_test;
+
+#[cfg(test)]
+mod sdf_test;
+#[cfg(test)]
+mod snapshot_test;
+
+#[cfg(test)]
+mod terrain_test;
+#[cfg(test)]
+mod tilemap_test;
+
+use {super::*, crate::math::*, std::rc::Rc, uuid::Uuid};
+
+/// Entity with new unique id
+pub(crate) fn entity() -> Entity {
+    Entity::new(Rc::new(Uuid::new_v4()))
+}
+
+/// Scene with the single floor plane `z = 0` drawn with its own charcoal
+pub(crate) struct FloorScene {
+    pub(crate) floor: HypePlane,
+}
+
+impl FloorScene {
+    pub(crate) fn new() -> Self {
+        Self {
+            floor: HypePlane::new(
+                entity(),
+                Point::default(),
+                Vector::new(vec![0.0, 0.0, 1.0]),
+                Some(Charcoal::new("#+.".to_string(), 30.0)),
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl AsScene for FloorScene {
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        self.floor.hit(cs, inc, dir)
+    }
+
+    fn background(&self, _cs: &CoordSys, _dir: &Vector) -> Option<Cell> {
+        Some(Cell::from(' '))
+    }
+
+    fn validate_mv(&self, _cs: &CoordSys, _pos: &Point, _mv: &mut Vector) {}
+}
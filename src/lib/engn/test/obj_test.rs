@@ -0,0 +1,113 @@
+use {
+    super::entity,
+    crate::{engn::*, errs::*, math::*},
+    std::{io::ErrorKind, path::PathBuf, rc::Rc},
+};
+
+const HOUSE: &str = "src/lib/engn/test/models/house.obj";
+
+fn parse(content: &str) -> ReRes<Mesh> {
+    Mesh::parse_obj(entity(), content, None)
+}
+
+#[test]
+fn from_obj_house() {
+    let mesh = Mesh::from_obj(entity(), HOUSE, None).unwrap();
+    assert_eq!(mesh.vertices().len(), 5);
+    assert_eq!(mesh.faces().len(), 6);
+    assert_eq!(mesh.faces()[0], [0, 3, 2]);
+    assert_eq!(mesh.faces()[1], [0, 2, 1]);
+    assert_eq!(mesh.faces()[4], [2, 3, 4]);
+    assert_eq!(mesh.group("floor"), Some(&vec![0, 1]));
+    assert_eq!(mesh.group("roof"), Some(&vec![2, 3, 4, 5]));
+    assert!(mesh.group("house").is_some_and(|faces| faces.is_empty()));
+    assert_eq!(mesh.group_of(3), Some("roof"));
+}
+
+#[test]
+fn from_obj_hit() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mesh = Mesh::from_obj(entity(), PathBuf::from(HOUSE), None).unwrap();
+    let hit = mesh
+        .hit(
+            &cs,
+            &Point::new(vec![1.0, 1.0, -1.0]),
+            &Vector::new(vec![0.0, 0.0, 1.0]),
+        )
+        .unwrap();
+    assert!(aeq(&hit.dist, &1.0));
+    assert_eq!(mesh.group_of(hit.face.unwrap()), Some("floor"));
+    assert_eq!(hit.normal, Some(Vector::new(vec![0.0, 0.0, -1.0])));
+
+    let hit = mesh
+        .hit(
+            &cs,
+            &Point::new(vec![1.0, 1.0, 3.0]),
+            &Vector::new(vec![0.0, 0.0, -1.0]),
+        )
+        .unwrap();
+    assert!(aeq(&hit.dist, &2.0));
+    assert_eq!(mesh.group_of(hit.face.unwrap()), Some("roof"));
+}
+
+#[test]
+fn from_obj_unreadable() {
+    assert_eq!(
+        Mesh::from_obj(entity(), "absent.obj", None).unwrap_err(),
+        ReErr::LoadErr(LoadErr::UnreadableFile(ErrorKind::NotFound))
+    );
+}
+
+#[test]
+fn parse_obj_errors() {
+    assert_eq!(
+        parse("v 0 0 0\nv 1 x 0").unwrap_err(),
+        ReErr::LoadErr(LoadErr::InvalidNumber { line: 2 })
+    );
+    assert_eq!(
+        parse("# comment\n\nv 0 0").unwrap_err(),
+        ReErr::LoadErr(LoadErr::MissingCoords {
+            line: 3,
+            expected: 3
+        })
+    );
+    assert_eq!(
+        parse("v 0 0 0\nv 1 0 0\nf 1 2").unwrap_err(),
+        ReErr::LoadErr(LoadErr::DegenerateFace { line: 3 })
+    );
+    assert_eq!(
+        parse("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4").unwrap_err(),
+        ReErr::LoadErr(LoadErr::InvalidIndex { line: 4, idx: 4 })
+    );
+    assert_eq!(
+        parse("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 0").unwrap_err(),
+        ReErr::LoadErr(LoadErr::InvalidIndex { line: 4, idx: 0 })
+    );
+    assert_eq!(
+        parse("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1//1 2//1 3//1").unwrap_err(),
+        ReErr::LoadErr(LoadErr::InvalidIndex { line: 4, idx: 1 })
+    );
+}
+
+#[test]
+fn parse_obj_normals() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mesh = parse(
+        "v 0 0 0\nv 2 0 0\nv 0 2 0\nvn -1 0 1\nvn 1 0 1\nvn 0 0 1\nf 1//1 2//2 3//3 # smooth\n",
+    )
+    .unwrap();
+    let (inc, down) = (
+        Point::new(vec![1.0, 0.0, 1.0]),
+        Vector::new(vec![0.0, 0.0, -1.0]),
+    );
+    let normal = mesh.hit(&cs, &inc, &down).unwrap().normal.unwrap();
+    let half = 0.5_f64.sqrt();
+    assert!(aeq(&normal.at(0), &0.0) && aeq(&normal.at(2), &1.0));
+
+    let inc = Point::new(vec![0.5, 0.0, 1.0]);
+    let normal = mesh.hit(&cs, &inc, &down).unwrap().normal.unwrap();
+    assert!(normal.at(0) < 0.0 && aeq(&cs.len(&normal.coord).unwrap(), &1.0));
+    assert!(normal.at(0) > -half);
+}
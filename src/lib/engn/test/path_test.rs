@@ -0,0 +1,272 @@
+use {
+    super::{
+        super::{path::Quat, *},
+        FloorScene,
+    },
+    crate::{conf::*, errs::ReRes, math::*},
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        style::Color,
+    },
+    std::{
+        f64::consts::{FRAC_PI_2, FRAC_PI_3, FRAC_PI_4, FRAC_PI_6, PI},
+        time::{Duration, Instant},
+    },
+};
+
+type TestGame = Game<MovementEvent<FloorScene>, MovementEventSys, FloorScene, Headless>;
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+fn key(time: f64, x: f64, y: f64) -> Keyframe {
+    Keyframe::new(time, Point::new(vec![x, y, 1.0]))
+}
+
+#[test]
+fn path_keys_sorted_by_time() {
+    let path = CameraPath::new()
+        .with_key(key(2.0, 2.0, 0.0))
+        .with_key(key(0.0, 0.0, 0.0))
+        .with_key(key(1.0, 1.0, 0.0))
+        .with_key(key(2.0, 5.0, 0.0));
+    let times = path.keys().iter().map(|k| k.time).collect::<Vec<f64>>();
+    assert_eq!(times, vec![0.0, 1.0, 2.0]);
+    assert_eq!(path.keys()[2].pos[0], 5.0);
+    assert_eq!(path.duration(), 2.0);
+    assert_eq!(path.with_fps(10.0).frames(), 21);
+}
+
+#[test]
+fn path_passes_through_keys() {
+    let path = CameraPath::new()
+        .with_key(key(0.0, 0.0, 0.0))
+        .with_key(key(1.0, 1.0, 2.0))
+        .with_key(key(3.0, 4.0, -1.0))
+        .with_key(key(4.0, 0.0, 0.0));
+    for k in path.keys() {
+        assert_eq!(path.sample(k.time).unwrap().pos, k.pos);
+    }
+    assert_eq!(path.sample(-1.0).unwrap().pos, path.keys()[0].pos);
+    assert_eq!(path.sample(5.0).unwrap().pos, path.keys()[3].pos);
+    assert!(CameraPath::new().sample(0.0).is_none());
+}
+
+#[test]
+fn catmull_rom_keeps_uniform_motion() {
+    let path = CameraPath::new()
+        .with_key(key(0.0, 0.0, 0.0))
+        .with_key(key(1.0, 1.0, 0.0))
+        .with_key(key(2.0, 2.0, 0.0))
+        .with_key(key(3.0, 3.0, 0.0));
+    for time in [0.25, 1.5, 2.75] {
+        let pos = path.sample(time).unwrap().pos;
+        assert_close(pos[0], time);
+        assert_close(pos[1], 0.0);
+        assert_close(pos[2], 1.0);
+    }
+}
+
+#[test]
+fn catmull_rom_curves_through_corner() {
+    let path = CameraPath::new()
+        .with_key(key(0.0, 0.0, 0.0))
+        .with_key(key(1.0, 1.0, 0.0))
+        .with_key(key(2.0, 1.0, 1.0));
+    let pos = path.sample(0.5).unwrap().pos;
+    assert_close(pos[0], 0.5625);
+    assert_close(pos[1], -0.0625);
+    let pos = path.sample(1.5).unwrap().pos;
+    assert_close(pos[0], 1.0625);
+    assert_close(pos[1], 0.4375);
+}
+
+#[test]
+fn euler_angles_round_trip() {
+    for angles in [
+        (0.0, 0.0, 0.0),
+        (FRAC_PI_4, FRAC_PI_6, -FRAC_PI_3),
+        (-2.0, -1.0, 3.0),
+        (PI / 2.0, 0.0, 0.0),
+    ] {
+        let (yaw, pitch, roll) = Quat::from_euler(angles).to_euler();
+        assert_close(yaw, angles.0);
+        assert_close(pitch, angles.1);
+        assert_close(roll, angles.2);
+    }
+}
+
+#[test]
+fn slerp_orientation() {
+    let path = CameraPath::new()
+        .with_key(key(0.0, 0.0, 0.0))
+        .with_key(key(1.0, 0.0, 0.0).with_orientation(FRAC_PI_2, 0.0, 0.0))
+        .with_key(key(2.0, 0.0, 0.0).with_orientation(FRAC_PI_2, FRAC_PI_3, 0.0));
+    let (yaw, pitch, roll) = path.sample(0.5).unwrap().orientation;
+    assert_close(yaw, FRAC_PI_4);
+    assert_close(pitch, 0.0);
+    assert_close(roll, 0.0);
+    let (yaw, pitch, _) = path.sample(1.5).unwrap().orientation;
+    assert_close(yaw, FRAC_PI_2);
+    assert_close(pitch, FRAC_PI_6);
+}
+
+#[test]
+fn slerp_along_shorter_arc() {
+    let path = CameraPath::new()
+        .with_key(key(0.0, 0.0, 0.0).with_orientation(0.75 * PI, 0.0, 0.0))
+        .with_key(key(1.0, 0.0, 0.0).with_orientation(-0.75 * PI, 0.0, 0.0));
+    let (yaw, _, _) = path.sample(0.5).unwrap().orientation;
+    assert_close(yaw.abs(), PI);
+}
+
+#[test]
+fn fov_interpolated_linearly() {
+    let path = CameraPath::new()
+        .with_key(key(0.0, 0.0, 0.0).with_fov(FRAC_PI_2, FRAC_PI_4))
+        .with_key(key(1.0, 0.0, 0.0).with_fov(FRAC_PI_4, FRAC_PI_6))
+        .with_key(key(2.0, 0.0, 0.0));
+    let (wfov, hfov) = path.sample(0.5).unwrap().fov.unwrap();
+    assert_close(wfov, 3.0 * PI / 8.0);
+    assert_close(hfov, 5.0 * PI / 24.0);
+    assert_eq!(path.sample(1.5).unwrap().fov, Some((FRAC_PI_4, FRAC_PI_6)));
+}
+
+#[test]
+fn keyframe_applied_to_camera() {
+    set_biform_identity();
+    let mut camera = Camera::new(Point::default(), 2, FRAC_PI_2, FRAC_PI_2, (3, 3), 10.0);
+    let key = key(0.0, 3.0, 4.0)
+        .with_orientation(FRAC_PI_2, 0.0, 0.0)
+        .with_fov(FRAC_PI_4, FRAC_PI_4);
+    key.apply(&mut camera).unwrap();
+    assert_eq!(camera.pos(), &key.pos);
+    assert_eq!(camera.yaw(), FRAC_PI_2);
+    assert_eq!(camera.fov(), (FRAC_PI_4, FRAC_PI_4));
+    assert_eq!(Keyframe::from_camera(0.0, &camera), key);
+}
+
+fn flythrough() -> CameraPath {
+    CameraPath::new()
+        .with_fps(4.0)
+        .with_key(key(0.0, 0.0, 0.0))
+        .with_key(key(1.0, 2.0, 0.0).with_orientation(FRAC_PI_2, 0.0, 0.0))
+}
+
+#[test]
+fn game_plays_path() {
+    let mut game = TestGame::with_term(
+        Conf::default(),
+        FloorScene::new(),
+        MovementEventSys::new(1.0),
+        Headless::new(8, 9),
+    )
+    .unwrap();
+    game.play(&flythrough()).unwrap();
+    assert_eq!(game.term().frames().len(), 5);
+    assert_eq!(game.camera().pos(), &Point::new(vec![2.0, 0.0, 1.0]));
+    assert_eq!(game.camera().yaw(), FRAC_PI_2);
+}
+
+#[test]
+fn game_skips_path() {
+    let skip = Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    let mut game = TestGame::with_term(
+        Conf::default(),
+        FloorScene::new(),
+        MovementEventSys::new(1.0),
+        Headless::new(8, 9).script(vec![skip]),
+    )
+    .unwrap();
+    game.play(&flythrough()).unwrap();
+    assert_eq!(game.term().frames().len(), 2);
+    assert_eq!(game.camera().pos(), &Point::new(vec![2.0, 0.0, 1.0]));
+    assert_eq!(game.camera().yaw(), FRAC_PI_2);
+}
+
+#[test]
+fn game_ignored_events_keep_frame() {
+    let press = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let events = vec![
+        press(KeyCode::Char('w')),
+        press(KeyCode::Char('w')),
+        press(KeyCode::Esc),
+    ];
+    let mut game = TestGame::with_term(
+        Conf::default(),
+        FloorScene::new(),
+        MovementEventSys::new(1.0),
+        Headless::new(8, 9).script(events),
+    )
+    .unwrap();
+    game.play(&flythrough()).unwrap();
+    assert_eq!(game.term().frames().len(), 2);
+    assert_eq!(game.camera().pos(), &Point::new(vec![2.0, 0.0, 1.0]));
+}
+
+/// `Headless` that waits out the whole timeout of `poll` when there are no scripted events like the real console
+struct PacedTerm(Headless);
+
+impl AsTerminal for PacedTerm {
+    fn init(&mut self) -> ReRes<(u16, u16)> {
+        self.0.init()
+    }
+
+    fn size(&self) -> ReRes<(u16, u16)> {
+        self.0.size()
+    }
+
+    fn listen(&mut self) -> ReRes<Option<Event>> {
+        self.0.listen()
+    }
+
+    fn poll(&mut self, timeout: Duration) -> ReRes<Option<Event>> {
+        let event = self.0.listen()?;
+        if event.is_none() {
+            std::thread::sleep(timeout);
+        }
+        Ok(event)
+    }
+
+    fn move_cursor(&mut self, row: u16, col: u16) -> ReRes<()> {
+        self.0.move_cursor(row, col)
+    }
+
+    fn print(&mut self, s: &str) -> ReRes<()> {
+        self.0.print(s)
+    }
+
+    fn set_colors(&mut self, fg: Option<Color>, bg: Option<Color>) -> ReRes<()> {
+        self.0.set_colors(fg, bg)
+    }
+
+    fn clear(&mut self) -> ReRes<()> {
+        self.0.clear()
+    }
+
+    fn flush(&mut self) -> ReRes<usize> {
+        self.0.flush()
+    }
+}
+
+#[test]
+fn ignored_events_dont_speed_up_playback() {
+    let events = vec![Event::Key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE)); 20];
+    let mut game =
+        Game::<MovementEvent<FloorScene>, MovementEventSys, FloorScene, PacedTerm>::with_term(
+            Conf::default(),
+            FloorScene::new(),
+            MovementEventSys::new(1.0),
+            PacedTerm(Headless::new(8, 9).script(events)),
+        )
+        .unwrap();
+    let path = CameraPath::new()
+        .with_fps(40.0)
+        .with_key(key(0.0, 0.0, 0.0))
+        .with_key(key(0.25, 2.0, 0.0));
+    let start = Instant::now();
+    game.play(&path).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(250));
+    assert_eq!(game.term().0.frames().len(), 11);
+}
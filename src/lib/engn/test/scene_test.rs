@@ -0,0 +1,189 @@
+use {
+    super::super::snapshot::*,
+    crate::{conf::*, engn::*, math::*},
+    std::rc::Rc,
+    uuid::Uuid,
+};
+
+fn wall(x: f64) -> Box<HypePlane> {
+    Box::new(
+        HypePlane::new(
+            Entity::new(Rc::new(Uuid::new_v4())),
+            Point::new(vec![x, 0.0, 0.0]),
+            Vector::new(vec![1.0, 0.0, 0.0]),
+            None,
+        )
+        .unwrap(),
+    )
+}
+
+fn ball(x: f64, charcoal: &str) -> Box<HypeEllipse> {
+    Box::new(
+        HypeEllipse::new(
+            Entity::new(Rc::new(Uuid::new_v4())),
+            Point::new(vec![x, 0.0, 0.0]),
+            Basis::default(),
+            vec![1.0, 1.0, 1.0],
+            Some(Charcoal::new(charcoal.to_string(), 10.0)),
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+fn nearest_hit() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut scene = SceneList::new();
+    scene.add(wall(10.0), None);
+    let near = scene.add(ball(5.0, "@"), None);
+    scene.add(ball(8.0, "O"), None);
+    let hit = scene
+        .collide(&cs, &Point::default(), &Vector::new(vec![1.0, 0.0, 0.0]))
+        .unwrap();
+    assert_eq!(hit.dist, 4.0);
+    assert_eq!(*hit.id, *near);
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '@');
+}
+
+#[test]
+fn scene_charcoal_replaces_own() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut scene = SceneList::new();
+    let id = scene.add(ball(5.0, "@"), Some(Charcoal::new("#".to_string(), 10.0)));
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let hit = scene.collide(&cs, &Point::default(), &dir).unwrap();
+    assert_eq!(scene.shade(&cs, &dir, &hit), Some(Cell::from('#')));
+
+    scene.set_charcoal(&id, Charcoal::new("%".to_string(), 10.0));
+    assert_eq!(scene.charcoal(&id).unwrap().ignite(0.0), '%');
+}
+
+#[test]
+fn object_without_charcoal_left_to_renderer() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut scene = SceneList::new().with_background(Cell::from(' '));
+    scene.add(wall(3.0), None);
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    let hit = scene.collide(&cs, &Point::default(), &dir).unwrap();
+    assert_eq!(scene.shade(&cs, &dir, &hit), None);
+    let back = Vector::new(vec![-1.0, 0.0, 0.0]);
+    assert!(scene.collide(&cs, &Point::default(), &back).is_none());
+    assert_eq!(scene.background(&cs, &back), Some(Cell::from(' ')));
+}
+
+#[test]
+fn add_remove_get() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut scene = SceneList::new();
+    let near = scene.add(ball(5.0, "@"), None);
+    let far = scene.add(ball(8.0, "O"), None);
+    assert_eq!(scene.len(), 2);
+    assert!(scene.get(&Rc::new(*far)).is_some());
+
+    scene.remove(&near);
+    assert_eq!(scene.len(), 1);
+    assert!(scene.get(&near).is_none());
+    let hit = scene
+        .collide(&cs, &Point::default(), &Vector::new(vec![1.0, 0.0, 0.0]))
+        .unwrap();
+    assert_eq!(*hit.id, *far);
+}
+
+#[test]
+fn get_mut_moves_object() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut scene = SceneList::new();
+    let id = scene.add(wall(3.0), None);
+    let dir = Vector::new(vec![1.0, 0.0, 0.0]);
+    assert_eq!(
+        scene.collide(&cs, &Point::default(), &dir).unwrap().dist,
+        3.0
+    );
+
+    scene
+        .get_mut(&id)
+        .unwrap()
+        .as_game_object_mut()
+        .unwrap()
+        .mv(&Vector::new(vec![2.0, 0.0, 0.0]))
+        .unwrap();
+    assert_eq!(
+        scene.collide(&cs, &Point::default(), &dir).unwrap().dist,
+        5.0
+    );
+
+    assert!(scene.mv(&id, &Vector::new(vec![-1.0, 0.0, 0.0])).unwrap());
+    assert_eq!(
+        scene.collide(&cs, &Point::default(), &dir).unwrap().dist,
+        4.0
+    );
+}
+
+#[test]
+fn validate_mv_stops_before_object() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut scene = SceneList::new().with_radius(0.5);
+    scene.add(wall(3.0), None);
+
+    let mut free = Vector::new(vec![1.0, 0.0, 0.0]);
+    scene.validate_mv(&cs, &Point::default(), &mut free);
+    assert_eq!(free, Vector::new(vec![1.0, 0.0, 0.0]));
+
+    let mut blocked = Vector::new(vec![1.0, 0.0, 0.0]);
+    scene.validate_mv(&cs, &Point::new(vec![2.0, 0.0, 0.0]), &mut blocked);
+    assert_eq!(blocked, Vector::new(vec![0.5, 0.0, 0.0]));
+
+    let mut stuck = Vector::new(vec![1.0, 0.0, 0.0]);
+    scene.validate_mv(&cs, &Point::new(vec![2.8, 0.0, 0.0]), &mut stuck);
+    assert_eq!(stuck, Vector::new(vec![0.0, 0.0, 0.0]));
+}
+
+#[test]
+fn lit_scene_list_snapshot() {
+    set_biform_identity();
+    let mut conf = Conf::default();
+    conf.angle_discr = 4;
+    conf.wfov = 0.5;
+    conf.hfov = Some(0.25);
+    let snapshot = Snapshot::new(conf, (11, 21));
+    let mut scene = SceneList::new()
+        .with_lighting(
+            Lighting::new(0.2, 30.0)
+                .with_light(Light::directional(Vector::new(vec![1.0, 1.0, -1.0]), 1.0)),
+        )
+        .with_background(Cell::from(' '));
+    scene.add(
+        Box::new(
+            HypePlane::new(
+                Entity::new(Rc::new(Uuid::new_v4())),
+                Point::default(),
+                Vector::new(vec![0.0, 0.0, 1.0]),
+                Some(Charcoal::new("#+-.".to_string(), 30.0)),
+            )
+            .unwrap(),
+        ),
+        None,
+    );
+    scene.add(
+        Box::new(
+            HypeEllipse::new(
+                Entity::new(Rc::new(Uuid::new_v4())),
+                Point::new(vec![8.0, 0.0, 2.0]),
+                Basis::default(),
+                vec![2.0, 2.0, 2.0],
+                None,
+            )
+            .unwrap(),
+        ),
+        Some(Charcoal::new("@O*o:.".to_string(), 10.0)),
+    );
+    let camera = snapshot.camera(Point::new(vec![0.0, 0.0, 2.0]));
+    let frame = snapshot.render(&scene, &camera).unwrap();
+    assert_snapshot("src/lib/engn/test/snapshots/lit_scene_list.txt", &frame);
+}
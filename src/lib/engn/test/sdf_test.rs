@@ -0,0 +1,157 @@
+use {
+    super::entity,
+    crate::{conf::*, engn::*, math::*},
+    std::rc::Rc,
+};
+
+fn sphere(center: [f64; 3], radius: f64) -> Box<dyn AsSdf> {
+    Box::new(SdfSphere { center, radius })
+}
+
+fn sdf(shape: Box<dyn AsSdf>) -> Sdf {
+    Sdf::new(
+        entity(),
+        shape,
+        100.0,
+        Some(Charcoal::new("#".to_string(), 100.0)),
+    )
+}
+
+#[test]
+fn primitive_distances() {
+    let ball = SdfSphere {
+        center: [1.0, 0.0, 0.0],
+        radius: 2.0,
+    };
+    assert_eq!(ball.dist(&[4.0, 0.0, 0.0]), 1.0);
+    assert_eq!(ball.dist(&[1.0, 0.0, 0.0]), -2.0);
+
+    let cube = SdfBox {
+        center: [0.0; 3],
+        extent: [1.0, 2.0, 3.0],
+        rounding: 0.0,
+    };
+    assert_eq!(cube.dist(&[3.0, 0.0, 0.0]), 2.0);
+    assert_eq!(cube.dist(&[0.0, 0.0, 0.0]), -1.0);
+    assert!(aeq(&cube.dist(&[4.0, 6.0, 0.0]), &5.0));
+
+    let torus = SdfTorus {
+        center: [0.0; 3],
+        major: 3.0,
+        minor: 1.0,
+    };
+    assert_eq!(torus.dist(&[3.0, 0.0, 0.0]), -1.0);
+    assert_eq!(torus.dist(&[0.0, 0.0, 0.0]), 2.0);
+
+    let capsule = SdfCapsule {
+        from: [0.0; 3],
+        to: [0.0, 0.0, 4.0],
+        radius: 1.0,
+    };
+    assert_eq!(capsule.dist(&[2.0, 0.0, 2.0]), 1.0);
+    assert_eq!(capsule.dist(&[0.0, 0.0, 6.0]), 1.0);
+    let bounds = capsule.bounds().unwrap();
+    assert_eq!(bounds.min(), &vec![-1.0, -1.0, -1.0]);
+    assert_eq!(bounds.max(), &vec![1.0, 1.0, 5.0]);
+}
+
+#[test]
+fn sdf_sphere_hit() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let ball = sdf(sphere([5.0, 0.0, 0.0], 1.0));
+
+    let inc = Point::new(vec![0.0, 0.0, 0.0]);
+    let hit = ball
+        .hit(&cs, &inc, &Vector::new(vec![2.0, 0.0, 0.0]))
+        .unwrap();
+    assert!((hit.dist - 2.0).abs() < 1e-5);
+    assert!((hit.pt[0] - 4.0).abs() < 1e-5);
+    let normal = hit.normal.unwrap();
+    assert!((normal.at(0) + 1.0).abs() < 1e-6);
+    assert!(hit.charcoal.is_some());
+
+    let dir = Vector::new(vec![0.0, 1.0, 0.0]);
+    assert!(AsCollided::collide(&ball, &cs, &inc, &dir).is_none());
+    let behind = Vector::new(vec![-1.0, 0.0, 0.0]);
+    assert!(AsCollided::collide(&ball, &cs, &inc, &behind).is_none());
+}
+
+#[test]
+fn sdf_closure() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let floor = sdf(Box::new(|pt: &[f64; 3]| pt[2] + 1.0));
+    assert!(floor.bounds().is_none());
+
+    let inc = Point::new(vec![0.0, 0.0, 0.0]);
+    let down = Vector::new(vec![1.0, 0.0, -1.0]);
+    let hit = floor.hit(&cs, &inc, &down).unwrap();
+    assert!((hit.dist - 1.0).abs() < 1e-5);
+    assert!((hit.normal.unwrap().at(2) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn sdf_draw_dist() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let conf = Conf::default();
+    let far = [conf.draw_dist + 10.0, 0.0, 0.0];
+    let ball = Sdf::from_conf(entity(), sphere(far, 1.0), &conf, None);
+    let (inc, dir) = (Point::new(vec![0.0; 3]), Vector::new(vec![1.0, 0.0, 0.0]));
+    assert!(AsCollided::collide(&ball, &cs, &inc, &dir).is_none());
+
+    let floor = sdf(Box::new(|pt: &[f64; 3]| 200.0 - pt[0]));
+    assert!(AsCollided::collide(&floor, &cs, &inc, &dir).is_none());
+    let floor = floor.with_steps(1);
+    assert!(AsCollided::collide(&floor, &cs, &inc, &dir).is_none());
+}
+
+#[test]
+fn sdf_smooth_operators() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let (inc, dir) = (
+        Point::new(vec![0.0, 0.0, 5.0]),
+        Vector::new(vec![0.0, 0.0, -1.0]),
+    );
+    let pair = |k| {
+        sdf(Box::new(SdfSmooth::union(
+            sphere([-1.1, 0.0, 0.0], 1.0),
+            sphere([1.1, 0.0, 0.0], 1.0),
+            k,
+        )))
+    };
+    assert!(AsCollided::collide(&pair(0.0), &cs, &inc, &dir).is_none());
+    assert!(AsCollided::collide(&pair(1.0), &cs, &inc, &dir).is_some());
+
+    let lens = SdfSmooth::intersection(
+        sphere([-0.5, 0.0, 0.0], 1.0),
+        sphere([0.5, 0.0, 0.0], 1.0),
+        0.0,
+    );
+    assert!(aeq(&lens.dist(&[0.0; 3]), &-0.5));
+    assert!(aeq(&lens.dist(&[1.0, 0.0, 0.0]), &0.5));
+
+    let bitten = sdf(Box::new(SdfSmooth::difference(
+        sphere([0.0; 3], 2.0),
+        sphere([0.0, 0.0, 2.0], 1.0),
+        0.0,
+    )));
+    let hit = bitten.hit(&cs, &inc, &dir).unwrap();
+    assert!((hit.pt[2] - 1.0).abs() < 1e-5);
+    assert!((hit.normal.unwrap().at(2) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn sdf_blend() {
+    let morph = |weight| SdfBlend {
+        left: sphere([0.0; 3], 1.0),
+        right: sphere([0.0; 3], 3.0),
+        weight,
+    };
+    assert!(aeq(&morph(0.0).dist(&[4.0, 0.0, 0.0]), &3.0));
+    assert!(aeq(&morph(1.0).dist(&[4.0, 0.0, 0.0]), &1.0));
+    assert!(aeq(&morph(0.5).dist(&[4.0, 0.0, 0.0]), &2.0));
+    assert_eq!(morph(0.5).bounds().unwrap().max(), &vec![3.0; 3]);
+}
@@ -0,0 +1,158 @@
+use {
+    super::{super::snapshot::*, super::*, FloorScene},
+    crate::{conf::*, math::*},
+    std::{env, fs::read_to_string, rc::Rc},
+    uuid::Uuid,
+};
+
+/// Floor with the ball lying on it
+struct BallScene {
+    floor: FloorScene,
+    ball: HypeEllipse,
+}
+
+impl BallScene {
+    fn new() -> Self {
+        Self {
+            floor: FloorScene::new(),
+            ball: HypeEllipse::new(
+                Entity::new(Rc::new(Uuid::new_v4())),
+                Point::new(vec![8.0, 0.0, 2.0]),
+                Basis::default(),
+                vec![2.0, 2.0, 2.0],
+                Some(Charcoal::new("@O".to_string(), 10.0)),
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl AsScene for BallScene {
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        self.ball
+            .hit(cs, inc, dir)
+            .or_else(|| self.floor.collide(cs, inc, dir))
+    }
+
+    fn background(&self, cs: &CoordSys, dir: &Vector) -> Option<Cell> {
+        self.floor.background(cs, dir)
+    }
+
+    fn validate_mv(&self, _cs: &CoordSys, _pos: &Point, _mv: &mut Vector) {}
+}
+
+fn snapshot() -> Snapshot {
+    let mut conf = Conf::default();
+    conf.angle_discr = 4;
+    conf.wfov = 0.5;
+    conf.hfov = Some(0.25);
+    Snapshot::new(conf, (11, 21))
+}
+
+#[test]
+fn floor_snapshot() {
+    let snapshot = snapshot();
+    let camera = snapshot.camera(Point::new(vec![0.0, 0.0, 2.0]));
+    let frame = snapshot.render(&FloorScene::new(), &camera).unwrap();
+    assert_snapshot("src/lib/engn/test/snapshots/floor.txt", &frame);
+}
+
+#[test]
+fn ball_snapshot() {
+    let snapshot = snapshot();
+    let camera = snapshot.camera(Point::new(vec![0.0, 0.0, 2.0]));
+    let frame = snapshot.render(&BallScene::new(), &camera).unwrap();
+    assert_snapshot("src/lib/engn/test/snapshots/ball.txt", &frame);
+}
+
+#[test]
+fn ball_rotated_snapshot() {
+    let snapshot = snapshot();
+    let mut camera = snapshot.camera(Point::new(vec![0.0, 0.0, 2.0]));
+    camera.rotate_left(1);
+    let frame = snapshot.render(&BallScene::new(), &camera).unwrap();
+    assert_snapshot("src/lib/engn/test/snapshots/ball_rotated.txt", &frame);
+}
+
+#[test]
+fn snapshot_size() {
+    let snapshot = snapshot();
+    let camera = snapshot.camera(Point::new(vec![0.0, 0.0, 2.0]));
+    let frame = snapshot.render(&FloorScene::new(), &camera).unwrap();
+    assert_eq!(frame.len(), 11);
+    assert!(frame.iter().all(|line| line.chars().count() == 21));
+}
+
+#[test]
+fn diff_equal() {
+    let frame = vec!["ab".to_string(), "cd".to_string()];
+    assert_eq!(diff(&frame, &frame), None);
+}
+
+#[test]
+fn diff_marks_mismatch() {
+    let expected = vec!["ab".to_string(), "cd".to_string()];
+    let actual = vec!["ab".to_string(), "ce".to_string()];
+    assert_eq!(
+        diff(&expected, &actual).unwrap(),
+        "row 1:\n  expected |cd|\n  actual   |ce|\n             ^\n"
+    );
+}
+
+#[test]
+fn diff_rows_mismatch() {
+    let expected = vec!["ab".to_string()];
+    let actual = vec!["ab".to_string(), "cd".to_string()];
+    assert!(diff(&expected, &actual)
+        .unwrap()
+        .starts_with("expected 1 rows, actual 2 rows\n"));
+}
+
+#[test]
+fn bless_then_check() {
+    let path = env::temp_dir().join(format!("{}/bless.txt", Uuid::new_v4()));
+    let path = path.to_str().unwrap();
+    let frame = vec!["ab ".to_string(), " cd".to_string()];
+    check_snapshot(path, &frame, true).unwrap();
+    assert_eq!(read_to_string(path).unwrap(), "ab \n cd\n");
+    assert!(check_snapshot(path, &frame, false).is_ok());
+    assert!(check_snapshot(path, &frame[..1], false).is_err());
+}
+
+#[test]
+fn check_missing_snapshot() {
+    let path = env::temp_dir().join(format!("{}.txt", Uuid::new_v4()));
+    let frame = vec!["ab".to_string()];
+    assert!(check_snapshot(path.to_str().unwrap(), &frame, false)
+        .unwrap_err()
+        .contains(BLESS_VAR));
+}
+
+#[test]
+fn ball_half_block_snapshot() {
+    let mut snapshot = snapshot();
+    snapshot.conf.render_mode = RenderMode::HalfBlock;
+    let camera = snapshot.camera(Point::new(vec![0.0, 0.0, 2.0]));
+    let frame = snapshot.render(&BallScene::new(), &camera).unwrap();
+    assert_snapshot("src/lib/engn/test/snapshots/ball_half_block.txt", &frame);
+}
+
+#[test]
+fn ball_braille_snapshot() {
+    let mut snapshot = snapshot();
+    snapshot.conf.render_mode = RenderMode::Braille;
+    let camera = snapshot.camera(Point::new(vec![0.0, 0.0, 2.0]));
+    let frame = snapshot.render(&BallScene::new(), &camera).unwrap();
+    assert_snapshot("src/lib/engn/test/snapshots/ball_braille.txt", &frame);
+}
+
+#[test]
+fn ball_orthographic_overview_snapshot() {
+    let mut snapshot = snapshot();
+    snapshot.conf.projection = Projection::Orthographic;
+    snapshot.conf.view_width = 12.0;
+    let mut camera = snapshot.camera(Point::new(vec![8.0, 0.0, 10.0]));
+    camera.look(0.0, -std::f64::consts::FRAC_PI_2);
+    let frame = snapshot.render(&BallScene::new(), &camera).unwrap();
+    assert_snapshot("src/lib/engn/test/snapshots/ball_orthographic.txt", &frame);
+}
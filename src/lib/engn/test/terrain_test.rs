@@ -0,0 +1,208 @@
+use {
+    super::entity,
+    crate::{
+        conf::*,
+        engn::*,
+        errs::{GameErr::*, LoadErr::*, ReErr::*},
+        math::*,
+    },
+    std::{io::ErrorKind, path::Path, rc::Rc},
+};
+
+const HILLS: &str = "src/lib/engn/test/models/hills.pgm";
+
+/// Slope rising along x with gradient 0.5 over 4x4 cells of side 2
+fn slope() -> Terrain {
+    Terrain::generate(entity(), Point::new(vec![0.0; 3]), 2.0, [5, 5], |x, _| {
+        x / 2.0
+    })
+    .unwrap()
+}
+
+#[test]
+fn parse_pgm() {
+    let heights = Terrain::read_pgm(Path::new(HILLS)).unwrap();
+    assert_eq!((heights.rows(), heights.cols()), (3, 4));
+    assert_eq!(*heights.att(0, 1), 0.25);
+    assert_eq!(*heights.att(1, 2), 1.0);
+    assert_eq!(*heights.att(2, 3), 0.0);
+
+    let terrain = Terrain::from_pgm(entity(), HILLS, Point::new(vec![0.0; 3]), 1.0, 2.0).unwrap();
+    assert_eq!(terrain.size(), [3, 2]);
+    assert_eq!(terrain.height_at(1.0, 1.0), Some(2.0));
+    assert_eq!(terrain.height_at(1.5, 1.0), Some(2.0));
+    assert_eq!(terrain.height_at(0.5, 0.0), Some(0.25));
+}
+
+#[test]
+fn parse_pgm_errors() {
+    assert!(matches!(
+        Terrain::parse_pgm("P5\n1 1\n255\n"),
+        Err(LoadErr(UnsupportedFormat { line: 1, .. }))
+    ));
+    assert!(matches!(
+        Terrain::parse_pgm("P2\n2 2\n255\n0 1\n0 x\n"),
+        Err(LoadErr(InvalidNumber { line: 5, .. }))
+    ));
+    assert!(matches!(
+        Terrain::parse_pgm("P2\n2 2\n9\n0 1\n0 10\n"),
+        Err(LoadErr(InvalidNumber { line: 5, .. }))
+    ));
+    assert!(matches!(
+        Terrain::parse_pgm("P2\n2 2\n9\n0 1 2\n"),
+        Err(LoadErr(MissingValues {
+            line: 4,
+            expected: 4,
+            ..
+        }))
+    ));
+    assert!(matches!(
+        Terrain::parse_pgm("P2\n0 2\n9\n"),
+        Err(LoadErr(InvalidNumber { line: 2, .. }))
+    ));
+    assert!(matches!(
+        Terrain::parse_pgm("P2\n2\n0\n9\n"),
+        Err(LoadErr(InvalidNumber { line: 3, .. }))
+    ));
+    assert!(matches!(
+        Terrain::parse_pgm("P2\n65536 65536\n9\n0\n"),
+        Err(LoadErr(MissingValues {
+            line: 4,
+            expected: 4294967296,
+            ..
+        }))
+    ));
+    assert!(matches!(
+        Terrain::read_pgm("missing.pgm"),
+        Err(LoadErr(UnreadableFile(ErrorKind::NotFound)))
+    ));
+}
+
+#[test]
+fn terrain_too_small() {
+    let res = Terrain::generate(entity(), Point::new(vec![0.0; 3]), 1.0, [1, 3], |_, _| 0.0);
+    assert!(matches!(res, Err(GameErr(TerrainTooSmall((1, 3))))));
+    let res = Terrain::generate(entity(), Point::new(vec![0.0; 3]), 0.0, [2, 2], |_, _| 0.0);
+    assert!(matches!(
+        res,
+        Err(GameErr(NonPositiveSize { key: "cell", .. }))
+    ));
+}
+
+#[test]
+fn height_at() {
+    let terrain = slope();
+    assert_eq!(terrain.size(), [4, 4]);
+    assert_eq!(terrain.height_at(0.0, 0.0), Some(0.0));
+    assert_eq!(terrain.height_at(3.0, 5.0), Some(1.5));
+    assert_eq!(terrain.height_at(8.0, 8.0), Some(4.0));
+    assert_eq!(terrain.height_at(-0.1, 1.0), None);
+    assert_eq!(terrain.height_at(1.0, 8.1), None);
+}
+
+#[test]
+fn terrain_hit() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let terrain = slope();
+
+    let inc = Point::new(vec![-2.0, 3.0, 3.0]);
+    let hit = terrain
+        .hit(&cs, &inc, &Vector::new(vec![1.0, 0.0, 0.0]))
+        .unwrap();
+    assert!(aeq(&hit.dist, &8.0));
+    assert!(aeq(&hit.pt[0], &6.0));
+
+    let normal = hit.normal.unwrap();
+    let expected = [-1.0 / 5.0_f64.sqrt(), 0.0, 2.0 / 5.0_f64.sqrt()];
+    for i in 0..3 {
+        assert!((normal.at(i) - expected[i]).abs() < 1e-9);
+    }
+    let uv = hit.uv.unwrap();
+    assert!((uv.0 - 0.75).abs() < 1e-9 && (uv.1 - 0.375).abs() < 1e-9);
+
+    let down = AsCollided::collide(
+        &terrain,
+        &cs,
+        &Point::new(vec![5.0, 5.0, 10.0]),
+        &Vector::new(vec![0.0, 0.0, -1.0]),
+    );
+    assert!((down.unwrap() - 7.5).abs() < 1e-9);
+}
+
+#[test]
+fn terrain_miss() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let terrain = slope();
+    let up = Vector::new(vec![0.0, 0.0, 1.0]);
+    assert!(AsCollided::collide(&terrain, &cs, &Point::new(vec![4.0, 4.0, 5.0]), &up).is_none());
+    let over = Vector::new(vec![1.0, 1.0, 0.0]);
+    assert!(AsCollided::collide(&terrain, &cs, &Point::new(vec![0.0, 0.0, 5.0]), &over).is_none());
+    let aside = Vector::new(vec![0.0, 0.0, -1.0]);
+    assert!(AsCollided::collide(&terrain, &cs, &Point::new(vec![9.0, 4.0, 5.0]), &aside).is_none());
+}
+
+#[test]
+fn terrain_zero_dir() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let zero = Vector::new(vec![0.0; 3]);
+    assert!(AsCollided::collide(&slope(), &cs, &Point::new(vec![2.0, 2.0, 3.0]), &zero).is_none());
+}
+
+#[test]
+fn terrain_validate_mv() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let terrain = slope().with_eye(1.0);
+
+    let pos = Point::new(vec![2.0, 2.0, 2.0]);
+    let mut mv = Vector::new(vec![2.0, 0.0, 0.0]);
+    AsScene::validate_mv(&terrain, &cs, &pos, &mut mv);
+    assert!(aeq(&mv.at(2), &1.0));
+
+    let mut mv = Vector::new(vec![-2.0, 0.0, 0.0]);
+    AsScene::validate_mv(&terrain, &cs, &pos, &mut mv);
+    assert!(aeq(&mv.at(2), &-1.0));
+
+    let mut mv = Vector::new(vec![-4.0, 0.0, 0.0]);
+    AsScene::validate_mv(&terrain, &cs, &pos, &mut mv);
+    assert!(aeq(&mv.at(2), &0.0));
+}
+
+#[test]
+fn terrain_validate_mv_in_scenes() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let wall = HypePlane::new(
+        entity(),
+        Point::new(vec![5.0, 0.0, 0.0]),
+        Vector::new(vec![-1.0, 0.0, 0.0]),
+        None,
+    )
+    .unwrap();
+    let mut list = SceneList::new().with_radius(0.5);
+    list.add(Box::new(slope().with_eye(1.0)), None);
+    list.add(Box::new(wall), None);
+    let pos = Point::new(vec![2.0, 2.0, 2.0]);
+    let mut mv = Vector::new(vec![2.0, 0.0, 0.0]);
+    list.validate_mv(&cs, &pos, &mut mv);
+    assert!(aeq(&mv.at(0), &2.0) && aeq(&mv.at(2), &1.0));
+    let mut mv = Vector::new(vec![4.0, 0.0, 0.0]);
+    list.validate_mv(&cs, &pos, &mut mv);
+    assert!(aeq(&mv.at(0), &2.5) && aeq(&mv.at(2), &1.25));
+
+    let mut graph = SceneGraph::new();
+    graph
+        .add(
+            None,
+            SceneNode::new(entity(), Point::new(vec![0.0, 0.0, 1.0]))
+                .with_object(Box::new(slope().with_eye(1.0))),
+        )
+        .unwrap();
+    let pos = Point::new(vec![2.0, 2.0, 3.0]);
+    let mut mv = Vector::new(vec![2.0, 0.0, 0.0]);
+    graph.validate_mv(&cs, &pos, &mut mv);
+    assert!(aeq(&mv.at(0), &2.0) && aeq(&mv.at(2), &1.0));
+}
@@ -0,0 +1,261 @@
+use {
+    super::super::{snapshot::*, tilemap::EMPTY},
+    super::entity,
+    crate::{conf::*, engn::*, errs::*, math::*},
+    std::rc::Rc,
+};
+
+/// Square room 5x5 with the pillar in the cell (3, 1)
+fn room() -> TileMap {
+    TileMap::from_rows(
+        entity(),
+        Point::default(),
+        &["#####", "#..@#", "#...#", "#...#", "#####"],
+        2.0,
+        4.0,
+        vec![
+            ('#', Charcoal::new("#".to_string(), 30.0)),
+            ('@', Charcoal::new("@".to_string(), 30.0)),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn from_rows_tiles() {
+    let map = room();
+    assert_eq!(map.size(), [5, 5, 1]);
+    assert_eq!(map.get([0, 0, 0]), Some(1));
+    assert_eq!(map.get([2, 2, 0]), Some(EMPTY));
+    assert_eq!(map.get([3, 1, 0]), Some(2));
+    assert_eq!(map.get([5, 0, 0]), None);
+}
+
+#[test]
+fn from_rows_errors() {
+    let legend = || vec![('#', Charcoal::new("#".to_string(), 30.0))];
+    assert_eq!(
+        TileMap::from_rows(entity(), Point::default(), &["##", "#"], 1.0, 1.0, legend())
+            .unwrap_err(),
+        ReErr::GameErr(GameErr::IrregularTileMap)
+    );
+    assert_eq!(
+        TileMap::from_rows(entity(), Point::default(), &["#?"], 1.0, 1.0, legend()).unwrap_err(),
+        ReErr::GameErr(GameErr::UnknownTile('?'))
+    );
+}
+
+#[test]
+fn empty_map_crossed_by_nothing() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let down = Vector::new(vec![0.0, 0.0, -1.0]);
+    let inc = Point::new(vec![0.0, 0.0, 5.0]);
+    let rows = TileMap::from_rows(entity(), Point::default(), &[], 1.0, 1.0, vec![]).unwrap();
+    assert!(AsCollided::collide(&rows, &cs, &inc, &down).is_none());
+    let flat = TileMap::new(entity(), Point::default(), [2, 0, 2], [1.0; 3]);
+    assert!(AsCollided::collide(&flat, &cs, &inc, &down).is_none());
+}
+
+#[test]
+fn set_out_of_map() {
+    let mut map = TileMap::new(entity(), Point::default(), [2, 2, 2], [1.0; 3]);
+    assert!(map.set([1, 1, 1], 1).is_ok());
+    assert_eq!(
+        map.set([2, 0, 0], 1),
+        Err(ReErr::GameErr(GameErr::TileOutOfMap((2, 0, 0))))
+    );
+}
+
+#[test]
+fn hit_wall_from_inside() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let map = room();
+    let inc = Point::new(vec![5.0, 5.0, 1.0]);
+    let hit = map
+        .hit(&cs, &inc, &Vector::new(vec![-1.0, 0.0, 0.0]))
+        .unwrap();
+    assert_eq!(hit.dist, 3.0);
+    assert_eq!(hit.pt, Point::new(vec![2.0, 5.0, 1.0]));
+    assert_eq!(hit.normal, Some(Vector::new(vec![1.0, 0.0, 0.0])));
+    assert_eq!(hit.uv, Some((0.5, 0.25)));
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '#');
+    assert_eq!(map.hit_cell(&hit), Some([0, 2, 0]));
+}
+
+#[test]
+fn hit_pillar_diagonally() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let map = room();
+    let hit = map
+        .hit(
+            &cs,
+            &Point::new(vec![5.0, 7.0, 1.0]),
+            &Vector::new(vec![1.0, -2.0, 0.0]),
+        )
+        .unwrap();
+    assert_eq!(hit.dist, 1.5);
+    assert_eq!(hit.normal, Some(Vector::new(vec![0.0, 1.0, 0.0])));
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '@');
+    assert_eq!(map.hit_cell(&hit), Some([3, 1, 0]));
+}
+
+#[test]
+fn enter_from_outside() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let map = room();
+    let hit = map
+        .hit(
+            &cs,
+            &Point::new(vec![-3.0, 5.0, 1.0]),
+            &Vector::new(vec![1.0, 0.0, 0.0]),
+        )
+        .unwrap();
+    assert_eq!(hit.dist, 3.0);
+    assert_eq!(hit.normal, Some(Vector::new(vec![-1.0, 0.0, 0.0])));
+}
+
+#[test]
+fn miss_over_walls() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let map = room();
+    let dir = Vector::new(vec![1.0, 0.0, 2.0]);
+    assert!(map
+        .hit(&cs, &Point::new(vec![5.0, 5.0, 1.0]), &dir)
+        .is_none());
+    assert!(map
+        .hit(
+            &cs,
+            &Point::new(vec![-3.0, 5.0, 5.0]),
+            &Vector::new(vec![1.0, 0.0, 0.0])
+        )
+        .is_none());
+}
+
+#[test]
+fn start_inside_wall() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let map = room();
+    let hit = map
+        .hit(
+            &cs,
+            &Point::new(vec![1.0, 1.0, 1.0]),
+            &Vector::new(vec![1.0, 0.0, 0.0]),
+        )
+        .unwrap();
+    assert_eq!(hit.dist, 0.0);
+    assert_eq!(hit.normal, None);
+}
+
+#[test]
+fn voxel_floor() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut map = TileMap::new(entity(), Point::default(), [4, 4, 4], [1.0; 3]);
+    let stone = map.add_material(Charcoal::new("S".to_string(), 10.0));
+    for x in 0..4 {
+        for y in 0..4 {
+            map.set([x, y, 0], stone).unwrap();
+        }
+    }
+    let hit = map
+        .hit(
+            &cs,
+            &Point::new(vec![0.5, 0.5, 3.5]),
+            &Vector::new(vec![1.0, 1.0, -1.0]),
+        )
+        .unwrap();
+    assert_eq!(hit.dist, 2.5);
+    assert_eq!(hit.normal, Some(Vector::new(vec![0.0, 0.0, 1.0])));
+    assert_eq!(map.hit_cell(&hit), Some([3, 3, 0]));
+}
+
+#[test]
+fn huge_map_far_wall() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut map = TileMap::new(entity(), Point::default(), [2000, 3, 1], [1.0; 3]);
+    let wall = map.add_material(Charcoal::new("#".to_string(), 10.0));
+    map.set([1999, 1, 0], wall).unwrap();
+    let hit = map
+        .hit(
+            &cs,
+            &Point::new(vec![0.5, 1.5, 0.5]),
+            &Vector::new(vec![1.0, 0.0, 0.0]),
+        )
+        .unwrap();
+    assert_eq!(hit.dist, 1998.5);
+}
+
+#[test]
+fn validate_mv_stops_at_wall() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let map = room().with_radius(0.5);
+    let mut mv = Vector::new(vec![-2.0, 0.0, 0.0]);
+    map.validate_mv(&cs, &Point::new(vec![3.0, 5.0, 1.0]), &mut mv);
+    assert_eq!(mv, Vector::new(vec![-0.5, 0.0, 0.0]));
+}
+
+#[test]
+fn validate_zero_mv() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let map = room().with_radius(0.5);
+    let mut mv = Vector::new(vec![0.0, 0.0, 0.0]);
+    map.validate_mv(&cs, &Point::new(vec![3.0, 5.0, 1.0]), &mut mv);
+    assert_eq!(mv, Vector::new(vec![0.0, 0.0, 0.0]));
+    assert!(AsCollided::collide(&map, &cs, &Point::new(vec![3.0, 5.0, 1.0]), &mv).is_none());
+}
+
+#[test]
+fn tilemap_in_scene_list() {
+    set_biform_identity();
+    let cs = CoordSys::default();
+    let mut scene = SceneList::new();
+    scene.append(Box::new(room()));
+    let hit = scene
+        .collide(
+            &cs,
+            &Point::new(vec![5.0, 5.0, 1.0]),
+            &Vector::new(vec![0.0, 1.0, 0.0]),
+        )
+        .unwrap();
+    assert_eq!(hit.dist, 3.0);
+    assert_eq!(hit.charcoal.unwrap().ignite(0.0), '#');
+}
+
+#[test]
+fn tilemap_snapshot() {
+    set_biform_identity();
+    let mut conf = Conf::default();
+    conf.angle_discr = 4;
+    conf.wfov = 0.5;
+    conf.hfov = Some(0.25);
+    let snapshot = Snapshot::new(conf, (11, 21));
+    let map = TileMap::from_rows(
+        entity(),
+        Point::default(),
+        &["#####", "#...#", "#.@.#", "#...#", "#####"],
+        2.0,
+        3.0,
+        vec![
+            ('#', Charcoal::new("#%=-".to_string(), 30.0)),
+            ('@', Charcoal::new("@O*.".to_string(), 30.0)),
+        ],
+    )
+    .unwrap()
+    .with_lighting(
+        Lighting::new(0.3, 30.0)
+            .with_light(Light::directional(Vector::new(vec![1.0, 0.5, -1.0]), 0.7)),
+    )
+    .with_background(Cell::from(' '));
+    let camera = snapshot.camera(Point::new(vec![2.5, 3.0, 1.5]));
+    let frame = snapshot.render(&map, &camera).unwrap();
+    assert_snapshot("src/lib/engn/test/snapshots/tilemap.txt", &frame);
+}
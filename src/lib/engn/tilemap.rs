@@ -0,0 +1,310 @@
+//! Tile map is the uniform grid of box cells, each one is either empty or filled with some material.
+//! Rays traverse it cell by cell with Amanatides–Woo stepping, so the cost of ray query depends
+//! on the number of crossed cells and doesn't depend on the size of map
+
+use {
+    crate::{
+        engn::*,
+        errs::{GameErr::*, ReErr::*, ReRes},
+        math::*,
+    },
+    std::{collections::HashMap, rc::Rc},
+    uuid::Uuid,
+};
+
+/// Tile that is empty
+pub const EMPTY: usize = 0;
+
+/// Characters of empty tiles in maps given as rows of characters
+const EMPTY_CHARS: [char; 2] = [' ', '.'];
+
+/// Crossing of ray with the filled cell: distance, axis of the crossed cell face, cell position and its tile.
+/// Axis is `None` if ray starts inside the filled cell
+type Crossing = (f64, Option<usize>, [usize; 3], usize);
+
+/// Grid of cells of the given size along x, y, z axes starting from the least corner `origin`.
+/// Tile of the cell is `EMPTY` or index of material in the order they're added
+#[derive(Debug)]
+pub struct TileMap {
+    pub(crate) entity: Entity,
+    pub(crate) origin: Point,
+    pub(crate) size: [usize; 3],
+    pub(crate) cell: [f64; 3],
+    pub(crate) tiles: Vec<usize>,
+    pub(crate) materials: Vec<Charcoal>,
+    pub(crate) lighting: Option<Lighting>,
+    pub(crate) background: Option<Cell>,
+    pub(crate) radius: f64,
+}
+
+impl TileMap {
+    /// Empty map of `size` cells, each one is box with sides `cell`
+    pub fn new(entity: Entity, origin: Point, size: [usize; 3], cell: [f64; 3]) -> Self {
+        Self {
+            entity,
+            origin,
+            size,
+            cell,
+            tiles: vec![EMPTY; size[0] * size[1] * size[2]],
+            materials: vec![],
+            lighting: None,
+            background: None,
+            radius: 0.0,
+        }
+    }
+
+    /// Flat map of walls of the given `height` with single layer of square cells with side `cell`.
+    /// Character in column `x` of row `y` is the tile of cell `(x, y)`, it's `legend` character
+    /// or empty `' '` or `'.'`
+    pub fn from_rows(
+        entity: Entity,
+        origin: Point,
+        rows: &[&str],
+        cell: f64,
+        height: f64,
+        legend: Vec<(char, Charcoal)>,
+    ) -> ReRes<Self> {
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        if rows.iter().any(|row| row.chars().count() != width) {
+            return Err(GameErr(IrregularTileMap));
+        }
+        let mut map = Self::new(entity, origin, [width, rows.len(), 1], [cell, cell, height]);
+        let mut tiles = HashMap::new();
+        for (ch, charcoal) in legend {
+            tiles.insert(ch, map.add_material(charcoal));
+        }
+        for (y, row) in rows.iter().enumerate() {
+            for (x, ch) in row.chars().enumerate() {
+                match tiles.get(&ch) {
+                    Some(tile) => map.set([x, y, 0], *tile)?,
+                    None if EMPTY_CHARS.contains(&ch) => {}
+                    None => return Err(GameErr(UnknownTile(ch))),
+                }
+            }
+        }
+        Ok(map)
+    }
+
+    /// Surfaces are lit according to normals of cell faces, otherwise they're faded with distance
+    pub fn with_lighting(mut self, lighting: Lighting) -> Self {
+        self.lighting = Some(lighting);
+        self
+    }
+
+    /// Cell drawn where rays hit nothing
+    pub fn with_background(mut self, cell: Cell) -> Self {
+        self.background = Some(cell);
+        self
+    }
+
+    /// Distance that `validate_mv` keeps between moving point and filled cells
+    pub fn with_radius(mut self, radius: f64) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Adds material and returns its tile
+    pub fn add_material(&mut self, charcoal: Charcoal) -> usize {
+        self.materials.push(charcoal);
+        self.materials.len()
+    }
+
+    /// Charcoal of the given tile, `None` for `EMPTY` and unknown tiles
+    pub fn material(&self, tile: usize) -> Option<&Charcoal> {
+        match tile {
+            EMPTY => None,
+            tile => self.materials.get(tile - 1),
+        }
+    }
+
+    /// Number of cells along each axis
+    pub fn size(&self) -> [usize; 3] {
+        self.size
+    }
+
+    /// Tile of the cell in position `pos`, `None` if it's out of map
+    pub fn get(&self, pos: [usize; 3]) -> Option<usize> {
+        self.index(pos).map(|idx| self.tiles[idx])
+    }
+
+    /// Sets tile of the cell in position `pos`
+    pub fn set(&mut self, pos: [usize; 3], tile: usize) -> ReRes<()> {
+        match self.index(pos) {
+            Some(idx) => self.tiles[idx] = tile,
+            None => return Err(GameErr(TileOutOfMap((pos[0], pos[1], pos[2])))),
+        }
+        Ok(())
+    }
+
+    /// Position of the cell containing point `pt`, `None` if point is out of map
+    pub fn cell_of(&self, pt: &Point) -> Option<[usize; 3]> {
+        let mut pos = [0; 3];
+        for i in 0..3 {
+            let c = ((pt[i] - self.origin[i]) / self.cell[i]).floor();
+            if c < 0.0 || self.size[i] as f64 <= c {
+                return None;
+            }
+            pos[i] = c as usize;
+        }
+        Some(pos)
+    }
+
+    /// Position of the filled cell that is hit, `None` if `hit` isn't the hit of this map
+    pub fn hit_cell(&self, hit: &Hit) -> Option<[usize; 3]> {
+        if !Rc::ptr_eq(&hit.id, self.entity.id()) {
+            return None;
+        }
+        match &hit.normal {
+            Some(normal) => {
+                let eps = self.cell.iter().copied().fold(f64::INFINITY, f64::min) / 2.0;
+                self.cell_of(&hit.pt.clone().mv(&normal.clone().resize(-eps)).ok()?)
+            }
+            None => self.cell_of(&hit.pt),
+        }
+    }
+
+    fn index(&self, pos: [usize; 3]) -> Option<usize> {
+        if (0..3).any(|i| self.size[i] <= pos[i]) {
+            return None;
+        }
+        Some(pos[0] + self.size[0] * (pos[1] + self.size[1] * pos[2]))
+    }
+
+    /// Enters map by slab method and steps from cell to cell until the filled one is found.
+    /// Zero direction and map without cells cross nothing
+    fn traverse(&self, inc: &Point, dir: &Vector) -> Option<Crossing> {
+        let (o, d) = ([inc[0], inc[1], inc[2]], [dir.at(0), dir.at(1), dir.at(2)]);
+        if d.iter().all(|x| *x == 0.0) || self.size.contains(&0) {
+            return None;
+        }
+        let lo = [self.origin[0], self.origin[1], self.origin[2]];
+
+        let (mut enter, mut exit, mut enter_axis) = (f64::NEG_INFINITY, f64::INFINITY, None);
+        for i in 0..3 {
+            let hi = lo[i] + self.cell[i] * self.size[i] as f64;
+            if d[i] == 0.0 {
+                if o[i] < lo[i] || hi < o[i] {
+                    return None;
+                }
+                continue;
+            }
+            let (t1, t2) = ((lo[i] - o[i]) / d[i], (hi - o[i]) / d[i]);
+            if t1.min(t2) > enter {
+                (enter, enter_axis) = (t1.min(t2), Some(i));
+            }
+            exit = exit.min(t1.max(t2));
+        }
+        if enter > exit || exit < 0.0 {
+            return None;
+        }
+        let (mut t, mut axis) = match enter > 0.0 {
+            true => (enter, enter_axis),
+            false => (0.0, None),
+        };
+
+        let (mut pos, mut step) = ([0_isize; 3], [0_isize; 3]);
+        let (mut next, mut delta) = ([f64::INFINITY; 3], [f64::INFINITY; 3]);
+        for i in 0..3 {
+            let c = ((o[i] + t * d[i] - lo[i]) / self.cell[i]).floor() as isize;
+            pos[i] = c.clamp(0, self.size[i] as isize - 1);
+            if d[i] > 0.0 {
+                step[i] = 1;
+                next[i] = (lo[i] + (pos[i] + 1) as f64 * self.cell[i] - o[i]) / d[i];
+                delta[i] = self.cell[i] / d[i];
+            } else if d[i] < 0.0 {
+                step[i] = -1;
+                next[i] = (lo[i] + pos[i] as f64 * self.cell[i] - o[i]) / d[i];
+                delta[i] = -self.cell[i] / d[i];
+            }
+        }
+
+        loop {
+            let cell = [pos[0] as usize, pos[1] as usize, pos[2] as usize];
+            let tile = self.get(cell)?;
+            if tile != EMPTY {
+                return Some((t, axis, cell, tile));
+            }
+            let i = (0..3).min_by_key(|i| Float(next[*i])).unwrap();
+            if !next[i].is_finite() || next[i] > exit {
+                return None;
+            }
+            (t, axis) = (next[i], Some(i));
+            next[i] += delta[i];
+            pos[i] += step[i];
+            if pos[i] < 0 || self.size[i] as isize <= pos[i] {
+                return None;
+            }
+        }
+    }
+}
+
+impl AsEntity for TileMap {
+    fn id(&self) -> &Rc<Uuid> {
+        self.entity.id()
+    }
+
+    fn props(&self) -> &HashMap<PropKey, PropVal> {
+        self.entity.props()
+    }
+
+    fn props_mut(&mut self) -> &mut HashMap<PropKey, PropVal> {
+        self.entity.props_mut()
+    }
+}
+
+impl AsCollided for TileMap {
+    fn collide(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<f64> {
+        self.traverse(inc, dir).map(|(t, ..)| t)
+    }
+
+    fn charmap(&self, _dist: f64) -> Option<char> {
+        None
+    }
+
+    fn bounds(&self) -> Option<Aabb> {
+        Some(Aabb::new(
+            (0..3).map(|i| self.origin[i]).collect(),
+            (0..3)
+                .map(|i| self.origin[i] + self.cell[i] * self.size[i] as f64)
+                .collect(),
+        ))
+    }
+
+    /// Hit with normal of the crossed cell face, texture coordinates within this face
+    /// and charcoal of the cell material
+    fn hit(&self, _cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        let (t, axis, _, tile) = self.traverse(inc, dir)?;
+        let mut hit = Hit::new(inc, dir, t, Rc::clone(self.entity.id()));
+        if let Some(i) = axis {
+            let mut normal = Vector::new(vec![0.0; 3]);
+            *normal.at_mut(i) = -dir.at(i).signum();
+            let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+            let frac = |j: usize| ((hit.pt[j] - self.origin[j]) / self.cell[j]).rem_euclid(1.0);
+            let uv = (frac(j.min(k)), frac(j.max(k)));
+            hit = hit.with_normal(normal).with_uv(uv);
+        }
+        hit.charcoal = self.material(tile);
+        Some(hit)
+    }
+}
+
+impl AsScene for TileMap {
+    fn collide(&self, cs: &CoordSys, inc: &Point, dir: &Vector) -> Option<Hit<'_>> {
+        self.hit(cs, inc, dir)
+    }
+
+    fn shade(&self, cs: &CoordSys, dir: &Vector, hit: &Hit) -> Option<Cell> {
+        hit.shade(self.lighting.as_ref(), cs, dir)
+    }
+
+    fn background(&self, _cs: &CoordSys, _dir: &Vector) -> Option<Cell> {
+        self.background
+    }
+
+    /// Shortens `mv` to stop at `radius` before the nearest filled cell on the way
+    fn validate_mv(&self, cs: &CoordSys, pos: &Point, mv: &mut Vector) {
+        if let Some(hit) = self.hit(cs, pos, mv) {
+            hit.stop(cs, mv, self.radius);
+        }
+    }
+}
@@ -17,4 +17,40 @@ pub enum GameErr {
 
     #[error("failed to parse value with key {0}")]
     InvalidConfValue(&'static str),
+
+    #[error("tile {0:?} isn't found in legend of tile map")]
+    UnknownTile(char),
+
+    #[error("rows of tile map have different lengths")]
+    IrregularTileMap,
+
+    #[error("tile position {0:?} is out of tile map")]
+    TileOutOfMap((usize, usize, usize)),
+
+    #[error("mesh face refers to vertex {0} that doesn't exist")]
+    InvalidMeshIndex(usize),
+
+    #[error("vertex {0} of polygon doesn't lie on its plane")]
+    VertexOffPlane(usize),
+
+    #[error("polygon vertices don't make convex polygon")]
+    NonConvexPolygon,
+
+    #[error("terrain of {0:?} vertices has no cells, at least 2x2 vertices are required")]
+    TerrainTooSmall((usize, usize)),
+
+    #[error("operand {0} of CSG node isn't solid")]
+    NonSolidOperand(uuid::Uuid),
+
+    #[error("{key} must be positive, got {val}")]
+    NonPositiveSize { key: &'static str, val: f64 },
+
+    #[error("torus tube of radius {minor} is wider than its circle of radius {major}")]
+    SelfCrossingTorus { major: f64, minor: f64 },
+
+    #[error("node {0} isn't found in scene graph")]
+    UnknownNode(uuid::Uuid),
+
+    #[error("node {0} can't be parent of its own ancestor")]
+    CyclicNode(uuid::Uuid),
 }
@@ -0,0 +1,27 @@
+use {std::io::ErrorKind, thiserror::Error};
+
+/// Errors of loading assets from files that can be obtained within `ReRes::ReErr::LoadErr`.
+/// Malformed input is reported with line number starting from 1
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum LoadErr {
+    #[error("failed to read file: {0}")]
+    UnreadableFile(ErrorKind),
+
+    #[error("line {line}: failed to parse number")]
+    InvalidNumber { line: usize },
+
+    #[error("line {line}: expected {expected} coordinates")]
+    MissingCoords { line: usize, expected: usize },
+
+    #[error("line {line}: expected {expected} values")]
+    MissingValues { line: usize, expected: usize },
+
+    #[error("line {line}: unsupported file format")]
+    UnsupportedFormat { line: usize },
+
+    #[error("line {line}: face has less than 3 vertices")]
+    DegenerateFace { line: usize },
+
+    #[error("line {line}: index {idx} refers to element that doesn't exist")]
+    InvalidIndex { line: usize, idx: isize },
+}
@@ -2,9 +2,10 @@
 
 mod engn_errs;
 mod grid_errs;
+mod load_errs;
 mod math_errs;
 
-pub use {engn_errs::GameErr, grid_errs::GridErr, math_errs::MathErr};
+pub use {engn_errs::GameErr, grid_errs::GridErr, load_errs::LoadErr, math_errs::MathErr};
 use {std::io, strum_macros::Display, thiserror::Error};
 
 /// `Result` with `ReErr` as `Err` variant
@@ -17,6 +18,7 @@ pub enum ReErr {
     GameErr(GameErr),
     GridErr(GridErr),
     MathErr(MathErr),
+    LoadErr(LoadErr),
     IoError,
 }
 
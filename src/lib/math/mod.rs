@@ -2,8 +2,10 @@
 //! 1. `Matrix` that is just `Grid<f64>`
 //! 2. Precision features like roundation and approximate equality
 //! 3. Types related to analytical geometry like `VectorSpace`, `Point`, `CoordSys`
+//! 4. Real roots of polynomials up to the fourth degree
 
 pub mod matrix;
+pub mod poly;
 pub mod precision;
 pub mod space;
 
@@ -17,6 +19,7 @@ use std::{
 
 pub use {
     matrix::{set_biform, set_biform_identity, set_biform_vec, Matrix},
+    poly::{solve_cubic, solve_linear, solve_quadratic, solve_quartic},
     precision::{aeq, round, set_exact_mode, set_precision, set_round_mode},
     space::{Basis, CoordSys, Point, Vector},
 };
@@ -36,11 +39,17 @@ pub(crate) fn pow_minus(x: usize) -> f64 {
     }
 }
 
-#[derive(Debug, PartialOrd, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Float(pub f64);
 
 impl Eq for Float {}
 
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
 impl Ord for Float {
     fn cmp(&self, other: &Self) -> Ordering {
         self.partial_cmp(other).unwrap()
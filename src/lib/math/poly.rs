@@ -0,0 +1,126 @@
+//! Real roots of polynomials up to the fourth degree, that are used to intersect rays with
+//! quadric and quartic surfaces. Coefficients are given from the highest degree, roots are sorted ascending.
+//! Polynomial of lower degree is solved if the leading coefficient is zero
+
+use std::f64::consts::PI;
+
+/// Tolerance of vanishing coefficients and discriminants
+const EPS: f64 = 1e-12;
+
+/// Number of Newton iterations polishing roots of quartic
+const POLISH_STEPS: usize = 2;
+
+fn sorted(mut roots: Vec<f64>) -> Vec<f64> {
+    roots.retain(|root| root.is_finite());
+    roots.sort_by(f64::total_cmp);
+    roots
+}
+
+/// Roots of `a * x + b`, there are no roots if `a` is zero
+pub fn solve_linear(a: f64, b: f64) -> Vec<f64> {
+    match a.abs() < EPS {
+        true => vec![],
+        false => vec![-b / a],
+    }
+}
+
+/// Roots of `a * x^2 + b * x + c`, double root is returned once.
+/// Computed without cancellation of close numbers
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a.abs() < EPS {
+        return solve_linear(b, c);
+    }
+    let d = b * b - 4.0 * a * c;
+    let scale = (b * b).max((4.0 * a * c).abs()).max(EPS);
+    if d.abs() < EPS * scale {
+        return vec![-b / 2.0 / a];
+    } else if d < 0.0 {
+        return vec![];
+    }
+    let q = -0.5 * (b + b.signum() * d.sqrt());
+    match q == 0.0 {
+        true => sorted(vec![(-c / a).sqrt(), -(-c / a).sqrt()]),
+        false => sorted(vec![q / a, c / q]),
+    }
+}
+
+/// Roots of `a * x^3 + b * x^2 + c * x + d` by Cardano formula or trigonometric one
+/// if there are three real roots
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if a.abs() < EPS {
+        return solve_quadratic(b, c, d);
+    }
+    let (b, c, d) = (b / a, c / a, d / a);
+    let shift = -b / 3.0;
+    let p = c - b * b / 3.0;
+    let q = 2.0 * b * b * b / 27.0 - b * c / 3.0 + d;
+    let disc = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+
+    let roots = if p.abs() < EPS && q.abs() < EPS {
+        vec![0.0]
+    } else if disc.abs() < EPS {
+        vec![3.0 * q / p, -1.5 * q / p]
+    } else if disc > 0.0 {
+        let sqrt = disc.sqrt();
+        vec![(-q / 2.0 + sqrt).cbrt() + (-q / 2.0 - sqrt).cbrt()]
+    } else {
+        let m = 2.0 * (-p / 3.0).sqrt();
+        let phi = (3.0 * q / p / m).clamp(-1.0, 1.0).acos() / 3.0;
+        (0..3)
+            .map(|k| m * (phi - 2.0 * PI * k as f64 / 3.0).cos())
+            .collect()
+    };
+    sorted(roots.into_iter().map(|root| root + shift).collect())
+}
+
+/// Roots of `a * x^4 + b * x^3 + c * x^2 + d * x + e` by Ferrari method factoring depressed quartic
+/// into two quadratics with root of resolvent cubic. Roots are polished by Newton iterations
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    if a.abs() < EPS {
+        return solve_cubic(b, c, d, e);
+    }
+    let (b, c, d, e) = (b / a, c / a, d / a, e / a);
+    let shift = -b / 4.0;
+    let p = c - 3.0 * b * b / 8.0;
+    let q = d - b * c / 2.0 + b * b * b / 8.0;
+    let r = e - b * d / 4.0 + b * b * c / 16.0 - 3.0 * b.powi(4) / 256.0;
+
+    let roots = if q.abs() < EPS {
+        solve_quadratic(1.0, p, r)
+            .into_iter()
+            .filter(|z| *z >= -EPS)
+            .flat_map(|z| {
+                let y = z.max(0.0).sqrt();
+                [y, -y]
+            })
+            .collect::<Vec<f64>>()
+    } else {
+        let m = solve_cubic(1.0, p, p * p / 4.0 - r, -q * q / 8.0)
+            .into_iter()
+            .fold(0.0, f64::max);
+        let s = (2.0 * m).sqrt();
+        let mut roots = solve_quadratic(1.0, -s, p / 2.0 + m + q / 2.0 / s);
+        roots.extend(solve_quadratic(1.0, s, p / 2.0 + m - q / 2.0 / s));
+        roots
+    };
+
+    let f = |x: f64| (((x + b) * x + c) * x + d) * x + e;
+    let df = |x: f64| ((4.0 * x + 3.0 * b) * x + 2.0 * c) * x + d;
+    let roots = roots
+        .into_iter()
+        .map(|root| {
+            let mut x = root + shift;
+            for _ in 0..POLISH_STEPS {
+                let slope = df(x);
+                if slope.abs() < EPS {
+                    break;
+                }
+                x -= f(x) / slope;
+            }
+            x
+        })
+        .collect::<Vec<f64>>();
+    let mut roots = sorted(roots);
+    roots.dedup_by(|l, r| (*l - *r).abs() < 1e-9);
+    roots
+}
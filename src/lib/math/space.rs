@@ -9,7 +9,10 @@ use {
         },
         grid::Repr::{self, *},
     },
-    std::ops::Index,
+    std::{
+        cell::{Ref, RefCell},
+        ops::Index,
+    },
 };
 
 /// Vector that is `Matrix::Col`
@@ -140,11 +143,12 @@ impl Index<usize> for Point {
     }
 }
 
-/// Basis that is `Matrix::MultiCol`, square, linear independence
+/// Basis that is `Matrix::MultiCol`, square, linear independence.
+/// Inverse is cached, it's computed again after the matrix is changed with `Basis::basis_mut`
 #[derive(Debug, Clone, PartialEq)]
 pub struct Basis {
     pub(crate) basis: Matrix,
-    pub(crate) inv: Matrix,
+    pub(crate) inv: RefCell<Option<Matrix>>,
 }
 
 impl Basis {
@@ -155,14 +159,33 @@ impl Basis {
         if basis.is_multirow() {
             basis = basis.transpose();
         }
-        Ok(Self { basis, inv })
+        Ok(Self {
+            basis,
+            inv: RefCell::new(Some(inv)),
+        })
     }
 
-    /// Decompose point in current basis
-    pub fn decompose(&self, pt: &Point) -> Vector {
-        Vector {
-            coord: pt.coord.mul_left(&self.inv).to_col(),
+    /// Mutable matrix of basis, its inverse is computed again when it's needed next time
+    pub fn basis_mut(&mut self) -> &mut Matrix {
+        *self.inv.get_mut() = None;
+        &mut self.basis
+    }
+
+    /// Inverse of basis matrix, `None` if the matrix changed with `basis_mut` is degenerate
+    pub fn inv(&self) -> Option<Ref<'_, Matrix>> {
+        if self.inv.borrow().is_none() {
+            *self.inv.borrow_mut() = self.basis.inv().ok();
         }
+        Ref::filter_map(self.inv.borrow(), Option::as_ref).ok()
+    }
+
+    /// Decompose point in current basis, coordinates are failed if basis is degenerate
+    pub fn decompose(&self, pt: &Point) -> Vector {
+        let coord = match self.inv() {
+            Some(inv) => pt.coord.mul_left(&inv).to_col(),
+            None => Matrix::Failure(MathErr(NullDeterminant)),
+        };
+        Vector { coord }
     }
 }
 
@@ -170,7 +193,7 @@ impl Default for Basis {
     fn default() -> Self {
         Basis {
             basis: Matrix::identity(3).to_multicol(),
-            inv: Matrix::identity(3),
+            inv: RefCell::new(Some(Matrix::identity(3))),
         }
     }
 }
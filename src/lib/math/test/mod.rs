@@ -9,3 +9,6 @@ mod vector_test;
 
 #[cfg(test)]
 mod space_test;
+
+#[cfg(test)]
+mod poly_test;
@@ -0,0 +1,70 @@
+use super::super::poly::*;
+
+fn assert_roots(roots: Vec<f64>, expected: &[f64]) {
+    assert_eq!(roots.len(), expected.len(), "roots {:?}", roots);
+    for (root, exp) in roots.iter().zip(expected) {
+        assert!((root - exp).abs() < 1e-9, "roots {:?}", roots);
+    }
+}
+
+#[test]
+fn linear_roots() {
+    assert_roots(solve_linear(2.0, -4.0), &[2.0]);
+    assert_roots(solve_linear(0.0, 1.0), &[]);
+}
+
+#[test]
+fn quadratic_roots() {
+    assert_roots(solve_quadratic(1.0, -3.0, 2.0), &[1.0, 2.0]);
+    assert_roots(solve_quadratic(1.0, 2.0, 1.0), &[-1.0]);
+    assert_roots(solve_quadratic(1.0, 0.0, 1.0), &[]);
+    assert_roots(solve_quadratic(1.0, 0.0, -4.0), &[-2.0, 2.0]);
+    assert_roots(solve_quadratic(0.0, 2.0, -1.0), &[0.5]);
+}
+
+#[test]
+fn quadratic_no_cancellation() {
+    let roots = solve_quadratic(1.0, -1e8, 1.0);
+    assert!((roots[0] - 1e-8).abs() < 1e-20);
+}
+
+#[test]
+fn cubic_roots() {
+    assert_roots(solve_cubic(1.0, -6.0, 11.0, -6.0), &[1.0, 2.0, 3.0]);
+    assert_roots(solve_cubic(2.0, 0.0, 0.0, -16.0), &[2.0]);
+    assert_roots(solve_cubic(1.0, -3.0, 3.0, -1.0), &[1.0]);
+    assert_roots(solve_cubic(1.0, 0.0, -3.0, 2.0), &[-2.0, 1.0]);
+    assert_roots(solve_cubic(0.0, 1.0, -3.0, 2.0), &[1.0, 2.0]);
+}
+
+#[test]
+fn quartic_roots() {
+    // (x - 1)(x - 2)(x - 3)(x - 4)
+    assert_roots(
+        solve_quartic(1.0, -10.0, 35.0, -50.0, 24.0),
+        &[1.0, 2.0, 3.0, 4.0],
+    );
+    // biquadratic (x^2 - 1)(x^2 - 4)
+    assert_roots(
+        solve_quartic(1.0, 0.0, -5.0, 0.0, 4.0),
+        &[-2.0, -1.0, 1.0, 2.0],
+    );
+    // (x^2 + 1)(x - 1)(x + 3)
+    assert_roots(solve_quartic(1.0, 2.0, -2.0, 2.0, -3.0), &[-3.0, 1.0]);
+    assert_roots(solve_quartic(1.0, 0.0, 0.0, 0.0, 1.0), &[]);
+    assert_roots(solve_quartic(0.0, 1.0, -6.0, 11.0, -6.0), &[1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn quartic_scaled_roots() {
+    // 3 (x + 0.5)(x - 0.25)(x - 7)(x - 8)
+    let (a, b, c, d) = (-0.5, 0.25, 7.0, 8.0);
+    let s1 = a + b + c + d;
+    let s2 = a * b + a * c + a * d + b * c + b * d + c * d;
+    let s3 = a * b * c + a * b * d + a * c * d + b * c * d;
+    let s4 = a * b * c * d;
+    assert_roots(
+        solve_quartic(3.0, -3.0 * s1, 3.0 * s2, -3.0 * s3, 3.0 * s4),
+        &[-0.5, 0.25, 7.0, 8.0],
+    );
+}
@@ -99,3 +99,15 @@ fn point_decomposition_in_basis() {
     let point = Point::new(vec![5.0, 5.0, 5.0]);
     assert_eq!(basis.decompose(&point), Vector::new(vec![2.5, 2.5, 2.5]));
 }
+
+#[test]
+fn point_decomposition_in_changed_basis() {
+    set_biform_identity();
+    let mut basis = Basis::new(Matrix::identity(3).num_mul_assign(2.0).to_multicol()).unwrap();
+    *basis.basis_mut() = Matrix::identity(3).num_mul_assign(4.0).to_multicol();
+    let point = Point::new(vec![5.0, 5.0, 5.0]);
+    assert_eq!(basis.decompose(&point), Vector::new(vec![1.25, 1.25, 1.25]));
+    *basis.basis_mut() = Matrix::zero(3, 3).to_multicol();
+    assert!(basis.inv().is_none());
+    assert!(basis.decompose(&point).coord.ag_failed().is_err());
+}